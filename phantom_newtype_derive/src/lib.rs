@@ -0,0 +1,287 @@
+// Copyright 2024 Peter Lyons Kehl
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Derive macros for unit (and entity) marker types of `phantom_newtype`. Use them through
+//! `phantom_newtype` with its feature `derive`, rather than depending on this crate directly.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::{parse_macro_input, DeriveInput, Error, Expr, Ident, LitStr, Path, Type};
+
+/// The wrapper aliases (one per combination of trait flags) that the generated impls cover.
+const WRAPPERS: &[&str] = &[
+    "Amount",
+    "AmountNoCopy",
+    "AmountNoDefault",
+    "AmountNoCopyNoDefault",
+    "Id",
+    "IdNoCopy",
+    "IdNoDefault",
+    "IdNoCopyNoDefault",
+    "Instant",
+    "InstantNoCopy",
+    "InstantNoDefault",
+    "InstantNoCopyNoDefault",
+];
+
+/// Implement `DisplayerOf` for amounts, ids and instants of the marker, formatting the `Repr`
+/// with `#[newtype_display(format = "...")]` (one `{}` placeholder).
+#[proc_macro_derive(NewtypeDisplay, attributes(newtype_display))]
+pub fn derive_newtype_display(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    newtype_display(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// Implement `serde_unit::UnitName` for the marker. The name is the marker's name, unless set by
+/// `#[unit_name(name = "...")]`. Former names are listed with `#[unit_name(alias = "...")]`,
+/// repeatedly.
+#[proc_macro_derive(UnitName, attributes(unit_name))]
+pub fn derive_unit_name(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    unit_name(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// Implement `validate::ValidatorOf` for amounts, ids and instants of the marker with `Repr` set by
+/// `#[newtype_validate(repr = "...")]`. They are checked against `min = ...` and `max = ...`
+/// (inclusive), and then by `with = "..."`, a function taking `&Repr` and returning
+/// `Result<(), ValidationError>`. Each of them is optional, but at least one is needed.
+#[proc_macro_derive(NewtypeValidate, attributes(newtype_validate))]
+pub fn derive_newtype_validate(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    newtype_validate(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// Implement `UnitLabel` for the marker, and with it `DisplayerOf` and `DebugerOf` of its amounts
+/// and instants, like `phantom_newtype::unit_label!`. The symbol is set by
+/// `#[unit_label(symbol = "...")]`. The name is the marker's name, unless set by
+/// `#[unit_label(name = "...")]`.
+#[proc_macro_derive(UnitLabel, attributes(unit_label))]
+pub fn derive_unit_label(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    unit_label(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn newtype_display(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let mut format = None;
+    for attr in &input.attrs {
+        if attr.path().is_ident("newtype_display") {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("format") {
+                    format = Some(meta.value()?.parse::<LitStr>()?);
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `format = \"...\"`"))
+                }
+            })?;
+        }
+    }
+    let format = format.ok_or_else(|| {
+        Error::new(
+            Span::call_site(),
+            "`#[derive(NewtypeDisplay)]` needs `#[newtype_display(format = \"...\")]`",
+        )
+    })?;
+
+    let marker = &input.ident;
+    let (_, type_generics, where_clause) = input.generics.split_for_impl();
+    let params = trailing_comma(&input.generics.params);
+    let impls = WRAPPERS.iter().map(|wrapper| {
+        let wrapper = Ident::new(wrapper, Span::call_site());
+        quote! {
+            #[allow(deprecated)]
+            impl<#params __Repr: ::core::fmt::Display>
+                ::phantom_newtype::DisplayerOf<
+                    ::phantom_newtype::#wrapper<#marker #type_generics, __Repr>
+                > for #marker #type_generics #where_clause
+            {
+                fn display(
+                    value: &::phantom_newtype::#wrapper<#marker #type_generics, __Repr>,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    ::core::write!(f, #format, value)
+                }
+            }
+        }
+    });
+    Ok(quote! { #(#impls)* })
+}
+
+/// Generic parameters followed by a comma, if any, to prepend to more parameters.
+fn trailing_comma<T: quote::ToTokens, P: quote::ToTokens>(
+    params: &syn::punctuated::Punctuated<T, P>,
+) -> TokenStream2 {
+    if params.is_empty() {
+        quote! {}
+    } else {
+        quote! { #params, }
+    }
+}
+
+fn unit_name(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let marker = &input.ident;
+    let mut name = LitStr::new(&marker.to_string(), marker.span());
+    let mut aliases = Vec::new();
+    for attr in &input.attrs {
+        if attr.path().is_ident("unit_name") {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("name") {
+                    name = meta.value()?.parse()?;
+                    Ok(())
+                } else if meta.path.is_ident("alias") {
+                    aliases.push(meta.value()?.parse::<LitStr>()?);
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `name = \"...\"` or `alias = \"...\"`"))
+                }
+            })?;
+        }
+    }
+
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::phantom_newtype::serde_unit::UnitName
+            for #marker #type_generics #where_clause
+        {
+            const NAME: &'static str = #name;
+            const ALIASES: &'static [&'static str] = &[#(#aliases),*];
+        }
+    })
+}
+
+fn newtype_validate(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let (mut repr, mut min, mut max, mut with) = (None, None, None, None);
+    for attr in &input.attrs {
+        if attr.path().is_ident("newtype_validate") {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("repr") {
+                    repr = Some(meta.value()?.parse::<LitStr>()?.parse::<Type>()?);
+                } else if meta.path.is_ident("min") {
+                    min = Some(meta.value()?.parse::<Expr>()?);
+                } else if meta.path.is_ident("max") {
+                    max = Some(meta.value()?.parse::<Expr>()?);
+                } else if meta.path.is_ident("with") {
+                    with = Some(meta.value()?.parse::<LitStr>()?.parse::<Path>()?);
+                } else {
+                    return Err(meta.error(
+                        "expected `repr = \"...\"`, `min = ...`, `max = ...` or `with = \"...\"`",
+                    ));
+                }
+                Ok(())
+            })?;
+        }
+    }
+    let repr = repr.ok_or_else(|| {
+        Error::new(
+            Span::call_site(),
+            "`#[derive(NewtypeValidate)]` needs `#[newtype_validate(repr = \"...\")]`",
+        )
+    })?;
+    if min.is_none() && max.is_none() && with.is_none() {
+        return Err(Error::new(
+            Span::call_site(),
+            "`#[derive(NewtypeValidate)]` needs `min = ...`, `max = ...` or `with = \"...\"`",
+        ));
+    }
+
+    let validate = quote! { ::phantom_newtype::validate };
+    let min = min.map(|min| {
+        quote! {
+            if *repr < #min {
+                return ::core::result::Result::Err(#validate::ValidationError::TooSmall);
+            }
+        }
+    });
+    let max = max.map(|max| {
+        quote! {
+            if *repr > #max {
+                return ::core::result::Result::Err(#validate::ValidationError::TooLarge);
+            }
+        }
+    });
+    let with = match with {
+        Some(with) => quote! { #with(repr) },
+        None => quote! { ::core::result::Result::Ok(()) },
+    };
+
+    let marker = &input.ident;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+    let impls = WRAPPERS.iter().map(|wrapper| {
+        let wrapper = Ident::new(wrapper, Span::call_site());
+        quote! {
+            #[allow(deprecated)]
+            impl #impl_generics
+                #validate::ValidatorOf<::phantom_newtype::#wrapper<#marker #type_generics, #repr>>
+                for #marker #type_generics #where_clause
+            {
+                fn validate(
+                    value: &::phantom_newtype::#wrapper<#marker #type_generics, #repr>,
+                ) -> ::core::result::Result<(), #validate::ValidationError> {
+                    let repr: &#repr = value.get();
+                    #min
+                    #max
+                    #with
+                }
+            }
+        }
+    });
+    Ok(quote! { #(#impls)* })
+}
+
+fn unit_label(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let marker = &input.ident;
+    if !input.generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            &input.generics,
+            "`#[derive(UnitLabel)]` doesn't support generic markers",
+        ));
+    }
+    let mut symbol = None;
+    let mut name = LitStr::new(&marker.to_string(), marker.span());
+    for attr in &input.attrs {
+        if attr.path().is_ident("unit_label") {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("symbol") {
+                    symbol = Some(meta.value()?.parse::<LitStr>()?);
+                    Ok(())
+                } else if meta.path.is_ident("name") {
+                    name = meta.value()?.parse()?;
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `symbol = \"...\"` or `name = \"...\"`"))
+                }
+            })?;
+        }
+    }
+    let symbol = symbol.ok_or_else(|| {
+        Error::new(
+            Span::call_site(),
+            "`#[derive(UnitLabel)]` needs `#[unit_label(symbol = \"...\")]`",
+        )
+    })?;
+
+    Ok(quote! {
+        ::phantom_newtype::unit_label! {
+            #marker { symbol: #symbol, name: #name }
+        }
+    })
+}
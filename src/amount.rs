@@ -13,14 +13,19 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::displayer::{DisplayProxy, DisplayerOf};
+use crate::displayer::{
+    unit_name, DebugProxy, DebugerOf, DisplayProxy, DisplayerOf, GroupedDisplay, SiDisplay,
+    UnitLabel,
+};
+use crate::ops::{AddTo, SubFrom};
+use crate::repr::{ConstDefault, Scale, ToF64};
 #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
 use crate::trait_flag::{self, TraitFlags};
 use core::cmp::Ordering;
 use core::fmt;
 use core::hash::{Hash, Hasher};
 use core::marker::PhantomData;
-use core::ops::{Add, AddAssign, Div, Mul, MulAssign, Sub, SubAssign};
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
@@ -30,16 +35,7 @@ use serde::{Deserialize, Deserializer, Serialize, Serializer};
 ///  E.g. the following code must not compile:
 ///
 /// ```compile_fail
-/// use phantom_newtype::Amount;
-///
-/// // These structs are just markers and have no semantic meaning.
-/// enum Apples {}
-/// enum Oranges {}
-///
-/// let trois_pommes = Amount::<Apples, u64>::from(3);
-/// let five_oranges = Amount::<Oranges, u64>::from(5);
-///
-/// assert_eq!(8, (trois_pommes + five_oranges).get())
+#[doc = include_str!("../tests/ui/mixing_amount_units.rs")]
 /// ```
 ///
 /// `Amount<Unit, Repr>` defines common boilerplate to make type-safe
@@ -86,9 +82,9 @@ use serde::{Deserialize, Deserializer, Serialize, Serializer};
 /// assert_eq!(y - y, Amount::<Oranges, u64>::from(0));
 /// ```
 ///
-/// Multiplication of amounts is not supported: multiplying meters by
-/// meters gives square meters. However, you can scale an amount by a
-/// scalar or divide amounts:
+/// Amounts of the same unit but different `Repr`s add up (and subtract) whenever their `Repr`s
+/// do, and the result has the `Repr` that their `Add` (or `Sub`) gives. (`core` adds primitive
+/// integers of equal types only, so widen those first, with `into()`.)
 ///
 /// ```
 /// #![cfg_attr(
@@ -96,14 +92,54 @@ use serde::{Deserialize, Deserializer, Serialize, Serializer};
 ///     feature(generic_const_exprs)
 /// )]
 ///
+/// use core::ops::Add;
 /// use phantom_newtype::Amount;
 ///
+/// #[derive(Clone, Copy, Debug, PartialEq)]
+/// struct Total(u64);
+///
+/// impl Add<u32> for Total {
+///     type Output = Total;
+///     fn add(self, rhs: u32) -> Total {
+///         Total(self.0 + u64::from(rhs))
+///     }
+/// }
+///
+/// enum Bytes {}
+///
+/// let total = Amount::<Bytes, Total>::from(Total(1 << 40));
+/// let chunk = Amount::<Bytes, u32>::from(4096);
+/// assert_eq!((total + chunk).get_copy(), Total((1 << 40) + 4096));
+///
+/// let small = Amount::<Bytes, u32>::from(7);
+/// let wide = Amount::<Bytes, u64>::from(1 << 40) + Amount::<Bytes, u64>::from(small);
+/// assert_eq!(wide.get_copy(), (1 << 40) + 7);
+/// ```
+///
+/// Multiplying amounts gives an amount of the product unit: meters by
+/// meters gives [Squared] meters, and newtons by meters gives a
+/// [Product] of the two. You can also scale an amount by a scalar, or
+/// divide amounts of the same unit (which gives a plain `Repr`):
+///
+/// ```
+/// #![cfg_attr(
+///     feature = "unstable_generic_const_own_type",
+///     feature(generic_const_exprs)
+/// )]
+///
+/// use phantom_newtype::{Amount, Squared};
+///
 /// enum Apples {}
+/// enum Meters {}
 ///
 /// let x = Amount::<Apples, u64>::from(3);
 /// assert_eq!(x * 3, Amount::<Apples, u64>::from(9));
 /// assert_eq!(1, x / x);
 /// assert_eq!(3, (x * 3) / x);
+///
+/// let side = Amount::<Meters, u64>::from(4);
+/// let area: Amount<Squared<Meters>, u64> = side * side;
+/// assert_eq!(area.get_copy(), 16);
 /// ```
 ///
 /// Note that the unit is only available at compile time, thus using
@@ -147,6 +183,26 @@ use serde::{Deserialize, Deserializer, Serialize, Serializer};
 /// }
 /// ```
 ///
+/// With feature `bytemuck`, amounts are `Zeroable`, `Pod` (if `Repr` is, and the amount is
+/// `Copy`) and `TransparentWrapper<Repr>`, so they can be cast to and from bytes.
+///
+/// ```
+/// #![cfg_attr(
+///     feature = "unstable_generic_const_own_type",
+///     feature(generic_const_exprs)
+/// )]
+///
+/// #[cfg(feature = "bytemuck")] {
+/// use phantom_newtype::Amount;
+/// enum Meters {}
+///
+/// let distances = [Amount::<Meters, u32>::from(1), Amount::from(2)];
+/// let bytes: &[u8] = bytemuck::cast_slice(&distances);
+/// assert_eq!(bytes.len(), 8);
+/// assert_eq!(bytemuck::cast_slice::<u8, Amount<Meters, u32>>(bytes), distances);
+/// }
+/// ```
+///
 /// You can also declare constants of `Amount<Unit, Repr>` using `new`
 /// function:
 /// ```
@@ -181,17 +237,34 @@ use serde::{Deserialize, Deserializer, Serialize, Serializer};
 /// let n_from_thread = std::thread::spawn(|| &N).join().unwrap();
 /// assert_eq!(N, *n_from_thread);
 /// ```
+///
+/// But not if the `Repr` doesn't allow it:
+///
+/// ```compile_fail
+/// use phantom_newtype::Amount;
+/// use std::rc::Rc;
+///
+/// let shared = Amount::<(), Rc<u8>>::new(Rc::new(1));
+/// std::thread::spawn(move || shared);
+/// ```
 #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+#[cfg_attr(
+    feature = "zerocopy",
+    derive(
+        zerocopy::FromBytes,
+        zerocopy::IntoBytes,
+        zerocopy::Unaligned,
+        zerocopy::KnownLayout,
+        zerocopy::Immutable
+    )
+)]
 #[repr(transparent)]
-pub struct Amount<const TF: TraitFlags, Unit, Repr>(
-    Repr,
-    PhantomData<core::sync::atomic::AtomicPtr<Unit>>,
-);
+pub struct Amount<const TF: TraitFlags, Unit, Repr>(pub(crate) Repr, PhantomData<fn() -> Unit>);
 
 #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
-impl<const TF: TraitFlags, Unit, Repr: Copy> Amount<TF, Unit, Repr> {
+impl<const TF: TraitFlags, Unit, Repr> Amount<TF, Unit, Repr> {
     // @TODO
-    /// Returns the wrapped value.
+    /// Returns a reference to the wrapped value.
     ///
     /// ```
     /// #![cfg_attr(
@@ -204,9 +277,32 @@ impl<const TF: TraitFlags, Unit, Repr: Copy> Amount<TF, Unit, Repr> {
     /// enum Apples {}
     ///
     /// let three_apples = Amount::<Apples, u64>::from(3);
-    /// assert_eq!(9, (three_apples * 3).get());
+    /// assert_eq!(9, *(three_apples * 3).get());
     /// ```
-    pub fn get(&self) -> Repr {
+    pub const fn get(&self) -> &Repr {
+        &self.0
+    }
+}
+
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+impl<const TF: TraitFlags, Unit, Repr: Copy> Amount<TF, Unit, Repr> {
+    /// Returns a copy of the wrapped value.
+    ///
+    /// ```
+    /// #![cfg_attr(
+    ///     feature = "unstable_generic_const_own_type",
+    ///     feature(generic_const_exprs)
+    /// )]
+    ///
+    /// use phantom_newtype::Amount;
+    ///
+    /// enum Apples {}
+    ///
+    /// const THREE_APPLES: Amount<Apples, u64> = Amount::new(3);
+    /// const THREE: u64 = THREE_APPLES.get_copy();
+    /// assert_eq!(3, THREE);
+    /// ```
+    pub const fn get_copy(&self) -> Repr {
         self.0
     }
 }
@@ -221,6 +317,41 @@ impl<const TF: TraitFlags, Unit, Repr> Amount<TF, Unit, Repr> {
     }
 }
 
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+impl<const TF: TraitFlags, Unit, Repr: ConstDefault> Amount<TF, Unit, Repr> {
+    /// Zero amount (more precisely, the [ConstDefault] of `Repr`), usable in patterns' guards and
+    /// in `const` and `static` initializers. Unlike `default()`, available regardless of the trait
+    /// flags:
+    ///
+    /// ```
+    /// #![cfg_attr(
+    ///     feature = "unstable_generic_const_own_type",
+    ///     feature(generic_const_exprs)
+    /// )]
+    ///
+    /// use phantom_newtype::{Amount, AmountNoDefault};
+    ///
+    /// enum Bytes {}
+    /// enum Packets {}
+    ///
+    /// struct Traffic {
+    ///     received: Amount<Bytes, u64>,
+    ///     sent: AmountNoDefault<Bytes, u64>,
+    ///     dropped: Amount<Packets, u32>,
+    /// }
+    ///
+    /// static EMPTY: Traffic = Traffic {
+    ///     received: Amount::ZERO,
+    ///     sent: AmountNoDefault::ZERO,
+    ///     dropped: Amount::ZERO,
+    /// };
+    /// assert_eq!(EMPTY.received, Amount::from(0));
+    /// assert_eq!(EMPTY.sent.get(), &0);
+    /// assert_eq!(EMPTY.dropped, Amount::default());
+    /// ```
+    pub const ZERO: Self = Self(Repr::DEFAULT, PhantomData);
+}
+
 #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
 impl<const TF: TraitFlags, Unit: Default, Repr> Amount<TF, Unit, Repr> {
     // @TODO similar but without &self
@@ -284,6 +415,198 @@ where
     pub fn display(&self) -> DisplayProxy<'_, Self, Unit> {
         DisplayProxy::new(self)
     }
+
+    /// Write with the displayer (as per [Self::display]) straight into `out`, for example a
+    /// reused buffer, without an intermediate `String`.
+    pub fn display_into<W: fmt::Write + ?Sized>(&self, out: &mut W) -> fmt::Result {
+        write!(out, "{}", self.display())
+    }
+}
+
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+impl<const TF: TraitFlags, Unit, Repr> Amount<TF, Unit, Repr>
+where
+    Unit: DebugerOf<Self>,
+{
+    /// `debug` attaches a custom `Debug` formatting (as per [DebugerOf]), separate from
+    /// [Self::display].
+    pub fn debug(&self) -> DebugProxy<'_, Self, Unit> {
+        DebugProxy::new(self)
+    }
+}
+
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+impl<const TF: TraitFlags, Unit: UnitLabel, Repr: ToF64> Amount<TF, Unit, Repr> {
+    /// Display scaled by an SI prefix (from `p` to `E`), followed by the unit's [UnitLabel], like
+    /// `1.5 MB`. Pass a precision (like `{:.1}`) to round the scaled number.
+    ///
+    /// ```
+    /// #![cfg_attr(
+    ///     feature = "unstable_generic_const_own_type",
+    ///     feature(generic_const_exprs)
+    /// )]
+    ///
+    /// use phantom_newtype::Amount;
+    ///
+    /// enum Bytes {}
+    /// enum Seconds {}
+    /// phantom_newtype::unit_label! {
+    ///     Bytes { symbol: "B", name: "Bytes" }
+    ///     Seconds { symbol: "s", name: "Seconds" }
+    /// }
+    ///
+    /// assert_eq!(Amount::<Bytes, u64>::from(1_500_000).si_display().to_string(), "1.5 MB");
+    /// assert_eq!(format!("{:.2}", Amount::<Bytes, u64>::from(1_234_567).si_display()), "1.23 MB");
+    /// assert_eq!(Amount::<Bytes, u64>::from(999).si_display().to_string(), "999 B");
+    /// assert_eq!(format!("{:.1}", Amount::<Bytes, u64>::from(999_960).si_display()), "1.0 MB");
+    /// assert_eq!(Amount::<Seconds, f64>::from(-0.0042).si_display().to_string(), "-4.2 ms");
+    /// assert_eq!(Amount::<Seconds, f64>::from(0.0).si_display().to_string(), "0 s");
+    /// ```
+    pub fn si_display(&self) -> SiDisplay {
+        SiDisplay::new(self.0.to_f64(), Unit::SYMBOL)
+    }
+}
+
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+impl<const TF: TraitFlags, Unit, Repr: fmt::Display> Amount<TF, Unit, Repr> {
+    /// Display with the digits of the integer part in groups of three, like `1,234,567`. See
+    /// [GroupedDisplay].
+    ///
+    /// ```
+    /// #![cfg_attr(
+    ///     feature = "unstable_generic_const_own_type",
+    ///     feature(generic_const_exprs)
+    /// )]
+    ///
+    /// use phantom_newtype::Amount;
+    ///
+    /// enum Wei {}
+    /// type Balance = Amount<Wei, i64>;
+    ///
+    /// assert_eq!(Balance::from(1_234_567).grouped_display().to_string(), "1,234,567");
+    /// assert_eq!(Balance::from(-999).grouped_display().to_string(), "-999");
+    /// assert_eq!(Balance::from(-1_000).grouped_display().separator('_').to_string(), "-1_000");
+    /// assert_eq!(
+    ///     format!("{:.2}", Amount::<Wei, f64>::from(12345.678).grouped_display()),
+    ///     "12,345.68"
+    /// );
+    /// ```
+    pub fn grouped_display(&self) -> GroupedDisplay<'_, Repr> {
+        GroupedDisplay::new(&self.0)
+    }
+}
+
+// Passthroughs for float `Repr`s, which are only `PartialOrd` (and `PartialEq`).
+macro_rules! impl_float {
+    ($($float:ident)*) => {
+        $(
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<const TF: TraitFlags, Unit> Amount<TF, Unit, $float> {
+                #[doc = concat!("Total order, as per [", stringify!($float), "::total_cmp].")]
+                ///
+                /// ```
+                /// #![cfg_attr(
+                ///     feature = "unstable_generic_const_own_type",
+                ///     feature(generic_const_exprs)
+                /// )]
+                ///
+                /// use phantom_newtype::Amount;
+                ///
+                /// enum Meters {}
+                #[doc = concat!("type Distance = Amount<Meters, ", stringify!($float), ">;")]
+                ///
+                #[doc = concat!("let nan = Distance::from(", stringify!($float), "::NAN);")]
+                /// let mut distances = [Distance::from(2.5), nan, Distance::from(-1.0)];
+                /// distances.sort_by(Distance::total_cmp);
+                /// assert_eq!(distances[0], Distance::from(-1.0));
+                /// assert!(distances[2].is_nan());
+                /// assert!(!distances[2].is_finite());
+                /// assert!(Distance::from(0.1 + 0.2).approx_eq(Distance::from(0.3), Distance::from(1e-6)));
+                /// ```
+                pub fn total_cmp(&self, other: &Self) -> Ordering {
+                    self.0.total_cmp(&other.0)
+                }
+
+                pub fn is_nan(&self) -> bool {
+                    self.0.is_nan()
+                }
+
+                pub fn is_finite(&self) -> bool {
+                    self.0.is_finite()
+                }
+
+                /// Whether `self` and `other` differ by at most `epsilon`. `false` if either is NaN.
+                pub fn approx_eq(&self, other: Self, epsilon: Self) -> bool {
+                    let difference = self.0 - other.0;
+                    difference <= epsilon.0 && -difference <= epsilon.0
+                }
+            }
+        )*
+    };
+}
+impl_float!(f32 f64);
+
+// Passthroughs for integer `Repr`s.
+macro_rules! impl_integer {
+    ($($int:ident => $unsigned:ident)*) => {
+        $(
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<const TF: TraitFlags, Unit> Amount<TF, Unit, $int> {
+                #[doc = concat!("Distance between `self` and `other`, as per [", stringify!($int), "::abs_diff].")]
+                ///
+                /// ```
+                /// #![cfg_attr(
+                ///     feature = "unstable_generic_const_own_type",
+                ///     feature(generic_const_exprs)
+                /// )]
+                ///
+                /// use phantom_newtype::Amount;
+                ///
+                /// enum Millis {}
+                #[doc = concat!("type Delay = Amount<Millis, ", stringify!($int), ">;")]
+                ///
+                #[doc = concat!("let jitter: Amount<Millis, ", stringify!($unsigned), "> = Delay::from(7).abs_diff(Delay::from(10));")]
+                /// assert_eq!(jitter.get_copy(), 3);
+                /// ```
+                pub const fn abs_diff(self, other: Self) -> Amount<TF, Unit, $unsigned> {
+                    Amount::new(self.0.abs_diff(other.0))
+                }
+
+                /// `self` raised to the power of `EXP`, in `Unit` raised to it (see [UnitPow]):
+                /// `pow::<2>` of meters is in [Squared] meters.
+                ///
+                /// ```
+                /// #![cfg_attr(
+                ///     feature = "unstable_generic_const_own_type",
+                ///     feature(generic_const_exprs)
+                /// )]
+                ///
+                /// use phantom_newtype::{Amount, Product, Squared};
+                ///
+                /// enum Meters {}
+                #[doc = concat!("type Length = Amount<Meters, ", stringify!($int), ">;")]
+                ///
+                #[doc = concat!("let area: Amount<Squared<Meters>, ", stringify!($int), "> = Length::from(3).pow::<2>();")]
+                /// assert_eq!(area.get_copy(), 9);
+                #[doc = concat!("let volume: Amount<Product<Squared<Meters>, Meters>, ", stringify!($int), "> =")]
+                ///     Length::from(1).pow::<3>() + Length::from(2).pow::<3>();
+                /// assert_eq!(volume.get_copy(), 9);
+                /// ```
+                pub const fn pow<const EXP: u32>(
+                    self,
+                ) -> Amount<TF, <Unit as UnitPow<EXP>>::Output, $int>
+                where
+                    Unit: UnitPow<EXP>,
+                {
+                    Amount::new(self.0.pow(EXP))
+                }
+            }
+        )*
+    };
+}
+impl_integer! {
+    u8 => u8 u16 => u16 u32 => u32 u64 => u64 u128 => u128 usize => usize
+    i8 => u8 i16 => u16 i32 => u32 i64 => u64 i128 => u128 isize => usize
 }
 
 #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
@@ -320,6 +643,11 @@ impl<Unit, Repr: Default> Default
     }
 }
 #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+impl<Unit, Repr: ConstDefault> Amount<{ trait_flag::TRAIT_FLAGS_IS_COPY_IS_DEFAULT }, Unit, Repr> {
+    /// `const` counterpart of `default()`, for amounts that implement `Default`. See [Self::ZERO].
+    pub const DEFAULT: Self = Self(Repr::DEFAULT, PhantomData);
+}
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
 impl<Unit, Repr: Default> Default
     for Amount<{ trait_flag::TRAIT_FLAGS_NO_COPY_IS_DEFAULT }, Unit, Repr>
 {
@@ -327,6 +655,11 @@ impl<Unit, Repr: Default> Default
         Self(Default::default(), PhantomData)
     }
 }
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+impl<Unit, Repr: ConstDefault> Amount<{ trait_flag::TRAIT_FLAGS_NO_COPY_IS_DEFAULT }, Unit, Repr> {
+    /// `const` counterpart of `default()`, for amounts that implement `Default`. See [Self::ZERO].
+    pub const DEFAULT: Self = Self(Repr::DEFAULT, PhantomData);
+}
 
 #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
 impl<const TF: TraitFlags, Unit, Repr: PartialEq> PartialEq for Amount<TF, Unit, Repr> {
@@ -360,47 +693,74 @@ impl<const TF: TraitFlags, Unit, Repr: Hash> Hash for Amount<TF, Unit, Repr> {
 }
 
 #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
-impl<const TF: TraitFlags, Unit, Repr> Add for Amount<TF, Unit, Repr>
+impl<const TF: TraitFlags, Unit, Repr, Rhs> Add<Rhs> for Amount<TF, Unit, Repr>
 where
-    Repr: AddAssign + Copy,
+    Rhs: AddTo<Self>,
 {
-    type Output = Self;
-    fn add(mut self, rhs: Self) -> Self {
-        self.add_assign(rhs);
-        self
+    type Output = Rhs::Output;
+    fn add(self, rhs: Rhs) -> Self::Output {
+        rhs.add_to(self)
     }
 }
 
+// Amounts with different `Repr`s add up (and subtract) as their `Repr`s do, with the result's
+// `Repr` chosen by `Repr`'s `Add` (or `Sub`). For primitive `Repr`s, `core` only adds equal types.
 #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
-impl<const TF: TraitFlags, Unit, Repr> AddAssign for Amount<TF, Unit, Repr>
+impl<const TF: TraitFlags, Unit, Repr, Repr2> AddTo<Amount<TF, Unit, Repr>>
+    for Amount<TF, Unit, Repr2>
 where
-    Repr: AddAssign + Copy,
+    Repr: Add<Repr2>,
 {
-    fn add_assign(&mut self, rhs: Self) {
-        self.0 += rhs.get()
+    type Output = Amount<TF, Unit, <Repr as Add<Repr2>>::Output>;
+    fn add_to(self, lhs: Amount<TF, Unit, Repr>) -> Self::Output {
+        Amount::new(lhs.0 + self.0)
     }
 }
 
 #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
-impl<const TF: TraitFlags, Unit, Repr> SubAssign for Amount<TF, Unit, Repr>
+impl<const TF: TraitFlags, Unit, Repr, Repr2> AddAssign<Amount<TF, Unit, Repr2>>
+    for Amount<TF, Unit, Repr>
 where
-    Repr: SubAssign + Copy,
+    Repr: AddAssign<Repr2>,
 {
-    fn sub_assign(&mut self, rhs: Self) {
-        self.0 -= rhs.get()
+    fn add_assign(&mut self, rhs: Amount<TF, Unit, Repr2>) {
+        self.0 += rhs.0
     }
 }
 
 #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
-impl<const TF: TraitFlags, Unit, Repr> Sub for Amount<TF, Unit, Repr>
+impl<const TF: TraitFlags, Unit, Repr, Repr2> SubAssign<Amount<TF, Unit, Repr2>>
+    for Amount<TF, Unit, Repr>
 where
-    Repr: SubAssign + Copy,
+    Repr: SubAssign<Repr2>,
 {
-    type Output = Self;
+    fn sub_assign(&mut self, rhs: Amount<TF, Unit, Repr2>) {
+        self.0 -= rhs.0
+    }
+}
 
-    fn sub(mut self, rhs: Self) -> Self {
-        self.sub_assign(rhs);
-        self
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+impl<const TF: TraitFlags, Unit, Repr, Rhs> Sub<Rhs> for Amount<TF, Unit, Repr>
+where
+    Rhs: SubFrom<Self>,
+{
+    type Output = Rhs::Output;
+
+    fn sub(self, rhs: Rhs) -> Self::Output {
+        rhs.sub_from(self)
+    }
+}
+
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+impl<const TF: TraitFlags, Unit, Repr, Repr2> SubFrom<Amount<TF, Unit, Repr>>
+    for Amount<TF, Unit, Repr2>
+where
+    Repr: Sub<Repr2>,
+{
+    type Output = Amount<TF, Unit, <Repr as Sub<Repr2>>::Output>;
+
+    fn sub_from(self, lhs: Amount<TF, Unit, Repr>) -> Self::Output {
+        Amount::new(lhs.0 - self.0)
     }
 }
 
@@ -427,6 +787,165 @@ where
     }
 }
 
+/// A dimensionless factor to multiply or divide an [Amount] by, when its type differs from `Repr`
+/// (as allowed by [crate::repr::Scale]).
+///
+/// ```
+/// #![cfg_attr(
+///     feature = "unstable_generic_const_own_type",
+///     feature(generic_const_exprs)
+/// )]
+///
+/// use phantom_newtype::{Amount, Scalar};
+///
+/// enum Cycles {}
+/// type CyclesAmount = Amount<Cycles, u128>;
+///
+/// let fee = CyclesAmount::from(1_000);
+/// assert_eq!(fee * Scalar(3u64), CyclesAmount::from(3_000));
+/// assert_eq!(fee * Scalar(1.5), CyclesAmount::from(1_500));
+/// assert_eq!(fee / Scalar(4u8), CyclesAmount::from(250));
+///
+/// let mut total = fee;
+/// total *= Scalar(2u32);
+/// total /= Scalar(0.5);
+/// assert_eq!(total, CyclesAmount::from(4_000));
+/// ```
+///
+/// Dividing an integer amount by `Scalar(0)` panics, while dividing it by `Scalar(0.0)`
+/// saturates (see [crate::repr::Scale]):
+///
+/// ```
+/// #![cfg_attr(
+///     feature = "unstable_generic_const_own_type",
+///     feature(generic_const_exprs)
+/// )]
+///
+/// use phantom_newtype::{Amount, Scalar};
+///
+/// enum Cycles {}
+/// type CyclesAmount = Amount<Cycles, u128>;
+///
+/// assert_eq!(CyclesAmount::from(1_000) / Scalar(0.0), CyclesAmount::from(u128::MAX));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Scalar<S>(pub S);
+
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+impl<const TF: TraitFlags, Unit, Repr, S> MulAssign<Scalar<S>> for Amount<TF, Unit, Repr>
+where
+    Repr: Scale<S> + Copy,
+{
+    fn mul_assign(&mut self, rhs: Scalar<S>) {
+        self.0 = self.0.scale_mul(rhs.0);
+    }
+}
+
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+impl<const TF: TraitFlags, Unit, Repr, S> Mul<Scalar<S>> for Amount<TF, Unit, Repr>
+where
+    Repr: Scale<S> + Copy,
+{
+    type Output = Self;
+
+    fn mul(mut self, rhs: Scalar<S>) -> Self {
+        self.mul_assign(rhs);
+        self
+    }
+}
+
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+impl<const TF: TraitFlags, Unit, Repr, S> DivAssign<Scalar<S>> for Amount<TF, Unit, Repr>
+where
+    Repr: Scale<S> + Copy,
+{
+    fn div_assign(&mut self, rhs: Scalar<S>) {
+        self.0 = self.0.scale_div(rhs.0);
+    }
+}
+
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+impl<const TF: TraitFlags, Unit, Repr, S> Div<Scalar<S>> for Amount<TF, Unit, Repr>
+where
+    Repr: Scale<S> + Copy,
+{
+    type Output = Self;
+
+    fn div(mut self, rhs: Scalar<S>) -> Self {
+        self.div_assign(rhs);
+        self
+    }
+}
+
+/// Unit of a product of amounts of `U1` and `U2`. See [Squared].
+pub struct Product<U1, U2>(PhantomData<(U1, U2)>);
+
+/// Unit of a product of amounts of `U` with itself, like a variance.
+///
+/// ```
+/// #![cfg_attr(
+///     feature = "unstable_generic_const_own_type",
+///     feature(generic_const_exprs)
+/// )]
+///
+/// use phantom_newtype::{Amount, Product, Squared};
+///
+/// enum Meters {}
+/// enum Newtons {}
+/// type Distance = Amount<Meters, i64>;
+///
+/// let samples = [Distance::from(2), Distance::from(4), Distance::from(6)];
+/// let mean = Distance::from(4);
+/// let sum_of_squares = samples
+///     .iter()
+///     .map(|&sample| (sample - mean) * (sample - mean))
+///     .fold(Amount::<Squared<Meters>, i64>::from(0), |sum, square| sum + square);
+/// assert_eq!(sum_of_squares, Amount::from(8));
+///
+/// let work: Amount<Product<Newtons, Meters>, i64> = Amount::<Newtons, i64>::from(3) * mean;
+/// assert_eq!(work.get_copy(), 12);
+/// ```
+pub type Squared<U> = Product<U, U>;
+
+/// Unit `Self` raised to the power of `EXP`, as a [Product] of `Self` with itself, for
+/// [Amount::pow]. Implemented for exponents 1 to 4.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` has no power `{EXP}` as a unit",
+    note = "amounts are raised to powers 1 to 4 only; for other exponents, use the `Repr`"
+)]
+pub trait UnitPow<const EXP: u32> {
+    type Output;
+}
+
+impl<U> UnitPow<1> for U {
+    type Output = U;
+}
+
+impl<U> UnitPow<2> for U {
+    type Output = Squared<U>;
+}
+
+impl<U> UnitPow<3> for U {
+    type Output = Product<Squared<U>, U>;
+}
+
+impl<U> UnitPow<4> for U {
+    type Output = Squared<Squared<U>>;
+}
+
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+impl<const TF: TraitFlags, U1, U2, Repr> Mul<Amount<TF, U2, Repr>> for Amount<TF, U1, Repr>
+where
+    Repr: MulAssign + Copy,
+{
+    type Output = Amount<TF, Product<U1, U2>, Repr>;
+
+    fn mul(mut self, rhs: Amount<TF, U2, Repr>) -> Self::Output {
+        self.0 *= rhs.0;
+        Amount::new(self.0)
+    }
+}
+
 #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
 impl<const TF: TraitFlags, Unit, Repr> Div<Self> for Amount<TF, Unit, Repr>
 where
@@ -445,17 +964,11 @@ where
     Repr: fmt::Debug,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{:?}", self.0)
-    }
-}
-
-#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
-impl<const TF: TraitFlags, Unit, Repr> fmt::Display for Amount<TF, Unit, Repr>
-where
-    Repr: fmt::Display,
-{
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.0)
+        if f.alternate() {
+            f.debug_tuple(unit_name::<Unit>()).field(&self.0).finish()
+        } else {
+            fmt::Debug::fmt(&self.0, f)
+        }
     }
 }
 
@@ -483,3 +996,117 @@ where
         Repr::deserialize(deserializer).map(Self::new)
     }
 }
+
+// `num-traits` impls delegate to `Repr`, so that generic numeric code (accumulators, interpolation)
+// accepts amounts. Not `One`, nor `CheckedMul` and the like: an amount times an amount is an amount
+// of a `Product` unit (see [Squared]), not `Self`.
+#[cfg(feature = "num-traits")]
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+impl<const TF: TraitFlags, Unit, Repr> num_traits::Zero for Amount<TF, Unit, Repr>
+where
+    Repr: num_traits::Zero + AddAssign + Copy,
+{
+    fn zero() -> Self {
+        Self::new(Repr::zero())
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0.is_zero()
+    }
+}
+
+#[cfg(feature = "num-traits")]
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+impl<const TF: TraitFlags, Unit, Repr> num_traits::Bounded for Amount<TF, Unit, Repr>
+where
+    Repr: num_traits::Bounded,
+{
+    fn min_value() -> Self {
+        Self::new(Repr::min_value())
+    }
+
+    fn max_value() -> Self {
+        Self::new(Repr::max_value())
+    }
+}
+
+#[cfg(feature = "num-traits")]
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+impl<const TF: TraitFlags, Unit, Repr> num_traits::CheckedAdd for Amount<TF, Unit, Repr>
+where
+    Repr: num_traits::CheckedAdd + AddAssign + Copy,
+{
+    fn checked_add(&self, v: &Self) -> Option<Self> {
+        self.0.checked_add(&v.0).map(Self::new)
+    }
+}
+
+#[cfg(feature = "num-traits")]
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+impl<const TF: TraitFlags, Unit, Repr> num_traits::CheckedSub for Amount<TF, Unit, Repr>
+where
+    Repr: num_traits::CheckedSub + SubAssign + Copy,
+{
+    fn checked_sub(&self, v: &Self) -> Option<Self> {
+        self.0.checked_sub(&v.0).map(Self::new)
+    }
+}
+
+#[cfg(feature = "num-traits")]
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+impl<const TF: TraitFlags, Unit, Repr> num_traits::Saturating for Amount<TF, Unit, Repr>
+where
+    Repr: num_traits::Saturating,
+{
+    fn saturating_add(self, v: Self) -> Self {
+        Self::new(self.0.saturating_add(v.0))
+    }
+
+    fn saturating_sub(self, v: Self) -> Self {
+        Self::new(self.0.saturating_sub(v.0))
+    }
+}
+
+#[cfg(feature = "num-traits")]
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+impl<const TF: TraitFlags, Unit, Repr> num_traits::SaturatingAdd for Amount<TF, Unit, Repr>
+where
+    Repr: num_traits::SaturatingAdd + AddAssign + Copy,
+{
+    fn saturating_add(&self, v: &Self) -> Self {
+        Self::new(self.0.saturating_add(&v.0))
+    }
+}
+
+#[cfg(feature = "num-traits")]
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+impl<const TF: TraitFlags, Unit, Repr> num_traits::SaturatingSub for Amount<TF, Unit, Repr>
+where
+    Repr: num_traits::SaturatingSub + SubAssign + Copy,
+{
+    fn saturating_sub(&self, v: &Self) -> Self {
+        Self::new(self.0.saturating_sub(&v.0))
+    }
+}
+
+#[cfg(feature = "num-traits")]
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+impl<const TF: TraitFlags, Unit, Repr> num_traits::WrappingAdd for Amount<TF, Unit, Repr>
+where
+    Repr: num_traits::WrappingAdd + AddAssign + Copy,
+{
+    fn wrapping_add(&self, v: &Self) -> Self {
+        Self::new(self.0.wrapping_add(&v.0))
+    }
+}
+
+#[cfg(feature = "num-traits")]
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+impl<const TF: TraitFlags, Unit, Repr> num_traits::WrappingSub for Amount<TF, Unit, Repr>
+where
+    Repr: num_traits::WrappingSub + SubAssign + Copy,
+{
+    fn wrapping_sub(&self, v: &Self) -> Self {
+        Self::new(self.0.wrapping_sub(&v.0))
+    }
+}
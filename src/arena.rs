@@ -0,0 +1,282 @@
+// Copyright 2024 Peter Lyons Kehl
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Generational ids and an [Arena] issuing them, like crate `slotmap`. Requires feature `arena`.
+//!
+//! A [GenerationalId] is an index into an [Arena] plus the generation of that slot at the time of
+//! insertion. Once its value is removed, the slot's generation changes, so the id (and its copies)
+//! become stale, instead of referring to whatever value reuses the slot later.
+//!
+//! ```
+//! #![cfg_attr(
+//!     feature = "unstable_generic_const_own_type",
+//!     feature(generic_const_exprs)
+//! )]
+//!
+//! use phantom_newtype::arena::{Arena, GenerationalId};
+//!
+//! enum Enemy {}
+//!
+//! let mut enemies = Arena::<Enemy, &str>::new();
+//! let orc: GenerationalId<Enemy> = enemies.insert("orc");
+//! assert_eq!(enemies[orc], "orc");
+//!
+//! assert_eq!(enemies.remove(orc), Some("orc"));
+//! let troll = enemies.insert("troll");
+//! assert_eq!(troll.index(), orc.index()); // The slot is reused,
+//! assert_eq!(enemies.get(orc), None); // but the old id is stale.
+//! assert_eq!(enemies.get(troll), Some(&"troll"));
+//! ```
+
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::convert::TryFrom;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use core::ops::{Index, IndexMut};
+
+/// An index into an [Arena] of `Entity`, and the generation of its slot. See the module docs.
+pub struct GenerationalId<Entity> {
+    index: u32,
+    generation: u32,
+    entity: PhantomData<fn() -> Entity>,
+}
+
+impl<Entity> GenerationalId<Entity> {
+    /// An id from its parts (for example, from the wire). Only an [Arena] knows whether it's live.
+    pub const fn from_parts(index: u32, generation: u32) -> Self {
+        Self {
+            index,
+            generation,
+            entity: PhantomData,
+        }
+    }
+
+    pub const fn index(&self) -> u32 {
+        self.index
+    }
+
+    pub const fn generation(&self) -> u32 {
+        self.generation
+    }
+}
+
+impl<Entity> Clone for GenerationalId<Entity> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<Entity> Copy for GenerationalId<Entity> {}
+
+impl<Entity> PartialEq for GenerationalId<Entity> {
+    fn eq(&self, rhs: &Self) -> bool {
+        (self.index, self.generation) == (rhs.index, rhs.generation)
+    }
+}
+
+impl<Entity> Eq for GenerationalId<Entity> {}
+
+impl<Entity> PartialOrd for GenerationalId<Entity> {
+    fn partial_cmp(&self, rhs: &Self) -> Option<Ordering> {
+        Some(self.cmp(rhs))
+    }
+}
+
+impl<Entity> Ord for GenerationalId<Entity> {
+    fn cmp(&self, rhs: &Self) -> Ordering {
+        (self.index, self.generation).cmp(&(rhs.index, rhs.generation))
+    }
+}
+
+impl<Entity> Hash for GenerationalId<Entity> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (self.index, self.generation).hash(state)
+    }
+}
+
+impl<Entity> fmt::Debug for GenerationalId<Entity> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}v{}", self.index, self.generation)
+    }
+}
+
+struct Slot<T> {
+    generation: u32,
+    value: Option<T>,
+}
+
+/// Values of `T`, each in a slot of its own, addressed by [GenerationalId]s of `Entity`. Slots of
+/// removed values are reused (with the next generation). See the module docs.
+pub struct Arena<Entity, T> {
+    slots: Vec<Slot<T>>,
+    // Indices of the slots without a value.
+    free: Vec<u32>,
+    entity: PhantomData<fn() -> Entity>,
+}
+
+impl<Entity, T> Arena<Entity, T> {
+    pub const fn new() -> Self {
+        Self {
+            slots: Vec::new(),
+            free: Vec::new(),
+            entity: PhantomData,
+        }
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            slots: Vec::with_capacity(capacity),
+            free: Vec::new(),
+            entity: PhantomData,
+        }
+    }
+
+    /// Number of values.
+    pub fn len(&self) -> usize {
+        self.slots.len() - self.free.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Stores `value`, and returns its id.
+    ///
+    /// # Panics
+    /// If the arena would need more than `u32::MAX` slots.
+    pub fn insert(&mut self, value: T) -> GenerationalId<Entity> {
+        if let Some(index) = self.free.pop() {
+            let slot = &mut self.slots[index as usize];
+            slot.value = Some(value);
+            GenerationalId::from_parts(index, slot.generation)
+        } else {
+            let index = u32::try_from(self.slots.len()).expect("arena of more than u32::MAX slots");
+            self.slots.push(Slot {
+                generation: 0,
+                value: Some(value),
+            });
+            GenerationalId::from_parts(index, 0)
+        }
+    }
+
+    /// Removes and returns the value of `id`, unless `id` is stale. Afterwards `id` (and its
+    /// copies) are stale. (Generations wrap around after `u32::MAX` removals from the same slot.)
+    pub fn remove(&mut self, id: GenerationalId<Entity>) -> Option<T> {
+        let slot = self.slots.get_mut(id.index as usize)?;
+        if slot.generation != id.generation {
+            return None;
+        }
+        let value = slot.value.take()?;
+        slot.generation = slot.generation.wrapping_add(1);
+        self.free.push(id.index);
+        Some(value)
+    }
+
+    /// Whether `id` is live (not stale).
+    pub fn contains(&self, id: GenerationalId<Entity>) -> bool {
+        self.get(id).is_some()
+    }
+
+    pub fn get(&self, id: GenerationalId<Entity>) -> Option<&T> {
+        match self.slots.get(id.index as usize) {
+            Some(slot) if slot.generation == id.generation => slot.value.as_ref(),
+            _ => None,
+        }
+    }
+
+    pub fn get_mut(&mut self, id: GenerationalId<Entity>) -> Option<&mut T> {
+        match self.slots.get_mut(id.index as usize) {
+            Some(slot) if slot.generation == id.generation => slot.value.as_mut(),
+            _ => None,
+        }
+    }
+
+    /// The values with their ids, in the order of their slots.
+    pub fn iter(&self) -> impl Iterator<Item = (GenerationalId<Entity>, &T)> {
+        self.slots.iter().enumerate().filter_map(|(index, slot)| {
+            let id = GenerationalId::from_parts(index as u32, slot.generation);
+            slot.value.as_ref().map(|value| (id, value))
+        })
+    }
+
+    /// The values with their ids, in the order of their slots.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (GenerationalId<Entity>, &mut T)> {
+        self.slots
+            .iter_mut()
+            .enumerate()
+            .filter_map(|(index, slot)| {
+                let id = GenerationalId::from_parts(index as u32, slot.generation);
+                slot.value.as_mut().map(|value| (id, value))
+            })
+    }
+}
+
+impl<Entity, T> Default for Arena<Entity, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Entity, T> Index<GenerationalId<Entity>> for Arena<Entity, T> {
+    type Output = T;
+
+    /// # Panics
+    /// If `id` is stale.
+    fn index(&self, id: GenerationalId<Entity>) -> &T {
+        self.get(id).expect("stale GenerationalId")
+    }
+}
+
+impl<Entity, T> IndexMut<GenerationalId<Entity>> for Arena<Entity, T> {
+    /// # Panics
+    /// If `id` is stale.
+    fn index_mut(&mut self, id: GenerationalId<Entity>) -> &mut T {
+        self.get_mut(id).expect("stale GenerationalId")
+    }
+}
+
+impl<Entity, T: fmt::Debug> fmt::Debug for Arena<Entity, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    enum Unit {}
+
+    #[test]
+    fn stale_ids_stay_stale() {
+        let mut arena = Arena::<Unit, u8>::new();
+        let first = arena.insert(1);
+        let second = arena.insert(2);
+        assert_eq!(arena.remove(first), Some(1));
+        assert_eq!(arena.remove(first), None);
+        assert_eq!(arena.len(), 1);
+
+        let reused = arena.insert(3);
+        assert_eq!((reused.index(), reused.generation()), (0, 1));
+        assert!(!arena.contains(first));
+        assert_eq!(arena.get_mut(first), None);
+        arena[second] += 10;
+
+        let live: alloc::vec::Vec<_> = arena.iter().collect();
+        assert_eq!(live, [(reused, &3), (second, &12)]);
+        assert_eq!(arena.get(GenerationalId::from_parts(7, 0)), None);
+    }
+}
@@ -0,0 +1,372 @@
+// Copyright 2024 Peter Lyons Kehl
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Atomics holding typed values. Requires feature `atomics`.
+
+use crate::{Amount, Id, Instant};
+use core::fmt;
+use core::marker::PhantomData;
+use core::sync::atomic::Ordering;
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// A `Repr` that has a `core::sync::atomic` counterpart. Sealed: implemented for primitive
+/// integers that the target supports atomically.
+pub trait AtomicRepr: Copy + sealed::Sealed {
+    /// The matching `core::sync::atomic` type.
+    type Atomic: Send + Sync;
+
+    fn new_atomic(value: Self) -> Self::Atomic;
+    fn load(atomic: &Self::Atomic, order: Ordering) -> Self;
+    fn store(atomic: &Self::Atomic, value: Self, order: Ordering);
+    fn swap(atomic: &Self::Atomic, value: Self, order: Ordering) -> Self;
+    fn compare_exchange(
+        atomic: &Self::Atomic,
+        current: Self,
+        new: Self,
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<Self, Self>;
+    /// Wrapping addition, like the primitive atomics do.
+    fn fetch_add(atomic: &Self::Atomic, value: Self, order: Ordering) -> Self;
+    /// Wrapping subtraction, like the primitive atomics do.
+    fn fetch_sub(atomic: &Self::Atomic, value: Self, order: Ordering) -> Self;
+    /// Store the maximum of the current value and `value`. Return the previous value.
+    fn fetch_max(atomic: &Self::Atomic, value: Self, order: Ordering) -> Self;
+    /// Increment by one unless that would overflow. Return the previous value, or `None` on
+    /// overflow (in which case the atomic is left unchanged).
+    fn fetch_checked_increment(atomic: &Self::Atomic, order: Ordering) -> Option<Self>;
+}
+
+macro_rules! impl_atomic_repr {
+    ($($width:literal: $t:ty => $atomic:ident;)*) => {
+        $(
+            #[cfg(target_has_atomic = $width)]
+            impl sealed::Sealed for $t {}
+
+            #[cfg(target_has_atomic = $width)]
+            impl AtomicRepr for $t {
+                type Atomic = core::sync::atomic::$atomic;
+
+                fn new_atomic(value: Self) -> Self::Atomic {
+                    Self::Atomic::new(value)
+                }
+                fn load(atomic: &Self::Atomic, order: Ordering) -> Self {
+                    atomic.load(order)
+                }
+                fn store(atomic: &Self::Atomic, value: Self, order: Ordering) {
+                    atomic.store(value, order)
+                }
+                fn swap(atomic: &Self::Atomic, value: Self, order: Ordering) -> Self {
+                    atomic.swap(value, order)
+                }
+                fn compare_exchange(
+                    atomic: &Self::Atomic,
+                    current: Self,
+                    new: Self,
+                    success: Ordering,
+                    failure: Ordering,
+                ) -> Result<Self, Self> {
+                    atomic.compare_exchange(current, new, success, failure)
+                }
+                fn fetch_add(atomic: &Self::Atomic, value: Self, order: Ordering) -> Self {
+                    atomic.fetch_add(value, order)
+                }
+                fn fetch_sub(atomic: &Self::Atomic, value: Self, order: Ordering) -> Self {
+                    atomic.fetch_sub(value, order)
+                }
+                fn fetch_max(atomic: &Self::Atomic, value: Self, order: Ordering) -> Self {
+                    atomic.fetch_max(value, order)
+                }
+                fn fetch_checked_increment(atomic: &Self::Atomic, order: Ordering) -> Option<Self> {
+                    atomic
+                        .fetch_update(order, Ordering::Relaxed, |value| value.checked_add(1))
+                        .ok()
+                }
+            }
+        )*
+    };
+}
+impl_atomic_repr! {
+    "8": u8 => AtomicU8;
+    "16": u16 => AtomicU16;
+    "32": u32 => AtomicU32;
+    "64": u64 => AtomicU64;
+    "ptr": usize => AtomicUsize;
+    "8": i8 => AtomicI8;
+    "16": i16 => AtomicI16;
+    "32": i32 => AtomicI32;
+    "64": i64 => AtomicI64;
+    "ptr": isize => AtomicIsize;
+}
+
+/// Thread-safe generator of monotonically increasing [crate::Id]s.
+///
+/// The maximum value of `Repr` is never issued: it marks that the generator is exhausted.
+///
+/// ```
+/// #![cfg_attr(
+///     feature = "unstable_generic_const_own_type",
+///     feature(generic_const_exprs)
+/// )]
+///
+/// use core::sync::atomic::AtomicU64;
+/// use phantom_newtype::{Id, IdGenerator};
+///
+/// enum User {}
+/// static NEXT_USER_ID: IdGenerator<User, u64> = IdGenerator::from_atomic(AtomicU64::new(1));
+///
+/// assert_eq!(NEXT_USER_ID.next(), Id::<User, u64>::from(1));
+/// assert_eq!(NEXT_USER_ID.next(), Id::<User, u64>::from(2));
+///
+/// let from_thread = std::thread::spawn(|| NEXT_USER_ID.next()).join().unwrap();
+/// assert_eq!(from_thread, Id::<User, u64>::from(3));
+/// ```
+pub struct IdGenerator<Entity, Repr: AtomicRepr> {
+    next: Repr::Atomic,
+    entity: PhantomData<fn() -> Entity>,
+}
+
+impl<Entity, Repr: AtomicRepr> IdGenerator<Entity, Repr> {
+    /// Create a generator whose first id is `first`.
+    pub fn new(first: Repr) -> Self {
+        Self::from_atomic(Repr::new_atomic(first))
+    }
+
+    /// Create a generator from an atomic holding the first id. Unlike [IdGenerator::new], this is
+    /// a `const fn`, so the generator can be a `static`.
+    pub const fn from_atomic(next: Repr::Atomic) -> Self {
+        Self {
+            next,
+            entity: PhantomData,
+        }
+    }
+
+    /// Return a new id, or `None` if all ids representable by `Repr` have been issued.
+    ///
+    /// ```
+    /// #![cfg_attr(
+    ///     feature = "unstable_generic_const_own_type",
+    ///     feature(generic_const_exprs)
+    /// )]
+    ///
+    /// use phantom_newtype::IdGenerator;
+    ///
+    /// enum Slot {}
+    /// let slots = IdGenerator::<Slot, u8>::new(u8::MAX - 1);
+    ///
+    /// assert_eq!(slots.try_next().map(|id| *id.get()), Some(u8::MAX - 1));
+    /// assert!(slots.try_next().is_none());
+    /// ```
+    pub fn try_next(&self) -> Option<Id<Entity, Repr>> {
+        Repr::fetch_checked_increment(&self.next, Ordering::Relaxed).map(Id::new)
+    }
+
+    /// Return a new id.
+    ///
+    /// # Panics
+    ///
+    /// If all ids representable by `Repr` have been issued. Use [IdGenerator::try_next] to handle
+    /// that.
+    pub fn next(&self) -> Id<Entity, Repr> {
+        self.try_next().expect("IdGenerator exhausted its Repr")
+    }
+
+    /// The id that the next call to [IdGenerator::next] would return (unless another thread gets
+    /// it first).
+    pub fn peek(&self) -> Id<Entity, Repr> {
+        Id::new(Repr::load(&self.next, Ordering::Relaxed))
+    }
+}
+
+impl<Entity, Repr: AtomicRepr + fmt::Debug> fmt::Debug for IdGenerator<Entity, Repr> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IdGenerator")
+            .field("next", &Repr::load(&self.next, Ordering::Relaxed))
+            .finish()
+    }
+}
+
+macro_rules! atomic_wrapper {
+    ($(#[$meta:meta])* $atomic:ident: $wrapper:ident<$param:ident>) => {
+        $(#[$meta])*
+        pub struct $atomic<$param, Repr: AtomicRepr> {
+            value: Repr::Atomic,
+            unit: PhantomData<fn() -> $param>,
+        }
+
+        impl<$param, Repr: AtomicRepr> $atomic<$param, Repr> {
+            pub fn new(value: $wrapper<$param, Repr>) -> Self {
+                Self::from_atomic(Repr::new_atomic(value.0))
+            }
+
+            #[doc = concat!("Create from an atomic holding the `Repr`. Unlike [", stringify!($atomic), "::new], this is a `const fn`, so it can be a `static`.")]
+            pub const fn from_atomic(value: Repr::Atomic) -> Self {
+                Self {
+                    value,
+                    unit: PhantomData,
+                }
+            }
+
+            pub fn load(&self, order: Ordering) -> $wrapper<$param, Repr> {
+                $wrapper::new(Repr::load(&self.value, order))
+            }
+
+            pub fn store(&self, value: $wrapper<$param, Repr>, order: Ordering) {
+                Repr::store(&self.value, value.0, order)
+            }
+
+            /// Store `value`, and return the previous value.
+            pub fn swap(&self, value: $wrapper<$param, Repr>, order: Ordering) -> $wrapper<$param, Repr> {
+                $wrapper::new(Repr::swap(&self.value, value.0, order))
+            }
+
+            /// Store `new` if the current value is `current`. Return the previous value: `Ok` if
+            /// it was `current` (so `new` was stored), `Err` otherwise.
+            pub fn compare_exchange(
+                &self,
+                current: $wrapper<$param, Repr>,
+                new: $wrapper<$param, Repr>,
+                success: Ordering,
+                failure: Ordering,
+            ) -> Result<$wrapper<$param, Repr>, $wrapper<$param, Repr>> {
+                Repr::compare_exchange(&self.value, current.0, new.0, success, failure)
+                    .map($wrapper::new)
+                    .map_err($wrapper::new)
+            }
+
+            /// Store the maximum of the current value and `value`, and return the previous value.
+            pub fn fetch_max(&self, value: $wrapper<$param, Repr>, order: Ordering) -> $wrapper<$param, Repr> {
+                $wrapper::new(Repr::fetch_max(&self.value, value.0, order))
+            }
+        }
+
+        impl<$param, Repr: AtomicRepr + fmt::Debug> fmt::Debug for $atomic<$param, Repr> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_tuple(stringify!($atomic))
+                    .field(&Repr::load(&self.value, Ordering::Relaxed))
+                    .finish()
+            }
+        }
+    };
+}
+
+atomic_wrapper! {
+    /// An atomic [crate::Id], like the last seen one. To issue new ids, use [IdGenerator].
+    ///
+    /// ```
+    /// #![cfg_attr(
+    ///     feature = "unstable_generic_const_own_type",
+    ///     feature(generic_const_exprs)
+    /// )]
+    ///
+    /// use core::sync::atomic::{AtomicU32, Ordering};
+    /// use phantom_newtype::{AtomicId, Id};
+    ///
+    /// enum Session {}
+    /// static CURRENT: AtomicId<Session, u32> = AtomicId::from_atomic(AtomicU32::new(0));
+    ///
+    /// let previous = CURRENT.swap(Id::from(5), Ordering::AcqRel);
+    /// assert_eq!(previous, Id::from(0));
+    /// assert_eq!(
+    ///     CURRENT.compare_exchange(Id::from(4), Id::from(6), Ordering::AcqRel, Ordering::Acquire),
+    ///     Err(Id::from(5))
+    /// );
+    /// assert_eq!(CURRENT.load(Ordering::Acquire), Id::from(5));
+    /// ```
+    AtomicId: Id<Entity>
+}
+
+atomic_wrapper! {
+    /// An atomic [crate::Amount], like a shared counter or total.
+    ///
+    /// ```
+    /// #![cfg_attr(
+    ///     feature = "unstable_generic_const_own_type",
+    ///     feature(generic_const_exprs)
+    /// )]
+    ///
+    /// use core::sync::atomic::Ordering;
+    /// use phantom_newtype::{Amount, AtomicAmount};
+    ///
+    /// enum Bytes {}
+    /// type Size = Amount<Bytes, u64>;
+    /// let received = AtomicAmount::<Bytes, u64>::new(Size::from(0));
+    ///
+    /// std::thread::scope(|scope| {
+    ///     for _ in 0..4 {
+    ///         scope.spawn(|| received.fetch_add(Size::from(100), Ordering::Relaxed));
+    ///     }
+    /// });
+    /// assert_eq!(received.load(Ordering::Relaxed), Size::from(400));
+    /// ```
+    AtomicAmount: Amount<Unit>
+}
+
+atomic_wrapper! {
+    /// An atomic [crate::Instant], like the last seen block height.
+    ///
+    /// ```
+    /// #![cfg_attr(
+    ///     feature = "unstable_generic_const_own_type",
+    ///     feature(generic_const_exprs)
+    /// )]
+    ///
+    /// use core::sync::atomic::{AtomicU64, Ordering};
+    /// use phantom_newtype::{Amount, AtomicInstant, Instant};
+    ///
+    /// enum Block {}
+    /// type Height = Instant<Block, u64>;
+    /// static LAST_SEEN: AtomicInstant<Block, u64> = AtomicInstant::from_atomic(AtomicU64::new(0));
+    ///
+    /// LAST_SEEN.fetch_max(Height::from(12), Ordering::AcqRel);
+    /// // A late report of an older block doesn't move it back.
+    /// LAST_SEEN.fetch_max(Height::from(9), Ordering::AcqRel);
+    /// assert_eq!(LAST_SEEN.load(Ordering::Acquire), Height::from(12));
+    ///
+    /// assert_eq!(LAST_SEEN.fetch_add(Amount::from(1), Ordering::AcqRel), Height::from(12));
+    /// assert_eq!(LAST_SEEN.load(Ordering::Acquire), Height::from(13));
+    /// ```
+    AtomicInstant: Instant<Unit>
+}
+
+impl<Unit, Repr: AtomicRepr> AtomicAmount<Unit, Repr> {
+    /// Add `value` (wrapping on overflow, like the primitive atomics), and return the previous
+    /// value.
+    pub fn fetch_add(&self, value: Amount<Unit, Repr>, order: Ordering) -> Amount<Unit, Repr> {
+        Amount::new(Repr::fetch_add(&self.value, value.0, order))
+    }
+
+    /// Subtract `value` (wrapping on overflow, like the primitive atomics), and return the
+    /// previous value.
+    pub fn fetch_sub(&self, value: Amount<Unit, Repr>, order: Ordering) -> Amount<Unit, Repr> {
+        Amount::new(Repr::fetch_sub(&self.value, value.0, order))
+    }
+}
+
+impl<Unit, Repr: AtomicRepr> AtomicInstant<Unit, Repr> {
+    /// Advance by `value` (wrapping on overflow, like the primitive atomics), and return the
+    /// previous instant.
+    pub fn fetch_add(&self, value: Amount<Unit, Repr>, order: Ordering) -> Instant<Unit, Repr> {
+        Instant::new(Repr::fetch_add(&self.value, value.0, order))
+    }
+
+    /// Move back by `value` (wrapping on overflow, like the primitive atomics), and return the
+    /// previous instant.
+    pub fn fetch_sub(&self, value: Amount<Unit, Repr>, order: Ordering) -> Instant<Unit, Repr> {
+        Instant::new(Repr::fetch_sub(&self.value, value.0, order))
+    }
+}
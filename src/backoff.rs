@@ -0,0 +1,149 @@
+// Copyright 2024 Peter Lyons Kehl
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Exponential backoff of typed delays.
+
+use crate::repr::CheckedMul;
+use crate::Amount;
+use core::fmt;
+use core::iter::FusedIterator;
+
+/// Successive delays of exponential backoff, as an (endless) iterator of [Amount]s: `initial`
+/// first, then each delay times `multiplier`, capped at `max` (also when the multiplication
+/// overflows `Repr`). Bound the attempts with [Iterator::take].
+///
+/// ```
+/// #![cfg_attr(
+///     feature = "unstable_generic_const_own_type",
+///     feature(generic_const_exprs)
+/// )]
+///
+/// use phantom_newtype::{Amount, Backoff};
+///
+/// enum Millis {}
+/// type Delay = Amount<Millis, u32>;
+///
+/// let mut backoff = Backoff::new(Delay::from(100), 2, Delay::from(1_000));
+/// let delays: Vec<u32> = backoff.by_ref().take(6).map(|delay| delay.get_copy()).collect();
+/// assert_eq!(delays, [100, 200, 400, 800, 1_000, 1_000]);
+/// assert_eq!(backoff.attempts(), 6);
+///
+/// backoff.reset();
+/// assert_eq!(backoff.next(), Some(Delay::from(100)));
+/// ```
+pub struct Backoff<Unit, Repr> {
+    initial: Amount<Unit, Repr>,
+    multiplier: Repr,
+    max: Amount<Unit, Repr>,
+    next: Amount<Unit, Repr>,
+    attempts: u32,
+}
+
+impl<Unit, Repr: CheckedMul + Ord + Copy> Backoff<Unit, Repr> {
+    /// Start with `initial` (or `max`, if `initial` is larger).
+    pub fn new(initial: Amount<Unit, Repr>, multiplier: Repr, max: Amount<Unit, Repr>) -> Self {
+        Self {
+            initial,
+            multiplier,
+            max,
+            next: initial.min(max),
+            attempts: 0,
+        }
+    }
+
+    /// Start over from `initial`.
+    pub fn reset(&mut self) {
+        self.next = self.initial.min(self.max);
+        self.attempts = 0;
+    }
+
+    /// How many delays have been taken since the start (or since [Backoff::reset]), saturating at
+    /// `u32::MAX`.
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    /// The delay that [Iterator::next] returns next, without advancing.
+    pub fn peek(&self) -> Amount<Unit, Repr> {
+        self.next
+    }
+}
+
+impl<Unit, Repr: CheckedMul + Ord + Copy> Iterator for Backoff<Unit, Repr> {
+    type Item = Amount<Unit, Repr>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let delay = self.next;
+        self.next = match delay.get_copy().checked_mul(self.multiplier) {
+            Some(next) => Amount::new(next).min(self.max),
+            None => self.max,
+        };
+        self.attempts = self.attempts.saturating_add(1);
+        Some(delay)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
+
+impl<Unit, Repr: CheckedMul + Ord + Copy> FusedIterator for Backoff<Unit, Repr> {}
+
+impl<Unit, Repr: Clone> Clone for Backoff<Unit, Repr> {
+    fn clone(&self) -> Self {
+        Self {
+            initial: self.initial.clone(),
+            multiplier: self.multiplier.clone(),
+            max: self.max.clone(),
+            next: self.next.clone(),
+            attempts: self.attempts,
+        }
+    }
+}
+
+impl<Unit, Repr: fmt::Debug> fmt::Debug for Backoff<Unit, Repr> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Backoff")
+            .field("initial", self.initial.get())
+            .field("multiplier", &self.multiplier)
+            .field("max", self.max.get())
+            .field("next", self.next.get())
+            .field("attempts", &self.attempts)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Backoff;
+    use crate::Amount;
+
+    enum Millis {}
+
+    #[test]
+    fn overflow_caps_at_max() {
+        let mut backoff = Backoff::<Millis, u8>::new(Amount::new(100), 3, Amount::new(250));
+        assert_eq!(backoff.next(), Some(Amount::new(100)));
+        // 100 * 3 overflows `u8`.
+        assert_eq!(backoff.next(), Some(Amount::new(250)));
+        assert_eq!(backoff.next(), Some(Amount::new(250)));
+    }
+
+    #[test]
+    fn initial_above_max() {
+        let mut backoff = Backoff::<Millis, u32>::new(Amount::new(5_000), 2, Amount::new(1_000));
+        assert_eq!(backoff.peek(), Amount::new(1_000));
+        assert_eq!(backoff.next(), Some(Amount::new(1_000)));
+    }
+}
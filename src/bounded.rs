@@ -0,0 +1,305 @@
+// Copyright 2024 Peter Lyons Kehl
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Amounts restricted to a range.
+
+use crate::validate::ValidationError;
+use crate::Amount;
+use core::cmp::Ordering;
+use core::convert::TryFrom;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::ops::{Add, Sub};
+
+/// An [Amount] of a primitive integer (other than `u128`) that is always within `MIN..=MAX`, like
+/// a percentage in `0..=100` or a port in `1..=65535`.
+///
+/// [BoundedAmount::new] rejects values out of the range, [BoundedAmount::clamped] moves them into
+/// it, and `+` and `-` saturate at the bounds. `MIN` and `MAX` are `i128`, so that one type works
+/// for any integer `Repr`; bounds that `Repr` can't hold (or `MIN > MAX`) fail to compile.
+///
+/// ```
+/// #![cfg_attr(
+///     feature = "unstable_generic_const_own_type",
+///     feature(generic_const_exprs)
+/// )]
+///
+/// use core::convert::TryFrom;
+/// use phantom_newtype::validate::ValidationError;
+/// use phantom_newtype::{Amount, BoundedAmount};
+///
+/// enum Percent {}
+/// type Percentage = BoundedAmount<Percent, u8, 0, 100>;
+/// enum PortNumber {}
+/// type Port = BoundedAmount<PortNumber, u16, 1, 65535>;
+///
+/// const HALF: Percentage = Percentage::new_unchecked(50);
+///
+/// assert_eq!(Percentage::new(100).map(|p| p.get_copy()), Ok(100));
+/// assert_eq!(Percentage::new(101), Err(ValidationError::TooLarge));
+/// assert_eq!(Port::new(0), Err(ValidationError::TooSmall));
+/// assert_eq!(Percentage::clamped(250), Percentage::MAX);
+///
+/// // Arithmetic saturates at the bounds, not at the bounds of `u8`.
+/// assert_eq!(HALF + HALF + HALF, Percentage::MAX);
+/// assert_eq!(HALF - Amount::from(70), Percentage::MIN);
+/// assert_eq!(HALF + Amount::from(7), Percentage::new(57).unwrap());
+///
+/// let unbounded: Amount<Percent, u8> = HALF.into();
+/// assert_eq!(Percentage::try_from(unbounded), Ok(HALF));
+/// ```
+///
+/// ```compile_fail
+/// use phantom_newtype::BoundedAmount;
+///
+/// enum Percent {}
+/// // 300 doesn't fit in `u8`.
+/// type TooWide = BoundedAmount<Percent, u8, 0, 300>;
+/// const MAX: TooWide = TooWide::MAX;
+/// ```
+#[repr(transparent)]
+pub struct BoundedAmount<Unit, Repr, const MIN: i128, const MAX: i128>(Amount<Unit, Repr>);
+
+macro_rules! impl_bounded {
+    ($($t:ty)*) => {
+        $(
+            impl<Unit, const MIN: i128, const MAX: i128> BoundedAmount<Unit, $t, MIN, MAX> {
+                // Evaluated (so checked) wherever a value is created.
+                const BOUNDS: (i128, i128) = {
+                    assert!(MIN <= MAX, "BoundedAmount needs MIN <= MAX");
+                    assert!(
+                        MIN >= <$t>::MIN as i128 && MAX <= <$t>::MAX as i128,
+                        "BoundedAmount's MIN and MAX must fit in Repr"
+                    );
+                    (MIN, MAX)
+                };
+
+                pub const MIN: Self = Self(Amount::new(Self::BOUNDS.0 as $t));
+                pub const MAX: Self = Self(Amount::new(Self::BOUNDS.1 as $t));
+
+                /// `repr`, if it's within `MIN..=MAX`.
+                pub const fn new(repr: $t) -> Result<Self, ValidationError> {
+                    let (min, max) = Self::BOUNDS;
+                    if (repr as i128) < min {
+                        Err(ValidationError::TooSmall)
+                    } else if (repr as i128) > max {
+                        Err(ValidationError::TooLarge)
+                    } else {
+                        Ok(Self(Amount::new(repr)))
+                    }
+                }
+
+                /// `repr`, which the caller guarantees to be within `MIN..=MAX`, for constants.
+                /// Checked only in debug builds (in `const` items, too).
+                pub const fn new_unchecked(repr: $t) -> Self {
+                    let (min, max) = Self::BOUNDS;
+                    debug_assert!(repr as i128 >= min && repr as i128 <= max);
+                    Self(Amount::new(repr))
+                }
+
+                /// `repr`, moved to the nearest bound if it's out of `MIN..=MAX`.
+                pub const fn clamped(repr: $t) -> Self {
+                    match Self::new(repr) {
+                        Ok(bounded) => bounded,
+                        Err(ValidationError::TooSmall) => Self::MIN,
+                        Err(_) => Self::MAX,
+                    }
+                }
+
+                // Clamps an exact (`i128`) result of arithmetic.
+                const fn clamped_i128(value: i128) -> Self {
+                    let (min, max) = Self::BOUNDS;
+                    if value < min {
+                        Self::MIN
+                    } else if value > max {
+                        Self::MAX
+                    } else {
+                        Self(Amount::new(value as $t))
+                    }
+                }
+            }
+
+            impl<Unit, const MIN: i128, const MAX: i128> Add<Amount<Unit, $t>>
+                for BoundedAmount<Unit, $t, MIN, MAX>
+            {
+                type Output = Self;
+
+                fn add(self, rhs: Amount<Unit, $t>) -> Self {
+                    match (self.0 .0 as i128).checked_add(rhs.0 as i128) {
+                        Some(sum) => Self::clamped_i128(sum),
+                        // Only for `i128` operands: beyond `i128`, so beyond the bounds, too.
+                        None if rhs.0 as i128 > 0 => Self::MAX,
+                        None => Self::MIN,
+                    }
+                }
+            }
+
+            impl<Unit, const MIN: i128, const MAX: i128> Sub<Amount<Unit, $t>>
+                for BoundedAmount<Unit, $t, MIN, MAX>
+            {
+                type Output = Self;
+
+                fn sub(self, rhs: Amount<Unit, $t>) -> Self {
+                    match (self.0 .0 as i128).checked_sub(rhs.0 as i128) {
+                        Some(difference) => Self::clamped_i128(difference),
+                        // Only for `i128` operands: beyond `i128`, so beyond the bounds, too.
+                        None if (rhs.0 as i128) < 0 => Self::MAX,
+                        None => Self::MIN,
+                    }
+                }
+            }
+
+            impl<Unit, const MIN: i128, const MAX: i128> Add for BoundedAmount<Unit, $t, MIN, MAX> {
+                type Output = Self;
+
+                fn add(self, rhs: Self) -> Self {
+                    self + rhs.0
+                }
+            }
+
+            impl<Unit, const MIN: i128, const MAX: i128> Sub for BoundedAmount<Unit, $t, MIN, MAX> {
+                type Output = Self;
+
+                fn sub(self, rhs: Self) -> Self {
+                    self - rhs.0
+                }
+            }
+
+            impl<Unit, const MIN: i128, const MAX: i128> TryFrom<Amount<Unit, $t>>
+                for BoundedAmount<Unit, $t, MIN, MAX>
+            {
+                type Error = ValidationError;
+
+                fn try_from(amount: Amount<Unit, $t>) -> Result<Self, ValidationError> {
+                    Self::new(amount.0)
+                }
+            }
+        )*
+    };
+}
+impl_bounded!(u8 u16 u32 u64 usize i8 i16 i32 i64 i128 isize);
+
+impl<Unit, Repr, const MIN: i128, const MAX: i128> BoundedAmount<Unit, Repr, MIN, MAX> {
+    /// A reference to the wrapped value.
+    pub const fn get(&self) -> &Repr {
+        self.0.get()
+    }
+
+    /// The amount, without the bounds.
+    pub const fn amount(&self) -> &Amount<Unit, Repr> {
+        &self.0
+    }
+}
+
+impl<Unit, Repr: Copy, const MIN: i128, const MAX: i128> BoundedAmount<Unit, Repr, MIN, MAX> {
+    /// A copy of the wrapped value.
+    pub const fn get_copy(&self) -> Repr {
+        self.0.get_copy()
+    }
+}
+
+impl<Unit, Repr, const MIN: i128, const MAX: i128> From<BoundedAmount<Unit, Repr, MIN, MAX>>
+    for Amount<Unit, Repr>
+{
+    fn from(bounded: BoundedAmount<Unit, Repr, MIN, MAX>) -> Self {
+        bounded.0
+    }
+}
+
+impl<Unit, Repr: Copy, const MIN: i128, const MAX: i128> Clone
+    for BoundedAmount<Unit, Repr, MIN, MAX>
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<Unit, Repr: Copy, const MIN: i128, const MAX: i128> Copy
+    for BoundedAmount<Unit, Repr, MIN, MAX>
+{
+}
+
+impl<Unit, Repr: PartialEq, const MIN: i128, const MAX: i128> PartialEq
+    for BoundedAmount<Unit, Repr, MIN, MAX>
+{
+    fn eq(&self, rhs: &Self) -> bool {
+        self.get() == rhs.get()
+    }
+}
+
+impl<Unit, Repr: Eq, const MIN: i128, const MAX: i128> Eq for BoundedAmount<Unit, Repr, MIN, MAX> {}
+
+impl<Unit, Repr: PartialOrd, const MIN: i128, const MAX: i128> PartialOrd
+    for BoundedAmount<Unit, Repr, MIN, MAX>
+{
+    fn partial_cmp(&self, rhs: &Self) -> Option<Ordering> {
+        self.get().partial_cmp(rhs.get())
+    }
+}
+
+impl<Unit, Repr: Ord, const MIN: i128, const MAX: i128> Ord
+    for BoundedAmount<Unit, Repr, MIN, MAX>
+{
+    fn cmp(&self, rhs: &Self) -> Ordering {
+        self.get().cmp(rhs.get())
+    }
+}
+
+impl<Unit, Repr: Hash, const MIN: i128, const MAX: i128> Hash
+    for BoundedAmount<Unit, Repr, MIN, MAX>
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.get().hash(state)
+    }
+}
+
+impl<Unit, Repr: fmt::Display, const MIN: i128, const MAX: i128> fmt::Display
+    for BoundedAmount<Unit, Repr, MIN, MAX>
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.get(), f)
+    }
+}
+
+impl<Unit, Repr: fmt::Debug, const MIN: i128, const MAX: i128> fmt::Debug
+    for BoundedAmount<Unit, Repr, MIN, MAX>
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BoundedAmount;
+    use crate::Amount;
+
+    enum Unit {}
+    type NonNegative = BoundedAmount<Unit, i128, 0, { i128::MAX }>;
+    type NonPositive = BoundedAmount<Unit, i128, { i128::MIN }, 0>;
+
+    #[test]
+    fn i128_add_saturates_beyond_i128() {
+        assert_eq!(NonNegative::MAX + Amount::from(i128::MAX), NonNegative::MAX);
+        assert_eq!(NonPositive::MIN + Amount::from(i128::MIN), NonPositive::MIN);
+        assert_eq!(NonNegative::MAX + Amount::from(i128::MIN), NonNegative::MIN);
+    }
+
+    #[test]
+    fn i128_sub_saturates_beyond_i128() {
+        assert_eq!(NonNegative::MAX - Amount::from(i128::MIN), NonNegative::MAX);
+        assert_eq!(NonPositive::MIN - Amount::from(i128::MAX), NonPositive::MIN);
+        assert_eq!(NonPositive::MIN - Amount::from(i128::MIN), NonPositive::MAX);
+    }
+}
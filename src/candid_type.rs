@@ -0,0 +1,58 @@
+// Copyright 2024 Peter Lyons Kehl
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! `candid::CandidType` of [Amount]s, [Id]s and [Instant]s: the Candid type and encoding of their
+//! `Repr`, the same way they serialize with `serde` as `Repr`. So a canister method taking
+//! `Id<User, u64>` has the `nat64` argument type in its `.did` interface. Requires feature `candid`.
+//!
+//! ```
+//! #![cfg_attr(
+//!     feature = "unstable_generic_const_own_type",
+//!     feature(generic_const_exprs)
+//! )]
+//!
+//! use candid::{decode_one, encode_one};
+//! use phantom_newtype::Id;
+//!
+//! enum User {}
+//!
+//! let bytes = encode_one(Id::<User, u64>::from(7)).unwrap();
+//! assert_eq!(bytes, encode_one(7u64).unwrap());
+//! assert_eq!(decode_one::<Id<User, u64>>(&bytes).unwrap(), Id::from(7));
+//! ```
+
+use crate::amount::Amount;
+use crate::id::Id;
+use crate::instant::Instant;
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+use crate::trait_flag::TraitFlags;
+use candid::types::{Serializer, Type};
+use candid::CandidType;
+
+macro_rules! impl_candid_type {
+    ($($wrapper:ident)*) => {
+        $(
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<const TF: TraitFlags, Unit, Repr: CandidType> CandidType for $wrapper<TF, Unit, Repr> {
+                fn _ty() -> Type {
+                    Repr::ty()
+                }
+                fn idl_serialize<S: Serializer>(&self, serializer: S) -> Result<(), S::Error> {
+                    self.0.idl_serialize(serializer)
+                }
+            }
+        )*
+    };
+}
+impl_candid_type!(Amount Id Instant);
@@ -0,0 +1,154 @@
+// Copyright 2024 Peter Lyons Kehl
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! CBOR-tagged serialization, for `#[serde(with = "phantom_newtype::cbor_tag")]`. Requires feature
+//! `ciborium`.
+//!
+//! A unit (or entity) marker declares its CBOR semantic tag with [CborTagOf]. Then its
+//! [crate::Amount]s, [crate::Id]s and [crate::Instant]s serialize as their `Repr` wrapped in that
+//! tag. Deserialization fails unless the tag matches, so data written for one unit doesn't load as
+//! another. Use [accepted] while migrating from untagged data.
+//!
+//! Tags work with `ciborium` only. Other formats ignore them.
+//!
+//! ```
+//! #![cfg_attr(
+//!     feature = "unstable_generic_const_own_type",
+//!     feature(generic_const_exprs)
+//! )]
+//!
+//! use phantom_newtype::cbor_tag::CborTagOf;
+//! use phantom_newtype::Amount;
+//! use serde::{Deserialize, Serialize};
+//!
+//! enum Meters {}
+//! impl CborTagOf for Meters {
+//!     const TAG: u64 = 80_000;
+//! }
+//! enum Seconds {}
+//! impl CborTagOf for Seconds {
+//!     const TAG: u64 = 80_001;
+//! }
+//!
+//! #[derive(Serialize, Deserialize, PartialEq, Debug)]
+//! struct Distance(#[serde(with = "phantom_newtype::cbor_tag")] Amount<Meters, u32>);
+//! #[derive(Serialize, Deserialize, PartialEq, Debug)]
+//! struct Duration(#[serde(with = "phantom_newtype::cbor_tag")] Amount<Seconds, u32>);
+//!
+//! let mut bytes = Vec::new();
+//! ciborium::into_writer(&Distance(Amount::from(5)), &mut bytes).unwrap();
+//! // Tag 80000 (major type 6, 4-byte argument), then unsigned integer 5.
+//! assert_eq!(bytes, [0xDA, 0x00, 0x01, 0x38, 0x80, 0x05]);
+//!
+//! let distance: Distance = ciborium::from_reader(&bytes[..]).unwrap();
+//! assert_eq!(distance, Distance(Amount::from(5)));
+//! assert!(ciborium::from_reader::<Duration, _>(&bytes[..]).is_err());
+//! ```
+
+use crate::serde_unit::HasUnit;
+use ciborium::tag::Captured;
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// The CBOR semantic tag of a unit (or entity) marker. Pick numbers from the IANA "CBOR Tags"
+/// registry's first-come-first-served range (32768 and up), or register your own.
+#[diagnostic::on_unimplemented(
+    message = "unit `{Self}` has no CBOR tag",
+    label = "no `cbor_tag::CborTagOf` for `{Self}`",
+    note = "`cbor_tag` needs `impl CborTagOf for {Self} {{ const TAG: u64 = ...; }}`"
+)]
+pub trait CborTagOf {
+    const TAG: u64;
+}
+
+pub fn serialize<W, S>(value: &W, serializer: S) -> Result<S::Ok, S::Error>
+where
+    W: HasUnit + Serialize,
+    W::Unit: CborTagOf,
+    S: Serializer,
+{
+    Captured(Some(W::Unit::TAG), value).serialize(serializer)
+}
+
+pub fn deserialize<'de, W, D>(deserializer: D) -> Result<W, D::Error>
+where
+    W: HasUnit + Deserialize<'de>,
+    W::Unit: CborTagOf,
+    D: Deserializer<'de>,
+{
+    match Captured::<W>::deserialize(deserializer)? {
+        Captured(Some(tag), value) if tag == W::Unit::TAG => Ok(value),
+        Captured(Some(tag), _) => Err(D::Error::custom(format_args!(
+            "expected CBOR tag {}, found tag {}",
+            W::Unit::TAG,
+            tag
+        ))),
+        Captured(None, _) => Err(D::Error::custom(format_args!(
+            "expected CBOR tag {}, found an untagged value",
+            W::Unit::TAG
+        ))),
+    }
+}
+
+/// Like [serialize](super::serialize) and [deserialize](super::deserialize), but deserialization
+/// accepts untagged values, too (a wrong tag is still an error). For data persisted before the
+/// unit got its tag.
+///
+/// ```
+/// #![cfg_attr(
+///     feature = "unstable_generic_const_own_type",
+///     feature(generic_const_exprs)
+/// )]
+///
+/// use phantom_newtype::cbor_tag::CborTagOf;
+/// use phantom_newtype::Id;
+/// use serde::{Deserialize, Serialize};
+///
+/// enum User {}
+/// impl CborTagOf for User {
+///     const TAG: u64 = 80_002;
+/// }
+///
+/// #[derive(Serialize, Deserialize, PartialEq, Debug)]
+/// struct Owner(#[serde(with = "phantom_newtype::cbor_tag::accepted")] Id<User, u64>);
+///
+/// // Written before `User` had a tag: plain unsigned integer 7.
+/// let legacy = [0x07];
+/// assert_eq!(ciborium::from_reader::<Owner, _>(&legacy[..]).unwrap(), Owner(Id::from(7)));
+///
+/// let mut bytes = Vec::new();
+/// ciborium::into_writer(&Owner(Id::from(7)), &mut bytes).unwrap();
+/// assert_eq!(bytes, [0xDA, 0x00, 0x01, 0x38, 0x82, 0x07]);
+/// ```
+pub mod accepted {
+    use super::*;
+
+    pub use super::serialize;
+
+    pub fn deserialize<'de, W, D>(deserializer: D) -> Result<W, D::Error>
+    where
+        W: HasUnit + Deserialize<'de>,
+        W::Unit: CborTagOf,
+        D: Deserializer<'de>,
+    {
+        match Captured::<W>::deserialize(deserializer)? {
+            Captured(Some(tag), _) if tag != W::Unit::TAG => Err(D::Error::custom(format_args!(
+                "expected CBOR tag {} (or none), found tag {}",
+                W::Unit::TAG,
+                tag
+            ))),
+            Captured(_, value) => Ok(value),
+        }
+    }
+}
@@ -0,0 +1,168 @@
+// Copyright 2024 Peter Lyons Kehl
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Clocks of typed [Instant]s: [ClockOf], implemented by [ManualClock] (for deterministic tests)
+//! and, with feature `std`, by [SystemClock] and [MonotonicClock].
+//!
+//! ```
+//! #![cfg_attr(
+//!     feature = "unstable_generic_const_own_type",
+//!     feature(generic_const_exprs)
+//! )]
+//!
+//! use phantom_newtype::clock::{ClockOf, ManualClock};
+//! use phantom_newtype::{Amount, Instant};
+//!
+//! enum Millis {}
+//!
+//! struct Session<C> {
+//!     clock: C,
+//!     expires: Instant<Millis, u64>,
+//! }
+//!
+//! impl<C: ClockOf<Millis, Repr = u64>> Session<C> {
+//!     fn is_expired(&self) -> bool {
+//!         self.clock.now() >= self.expires
+//!     }
+//! }
+//!
+//! let session = Session {
+//!     clock: ManualClock::new(Instant::from(1_000)),
+//!     expires: Instant::from(1_500),
+//! };
+//! assert!(!session.is_expired());
+//! session.clock.advance(Amount::from(500));
+//! assert!(session.is_expired());
+//! ```
+
+use crate::{Amount, Instant};
+use core::cell::Cell;
+use core::ops::AddAssign;
+
+/// A source of the current [Instant] of `Unit`. Pass one to code that needs the time, so that
+/// tests can pass a [ManualClock] instead.
+pub trait ClockOf<Unit> {
+    type Repr;
+
+    fn now(&self) -> Instant<Unit, Self::Repr>;
+}
+
+/// A clock that shows what it's set to, for tests. It's not `Sync`: each test thread has its own.
+pub struct ManualClock<Unit, Repr> {
+    now: Cell<Instant<Unit, Repr>>,
+}
+
+impl<Unit, Repr: Copy> ManualClock<Unit, Repr> {
+    pub const fn new(now: Instant<Unit, Repr>) -> Self {
+        Self {
+            now: Cell::new(now),
+        }
+    }
+
+    pub fn set(&self, now: Instant<Unit, Repr>) {
+        self.now.set(now);
+    }
+
+    /// Moves the clock forward by `by`.
+    pub fn advance(&self, by: Amount<Unit, Repr>)
+    where
+        Repr: AddAssign,
+    {
+        self.now.set(self.now.get() + by);
+    }
+}
+
+impl<Unit, Repr: Copy> ClockOf<Unit> for ManualClock<Unit, Repr> {
+    type Repr = Repr;
+
+    fn now(&self) -> Instant<Unit, Repr> {
+        self.now.get()
+    }
+}
+
+#[cfg(feature = "std")]
+pub use std_clocks::{MonotonicClock, SystemClock};
+
+#[cfg(feature = "std")]
+mod std_clocks {
+    use super::ClockOf;
+    use crate::time::{duration_to_units, ClockSource, EpochUnit, TimeUnit};
+    use crate::Instant;
+    use core::convert::TryFrom;
+
+    /// The system clock (`std::time::SystemTime`), for [EpochUnit]s. See [crate::time].
+    #[derive(Clone, Copy, Debug, Default)]
+    pub struct SystemClock;
+
+    impl<Unit: EpochUnit> ClockOf<Unit> for SystemClock {
+        type Repr = u64;
+
+        /// # Panics
+        /// If the system clock is before the Unix epoch, or out of range of `u64`.
+        fn now(&self) -> Instant<Unit, u64> {
+            Instant::new(<Unit as ClockSource<u64>>::now())
+        }
+    }
+
+    /// A monotonic clock (`std::time::Instant`), showing the time since it was created. Unlike
+    /// [SystemClock], it never goes backwards.
+    ///
+    /// ```
+    /// #![cfg_attr(
+    ///     feature = "unstable_generic_const_own_type",
+    ///     feature(generic_const_exprs)
+    /// )]
+    ///
+    /// use phantom_newtype::clock::{ClockOf, MonotonicClock};
+    /// use phantom_newtype::time::TimeUnit;
+    ///
+    /// enum Micros {}
+    /// impl TimeUnit for Micros {
+    ///     const NANOS_PER_UNIT: u64 = 1_000;
+    /// }
+    ///
+    /// let clock = MonotonicClock::new();
+    /// let start = ClockOf::<Micros>::now(&clock);
+    /// assert!(ClockOf::<Micros>::now(&clock) >= start);
+    /// ```
+    #[derive(Clone, Copy, Debug)]
+    pub struct MonotonicClock {
+        start: std::time::Instant,
+    }
+
+    impl MonotonicClock {
+        pub fn new() -> Self {
+            Self {
+                start: std::time::Instant::now(),
+            }
+        }
+    }
+
+    impl Default for MonotonicClock {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    impl<Unit: TimeUnit> ClockOf<Unit> for MonotonicClock {
+        type Repr = u64;
+
+        /// # Panics
+        /// If the time since the clock's creation is out of range of `u64`.
+        fn now(&self) -> Instant<Unit, u64> {
+            let units = duration_to_units::<Unit>(self.start.elapsed());
+            Instant::new(u64::try_from(units).expect("monotonic clock out of range"))
+        }
+    }
+}
@@ -0,0 +1,160 @@
+// Copyright 2024 Peter Lyons Kehl
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Conversions between [crate::Amount]s, [crate::Id]s and [crate::Instant]s that differ only in
+//! their (primitive integer) `Repr`.
+//!
+//! They mirror `core`: `From` where `core` has a lossless `From` between the primitives, and
+//! `TryFrom` (with `core::num::TryFromIntError`) otherwise. (A blanket impl over `Repr2:
+//! TryFrom<Repr>` would overlap with `core`'s blanket `TryFrom` for types implementing `Into`.)
+//!
+//! ```
+//! #![cfg_attr(
+//!     feature = "unstable_generic_const_own_type",
+//!     feature(generic_const_exprs)
+//! )]
+//!
+//! use core::convert::TryFrom;
+//! use phantom_newtype::{Amount, Id};
+//!
+//! enum Bytes {}
+//! let small = Amount::<Bytes, u32>::from(7);
+//! let big: Amount<Bytes, u64> = small.into();
+//! assert_eq!(big.get_copy(), 7);
+//!
+//! enum User {}
+//! let id = Id::<User, u64>::from(u64::from(u32::MAX) + 1);
+//! assert!(Id::<User, u32>::try_from(id).is_err());
+//! ```
+//!
+//! Wrappers of the same marker type, `Repr` and trait flags also convert into each other, with
+//! `into_amount`, `into_id` and `into_instant` (all `const`, and free of any cost). That's for
+//! domains that use the same value as an id in one place and as an amount or instant in another.
+//!
+//! ```
+//! #![cfg_attr(
+//!     feature = "unstable_generic_const_own_type",
+//!     feature(generic_const_exprs)
+//! )]
+//!
+//! use phantom_newtype::{Amount, Id, Instant};
+//!
+//! enum Sequence {}
+//! const NEXT: Id<Sequence, u64> = Id::new(41).into_instant().into_amount().into_id();
+//!
+//! let issued: Amount<Sequence, u64> = NEXT.into_amount();
+//! assert_eq!(issued.get_copy(), 41);
+//! let position: Instant<Sequence, u64> = issued.into_instant();
+//! assert_eq!(position.get_copy(), 41);
+//!
+//! let label = Id::<Sequence, String>::from("first".to_owned()).into_amount();
+//! assert_eq!(label.get(), "first");
+//! ```
+
+use crate::amount::Amount;
+use crate::id::Id;
+use crate::instant::Instant;
+use crate::to::transmute_unchecked;
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+use crate::trait_flag::TraitFlags;
+use core::convert::TryFrom;
+use core::num::TryFromIntError;
+
+macro_rules! impl_from {
+    ($wrapper:ident($value:ident => $get:expr), $from:ty => $($to:ty)*) => {
+        $(
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<const TF: TraitFlags, Unit> From<$wrapper<TF, Unit, $from>>
+                for $wrapper<TF, Unit, $to>
+            {
+                fn from($value: $wrapper<TF, Unit, $from>) -> Self {
+                    Self::new(<$to>::from($get))
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_try_from {
+    ($wrapper:ident($value:ident => $get:expr), $from:ty => $($to:ty)*) => {
+        $(
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<const TF: TraitFlags, Unit> TryFrom<$wrapper<TF, Unit, $from>>
+                for $wrapper<TF, Unit, $to>
+            {
+                type Error = TryFromIntError;
+
+                fn try_from($value: $wrapper<TF, Unit, $from>) -> Result<Self, Self::Error> {
+                    <$to>::try_from($get).map(Self::new)
+                }
+            }
+        )*
+    };
+}
+
+// Each wrapper comes with an expression getting the `Repr` by value (`Id::get` returns a reference).
+macro_rules! impl_conversions {
+    ($($wrapper:ident($value:ident => $get:expr);)*) => {
+        $(
+            impl_from!($wrapper($value => $get), u8 => u16 u32 u64 u128 usize i16 i32 i64 i128 isize);
+            impl_from!($wrapper($value => $get), u16 => u32 u64 u128 usize i32 i64 i128);
+            impl_from!($wrapper($value => $get), u32 => u64 u128 i64 i128);
+            impl_from!($wrapper($value => $get), u64 => u128 i128);
+            impl_from!($wrapper($value => $get), i8 => i16 i32 i64 i128 isize);
+            impl_from!($wrapper($value => $get), i16 => i32 i64 i128 isize);
+            impl_from!($wrapper($value => $get), i32 => i64 i128);
+            impl_from!($wrapper($value => $get), i64 => i128);
+
+            impl_try_from!($wrapper($value => $get), u8 => i8);
+            impl_try_from!($wrapper($value => $get), u16 => u8 i8 i16 isize);
+            impl_try_from!($wrapper($value => $get), u32 => u8 u16 usize i8 i16 i32 isize);
+            impl_try_from!($wrapper($value => $get), u64 => u8 u16 u32 usize i8 i16 i32 i64 isize);
+            impl_try_from!($wrapper($value => $get), u128 => u8 u16 u32 u64 usize i8 i16 i32 i64 i128 isize);
+            impl_try_from!($wrapper($value => $get), usize => u8 u16 u32 u64 u128 i8 i16 i32 i64 i128 isize);
+            impl_try_from!($wrapper($value => $get), i8 => u8 u16 u32 u64 u128 usize);
+            impl_try_from!($wrapper($value => $get), i16 => u8 u16 u32 u64 u128 usize i8);
+            impl_try_from!($wrapper($value => $get), i32 => u8 u16 u32 u64 u128 usize i8 i16 isize);
+            impl_try_from!($wrapper($value => $get), i64 => u8 u16 u32 u64 u128 usize i8 i16 i32 isize);
+            impl_try_from!($wrapper($value => $get), i128 => u8 u16 u32 u64 u128 usize i8 i16 i32 i64 isize);
+            impl_try_from!($wrapper($value => $get), isize => u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128);
+        )*
+    };
+}
+impl_conversions! {
+    Amount(value => *value.get());
+    Id(value => *value.get());
+    Instant(value => *value.get());
+}
+
+macro_rules! impl_into_wrapper {
+    ($($from:ident => $($method:ident $to:ident),*;)*) => {
+        $(
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<const TF: TraitFlags, Unit, Repr> $from<TF, Unit, Repr> {
+                $(
+                    #[doc = concat!("The same value as [", stringify!($to), "].")]
+                    pub const fn $method(self) -> $to<TF, Unit, Repr> {
+                        // SAFETY: All three wrappers are `#[repr(transparent)]` over `Repr`.
+                        unsafe { transmute_unchecked(self) }
+                    }
+                )*
+            }
+        )*
+    };
+}
+impl_into_wrapper! {
+    Amount => into_id Id, into_instant Instant;
+    Id => into_amount Amount, into_instant Instant;
+    Instant => into_amount Amount, into_id Id;
+}
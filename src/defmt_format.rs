@@ -0,0 +1,37 @@
+// Copyright 2024 Peter Lyons Kehl
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! `defmt::Format` of [Amount]s, [Id]s and [Instant]s: it delegates to `Repr`, so that a wrapper
+//! logs like its `Repr`. Requires feature `defmt`.
+
+use crate::amount::Amount;
+use crate::id::Id;
+use crate::instant::Instant;
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+use crate::trait_flag::TraitFlags;
+use defmt::{Format, Formatter};
+
+macro_rules! impl_format {
+    ($($wrapper:ident)*) => {
+        $(
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<const TF: TraitFlags, Unit, Repr: Format> Format for $wrapper<TF, Unit, Repr> {
+                fn format(&self, f: Formatter<'_>) {
+                    self.0.format(f)
+                }
+            }
+        )*
+    };
+}
+impl_format!(Amount Id Instant);
@@ -0,0 +1,101 @@
+// Copyright 2024 Peter Lyons Kehl
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! `diesel` `ToSql`, `FromSql`, `Queryable` and `AsExpression` of [Amount]s, [Id]s and
+//! [Instant]s: they delegate to `Repr`, so that a wrapper can be a column's Rust type (like a typed
+//! primary key) wherever its `Repr` can. Requires feature `diesel`.
+
+use crate::amount::Amount;
+use crate::id::Id;
+use crate::instant::Instant;
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+use crate::trait_flag::TraitFlags;
+use diesel::backend::Backend;
+use diesel::deserialize::{self, FromSql, Queryable};
+use diesel::expression::{AsExpression, TypedExpressionType};
+use diesel::serialize::{self, Output, ToSql};
+use diesel::sql_types::{SingleValue, SqlType};
+
+macro_rules! impl_diesel {
+    ($($wrapper:ident)*) => {
+        $(
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<const TF: TraitFlags, Unit, Repr, ST, DB> ToSql<ST, DB> for $wrapper<TF, Unit, Repr>
+            where
+                DB: Backend,
+                Repr: ToSql<ST, DB>,
+            {
+                fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, DB>) -> serialize::Result {
+                    self.0.to_sql(out)
+                }
+            }
+
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<const TF: TraitFlags, Unit, Repr, ST, DB> FromSql<ST, DB> for $wrapper<TF, Unit, Repr>
+            where
+                DB: Backend,
+                Repr: FromSql<ST, DB>,
+            {
+                fn from_sql(bytes: DB::RawValue<'_>) -> deserialize::Result<Self> {
+                    Repr::from_sql(bytes).map($wrapper::new)
+                }
+
+                fn from_nullable_sql(bytes: Option<DB::RawValue<'_>>) -> deserialize::Result<Self> {
+                    Repr::from_nullable_sql(bytes).map($wrapper::new)
+                }
+            }
+
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<const TF: TraitFlags, Unit, Repr, ST, DB> Queryable<ST, DB> for $wrapper<TF, Unit, Repr>
+            where
+                DB: Backend,
+                ST: SingleValue,
+                Self: FromSql<ST, DB>,
+            {
+                type Row = Self;
+
+                fn build(row: Self) -> deserialize::Result<Self> {
+                    Ok(row)
+                }
+            }
+
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<const TF: TraitFlags, Unit, Repr, ST> AsExpression<ST> for $wrapper<TF, Unit, Repr>
+            where
+                ST: SqlType + TypedExpressionType,
+                Repr: AsExpression<ST>,
+            {
+                type Expression = Repr::Expression;
+
+                fn as_expression(self) -> Self::Expression {
+                    self.0.as_expression()
+                }
+            }
+
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<'a, const TF: TraitFlags, Unit, Repr, ST> AsExpression<ST> for &'a $wrapper<TF, Unit, Repr>
+            where
+                ST: SqlType + TypedExpressionType,
+                &'a Repr: AsExpression<ST>,
+            {
+                type Expression = <&'a Repr as AsExpression<ST>>::Expression;
+
+                fn as_expression(self) -> Self::Expression {
+                    (&self.0).as_expression()
+                }
+            }
+        )*
+    };
+}
+impl_diesel!(Amount Id Instant);
@@ -12,8 +12,11 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use core::cmp::Ordering;
 use core::fmt;
+use core::hash::{Hash, Hasher};
 use core::marker::PhantomData;
+use core::ops::{Deref, DerefMut};
 
 /// This trait provides display capabilities for the type it
 /// parameterized with, `T`.
@@ -22,6 +25,11 @@ use core::marker::PhantomData;
 /// [newtype
 /// idiom](https://doc.rust-lang.org/rust-by-example/generics/new_types.html)
 /// instead of relying on `phantom_newtype`.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` has no displayer for `{T}`",
+    label = "no `DisplayerOf<{T}>` for `{Self}`",
+    note = "to `.display()` an amount, id or instant, implement `DisplayerOf` for its unit (or entity) marker, or wrap it in `WithDisplayer`"
+)]
 pub trait DisplayerOf<T> {
     fn display(value: &T, f: &mut fmt::Formatter<'_>) -> fmt::Result;
 }
@@ -34,7 +42,7 @@ where
     Displayer: DisplayerOf<T>,
 {
     value: &'a T,
-    displayer_tag: PhantomData<Displayer>,
+    displayer_tag: PhantomData<fn() -> Displayer>,
 }
 
 impl<'a, T, Displayer> DisplayProxy<'a, T, Displayer>
@@ -53,7 +61,506 @@ impl<'a, T, Displayer> fmt::Display for DisplayProxy<'a, T, Displayer>
 where
     Displayer: DisplayerOf<T>,
 {
+    #[allow(clippy::needless_borrow)]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         Displayer::display(&self.value, f)
     }
 }
+
+/// Like [DisplayerOf], but for `Debug` formatting (through [DebugProxy]). Use it when `Display` is
+/// user-facing, but `Debug` should show, for example, raw hex or internal structure.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` has no debuger for `{T}`",
+    label = "no `DebugerOf<{T}>` for `{Self}`",
+    note = "to `.debug()` an amount, id or instant, implement `DebugerOf` for its unit (or entity) marker"
+)]
+pub trait DebugerOf<T> {
+    fn debug(value: &T, f: &mut fmt::Formatter<'_>) -> fmt::Result;
+}
+
+/// An object `DebugProxy`, when is asked to `Debug`-format itself,
+/// formats `T` using the specified `Debuger` instead.
+#[repr(transparent)]
+pub struct DebugProxy<'a, T, Debuger>
+where
+    Debuger: DebugerOf<T>,
+{
+    value: &'a T,
+    debuger_tag: PhantomData<fn() -> Debuger>,
+}
+
+impl<'a, T, Debuger> DebugProxy<'a, T, Debuger>
+where
+    Debuger: DebugerOf<T>,
+{
+    pub fn new(value: &'a T) -> Self {
+        Self {
+            value,
+            debuger_tag: PhantomData,
+        }
+    }
+}
+
+impl<'a, T, Debuger> fmt::Debug for DebugProxy<'a, T, Debuger>
+where
+    Debuger: DebugerOf<T>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Debuger::debug(self.value, f)
+    }
+}
+
+/// A wrapper (like an [crate::Id]) displayed with `Displayer`, chosen where the type alias is
+/// defined. Use it when you can't implement [DisplayerOf] on the unit (or entity) marker, for
+/// example because the marker comes from another crate. Define `Displayer` from a closure or a
+/// function with [crate::fn_displayer].
+///
+/// It dereferences to the wrapper, and compares, hashes and `Debug`-formats like it.
+///
+/// ```
+/// #![cfg_attr(
+///     feature = "unstable_generic_const_own_type",
+///     feature(generic_const_exprs)
+/// )]
+///
+/// use phantom_newtype::{fn_displayer, WithDisplayer};
+/// # mod other_crate { pub enum Message {} }
+/// use other_crate::Message;
+///
+/// #[cfg(not(feature = "unstable_generic_const_own_type"))]
+/// type RawMessageId = phantom_newtype::Id<Message, [u8; 4]>;
+/// #[cfg(feature = "unstable_generic_const_own_type")]
+/// type RawMessageId = phantom_newtype::IdForFlags<{phantom_newtype::trait_flag::TRAIT_FLAGS_NO_COPY_NO_DEFAULT}, Message, [u8; 4]>;
+///
+/// fn_displayer!(Hex: RawMessageId = |id, f| id.get().iter().try_for_each(|b| write!(f, "{:02x}", b)));
+/// type MessageId = WithDisplayer<RawMessageId, Hex>;
+///
+/// let id = MessageId::from(RawMessageId::from([0xde, 0xad, 0xbe, 0xef]));
+/// assert_eq!(id.to_string(), "deadbeef");
+/// assert_eq!(id.get(), &[0xde, 0xad, 0xbe, 0xef]);
+/// ```
+#[repr(transparent)]
+pub struct WithDisplayer<T, Displayer> {
+    value: T,
+    displayer_tag: PhantomData<fn() -> Displayer>,
+}
+
+impl<T, Displayer> WithDisplayer<T, Displayer> {
+    pub const fn new(value: T) -> Self {
+        Self {
+            value,
+            displayer_tag: PhantomData,
+        }
+    }
+
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T, Displayer> From<T> for WithDisplayer<T, Displayer> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T, Displayer> Deref for WithDisplayer<T, Displayer> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T, Displayer> DerefMut for WithDisplayer<T, Displayer> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
+
+impl<T, Displayer> fmt::Display for WithDisplayer<T, Displayer>
+where
+    Displayer: DisplayerOf<T>,
+{
+    #[allow(clippy::needless_borrow)]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Displayer::display(&self.value, f)
+    }
+}
+
+impl<T: fmt::Debug, Displayer> fmt::Debug for WithDisplayer<T, Displayer> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.value.fmt(f)
+    }
+}
+
+impl<T: Clone, Displayer> Clone for WithDisplayer<T, Displayer> {
+    fn clone(&self) -> Self {
+        Self::new(self.value.clone())
+    }
+}
+
+impl<T: Copy, Displayer> Copy for WithDisplayer<T, Displayer> {}
+
+impl<T: Default, Displayer> Default for WithDisplayer<T, Displayer> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T: PartialEq, Displayer> PartialEq for WithDisplayer<T, Displayer> {
+    fn eq(&self, rhs: &Self) -> bool {
+        self.value.eq(&rhs.value)
+    }
+}
+
+impl<T: Eq, Displayer> Eq for WithDisplayer<T, Displayer> {}
+
+impl<T: PartialOrd, Displayer> PartialOrd for WithDisplayer<T, Displayer> {
+    fn partial_cmp(&self, rhs: &Self) -> Option<Ordering> {
+        self.value.partial_cmp(&rhs.value)
+    }
+}
+
+impl<T: Ord, Displayer> Ord for WithDisplayer<T, Displayer> {
+    fn cmp(&self, rhs: &Self) -> Ordering {
+        self.value.cmp(&rhs.value)
+    }
+}
+
+impl<T: Hash, Displayer> Hash for WithDisplayer<T, Displayer> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.value.hash(state)
+    }
+}
+
+/// Define a zero-sized `Displayer` type (for [WithDisplayer] or [DisplayProxy]) from a closure or
+/// a function: `fn_displayer!(Name: Type = |value, f| ...)`, or `fn_displayer!(Name: Type = path)`
+/// where the function takes `(&Type, &mut core::fmt::Formatter<'_>)` and returns
+/// `core::fmt::Result`. Prefix with `pub` (or other visibility) to export `Name`.
+#[macro_export]
+macro_rules! fn_displayer {
+    ($vis:vis $name:ident : $type:ty = $display:expr) => {
+        $vis enum $name {}
+
+        impl $crate::DisplayerOf<$type> for $name {
+            fn display(value: &$type, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                let display: fn(&$type, &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result =
+                    $display;
+                display(value, f)
+            }
+        }
+    };
+}
+
+/// Implement [DisplayerOf] for a unit (or entity) marker, formatting the `Repr` (from `.get()`)
+/// with a format string: `impl_displayer!(Unit for Type => "...")`, where `Type` is the amount, id
+/// or instant of `Unit`. Separate several with `;`.
+///
+/// ```
+/// #![cfg_attr(
+///     feature = "unstable_generic_const_own_type",
+///     feature(generic_const_exprs)
+/// )]
+///
+/// use phantom_newtype::{impl_displayer, Amount, Id};
+///
+/// enum Message {}
+/// type MessageId = Id<Message, u32>;
+/// enum Cents {}
+///
+/// impl_displayer! {
+///     Message for MessageId => "msg-{:08x}";
+///     Cents for Amount<Cents, u64> => "{}¢";
+/// }
+///
+/// assert_eq!(MessageId::from(255).display().to_string(), "msg-000000ff");
+/// assert_eq!(Amount::<Cents, u64>::from(99).display().to_string(), "99¢");
+/// ```
+#[macro_export]
+macro_rules! impl_displayer {
+    ($($unit:ident for $type:ty => $format:literal);* $(;)?) => {
+        $(
+            impl $crate::DisplayerOf<$type> for $unit {
+                fn display(value: &$type, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    ::core::write!(f, $format, value.get())
+                }
+            }
+        )*
+    };
+}
+
+/// A symbol and a name of a unit marker, for [crate::unit_label] to display amounts and instants
+/// of it like `42 ms`, and to `Debug`-format them like `Amount<Milliseconds>(42)`.
+#[diagnostic::on_unimplemented(
+    message = "unit `{Self}` has no symbol",
+    label = "no `UnitLabel` for `{Self}`",
+    note = "declare it with `phantom_newtype::unit_label! {{ {Self} {{ symbol: \"...\", name: \"...\" }} }}`"
+)]
+pub trait UnitLabel {
+    /// Like `"ms"`. Displayed after the value, separated by a space.
+    const SYMBOL: &'static str;
+    /// Like `"Milliseconds"`. In `Debug` output.
+    const NAME: &'static str;
+}
+
+#[doc(hidden)]
+pub fn display_with_symbol<Unit: UnitLabel, Repr: fmt::Display>(
+    repr: &Repr,
+    f: &mut fmt::Formatter<'_>,
+) -> fmt::Result {
+    fmt::Display::fmt(repr, f)?;
+    write!(f, " {}", Unit::SYMBOL)
+}
+
+#[doc(hidden)]
+pub fn debug_with_name<Unit: UnitLabel, Repr: fmt::Debug>(
+    wrapper: &str,
+    repr: &Repr,
+    f: &mut fmt::Formatter<'_>,
+) -> fmt::Result {
+    write!(f, "{}<{}>(", wrapper, Unit::NAME)?;
+    fmt::Debug::fmt(repr, f)?;
+    f.write_str(")")
+}
+
+/// Implement [UnitLabel] for unit markers, and with it [DisplayerOf] and [DebugerOf] for their
+/// amounts and instants (of any `Repr` that is `Display` and `Debug`, respectively), so that
+/// `.display()` and `.debug()` show the unit.
+///
+/// ```
+/// #![cfg_attr(
+///     feature = "unstable_generic_const_own_type",
+///     feature(generic_const_exprs)
+/// )]
+///
+/// use phantom_newtype::{Amount, Instant};
+///
+/// enum Milliseconds {}
+/// enum Celsius {}
+///
+/// phantom_newtype::unit_label! {
+///     Milliseconds { symbol: "ms", name: "Milliseconds" }
+///     Celsius { symbol: "°C", name: "Celsius" }
+/// }
+///
+/// let timeout = Amount::<Milliseconds, u32>::from(42);
+/// assert_eq!(timeout.display().to_string(), "42 ms");
+/// assert_eq!(format!("{:?}", timeout.debug()), "Amount<Milliseconds>(42)");
+///
+/// let reading = Instant::<Celsius, f64>::from(21.5);
+/// assert_eq!(format!("{:.2}", reading.display()), "21.50 °C");
+/// assert_eq!(format!("{:?}", reading.debug()), "Instant<Celsius>(21.5)");
+/// ```
+#[macro_export]
+macro_rules! unit_label {
+    ($($unit:ty { symbol: $symbol:expr, name: $name:expr $(,)? })*) => {
+        $(
+            impl $crate::UnitLabel for $unit {
+                const SYMBOL: &'static str = $symbol;
+                const NAME: &'static str = $name;
+            }
+
+            $crate::__unit_label_impls!(
+                $unit;
+                "Amount" Amount AmountNoCopy AmountNoDefault AmountNoCopyNoDefault;
+                "Instant" Instant InstantNoCopy InstantNoDefault InstantNoCopyNoDefault;
+            );
+        )*
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __unit_label_impls {
+    ($unit:ty; $($wrapper:literal $($alias:ident)*;)*) => {
+        $($(
+            impl<Repr: ::core::fmt::Display> $crate::DisplayerOf<$crate::$alias<$unit, Repr>> for $unit {
+                fn display(
+                    value: &$crate::$alias<$unit, Repr>,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    $crate::__display_with_symbol::<$unit, Repr>(value.get(), f)
+                }
+            }
+
+            impl<Repr: ::core::fmt::Debug> $crate::DebugerOf<$crate::$alias<$unit, Repr>> for $unit {
+                fn debug(
+                    value: &$crate::$alias<$unit, Repr>,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    $crate::__debug_with_name::<$unit, Repr>($wrapper, value.get(), f)
+                }
+            }
+        )*)*
+    };
+}
+
+/// Displays a value scaled by an SI prefix, with its unit's [UnitLabel], like `1.5 Mms` for
+/// 1_500_000 milliseconds. See [crate::Amount::si_display].
+///
+/// The precision of the formatter (like `{:.1}`) applies to the scaled number. Without one, the
+/// number is as short as it can be while still exact (as by `f64`'s `Display`).
+#[derive(Clone, Copy, Debug)]
+pub struct SiDisplay {
+    value: f64,
+    symbol: &'static str,
+}
+
+// From 10^-12 to 10^18, each 1000 times the previous one.
+const SI_PREFIXES: [&str; 11] = ["p", "n", "µ", "m", "", "k", "M", "G", "T", "P", "E"];
+const SI_PREFIX_NONE: usize = 4;
+
+impl SiDisplay {
+    pub(crate) fn new(value: f64, symbol: &'static str) -> Self {
+        Self { value, symbol }
+    }
+}
+
+impl fmt::Display for SiDisplay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut scaled = self.value;
+        let mut prefix = SI_PREFIX_NONE;
+        if scaled.is_finite() && scaled != 0.0 {
+            while (scaled >= 1000.0 || scaled <= -1000.0) && prefix + 1 < SI_PREFIXES.len() {
+                scaled /= 1000.0;
+                prefix += 1;
+            }
+            while scaled > -1.0 && scaled < 1.0 && prefix > 0 {
+                scaled *= 1000.0;
+                prefix -= 1;
+            }
+            // Rounding to the precision may carry into a fourth digit, like 999.96 to `1000.0`.
+            if let Some(precision) = f.precision() {
+                let mut digits = IntegerDigits::default();
+                fmt::Write::write_fmt(&mut digits, format_args!("{:.*}", precision, scaled))?;
+                if digits.count > 3 && prefix + 1 < SI_PREFIXES.len() {
+                    scaled /= 1000.0;
+                    prefix += 1;
+                }
+            }
+        }
+        fmt::Display::fmt(&scaled, f)?;
+        write!(f, " {}{}", SI_PREFIXES[prefix], self.symbol)
+    }
+}
+
+/// Displays a `Repr` with the digits of its integer part in groups of three, like `1,234,567` or
+/// `-1,234.5`. See [crate::Amount::grouped_display] and [crate::Instant::grouped_display].
+///
+/// The separator is `,` unless set with [GroupedDisplay::separator]. The precision of the
+/// formatter (like `{:.2}`) applies to `Repr`; width and fill don't apply.
+pub struct GroupedDisplay<'a, Repr> {
+    repr: &'a Repr,
+    separator: char,
+}
+
+impl<'a, Repr> GroupedDisplay<'a, Repr> {
+    pub(crate) fn new(repr: &'a Repr) -> Self {
+        Self {
+            repr,
+            separator: ',',
+        }
+    }
+
+    /// Separate the groups with `separator` (like `'_'`, `'.'` or `' '`) instead of `,`.
+    pub fn separator(self, separator: char) -> Self {
+        Self { separator, ..self }
+    }
+}
+
+impl<Repr: fmt::Display> fmt::Display for GroupedDisplay<'_, Repr> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn write_repr<Repr: fmt::Display, W: fmt::Write>(
+            out: &mut W,
+            repr: &Repr,
+            precision: Option<usize>,
+        ) -> fmt::Result {
+            match precision {
+                Some(precision) => write!(out, "{:.*}", precision, repr),
+                None => write!(out, "{}", repr),
+            }
+        }
+        // First count the digits of the integer part, then write it all with separators.
+        let precision = f.precision();
+        let mut counter = IntegerDigits::default();
+        write_repr(&mut counter, self.repr, precision)?;
+        let mut grouper = Grouper {
+            out: f,
+            separator: self.separator,
+            digits: IntegerDigits::default(),
+            remaining: counter.count,
+        };
+        write_repr(&mut grouper, self.repr, precision)
+    }
+}
+
+/// Tracks the integer part of a number as it's being written: an optional sign followed by the
+/// leading run of ASCII digits.
+#[derive(Default)]
+struct IntegerDigits {
+    count: usize,
+    past: bool,
+}
+
+impl IntegerDigits {
+    /// Whether `c` is a digit of the integer part.
+    fn accept(&mut self, c: char) -> bool {
+        if self.past {
+            return false;
+        }
+        if c.is_ascii_digit() {
+            self.count += 1;
+            return true;
+        }
+        if !(self.count == 0 && (c == '-' || c == '+')) {
+            self.past = true;
+        }
+        false
+    }
+}
+
+impl fmt::Write for IntegerDigits {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            self.accept(c);
+        }
+        Ok(())
+    }
+}
+
+struct Grouper<'a, 'f> {
+    out: &'a mut fmt::Formatter<'f>,
+    separator: char,
+    digits: IntegerDigits,
+    // Digits of the integer part yet to be written.
+    remaining: usize,
+}
+
+impl fmt::Write for Grouper<'_, '_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            if self.digits.accept(c) {
+                self.out.write_char(c)?;
+                self.remaining -= 1;
+                if self.remaining > 0 && self.remaining % 3 == 0 {
+                    self.out.write_char(self.separator)?;
+                }
+            } else {
+                self.out.write_char(c)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The name of `Unit` for alternate (`{:#?}`) `Debug` output: the last segment of its path, like
+/// `BlockHeight`. Names that are not simple paths (like tuples or generic types) are in full.
+pub(crate) fn unit_name<Unit>() -> &'static str {
+    let name = core::any::type_name::<Unit>();
+    if name.contains(['<', '(', '[', '&', ' ']) {
+        name
+    } else {
+        name.rsplit("::").next().unwrap_or(name)
+    }
+}
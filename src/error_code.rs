@@ -0,0 +1,66 @@
+// Copyright 2024 Peter Lyons Kehl
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Typed error codes.
+
+use crate::displayer::{DisplayerOf, WithDisplayer};
+use crate::Id;
+use core::fmt;
+
+/// An error code of `Domain`: an [Id] that displays with the `Domain` marker's [DisplayerOf], and
+/// that is a `core::error::Error`. So it works with `?`, `Box<dyn Error>` and error reporting
+/// crates, while codes of different domains stay different types.
+///
+/// ```
+/// #![cfg_attr(
+///     feature = "unstable_generic_const_own_type",
+///     feature(generic_const_exprs)
+/// )]
+///
+/// use core::fmt;
+/// use phantom_newtype::{DisplayerOf, ErrorCode, Id};
+///
+/// enum Modem {}
+/// type ModemError = ErrorCode<Modem, u16>;
+///
+/// impl DisplayerOf<Id<Modem, u16>> for Modem {
+///     fn display(code: &Id<Modem, u16>, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+///         match code.get() {
+///             3 => f.write_str("modem: no carrier"),
+///             other => write!(f, "modem: error {}", other),
+///         }
+///     }
+/// }
+///
+/// fn dial() -> Result<(), ModemError> {
+///     Err(ModemError::from(Id::from(3)))
+/// }
+///
+/// fn connect() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+///     dial()?;
+///     Ok(())
+/// }
+///
+/// let error = connect().unwrap_err();
+/// assert_eq!(error.to_string(), "modem: no carrier");
+/// assert_eq!(error.downcast_ref::<ModemError>().map(|code| *code.get()), Some(3));
+/// ```
+pub type ErrorCode<Domain, Repr> = WithDisplayer<Id<Domain, Repr>, Domain>;
+
+impl<Domain, Repr> core::error::Error for ErrorCode<Domain, Repr>
+where
+    Domain: DisplayerOf<Id<Domain, Repr>>,
+    Repr: fmt::Debug,
+{
+}
@@ -0,0 +1,181 @@
+// Copyright 2024 Peter Lyons Kehl
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Compile-time arithmetic over typed constants.
+
+use crate::amount::Amount;
+use crate::instant::Instant;
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+use crate::trait_flag::TraitFlags;
+
+/// Evaluate, at compile time, an arithmetic expression over constants of one [crate::Amount]
+/// type, giving an amount of that type.
+///
+/// The expression may contain constants (or paths to them) of type `$amount`, literals (as
+/// scalars of its `Repr`), operators and parentheses. Each constant stands for its `Repr`, and
+/// the expression is evaluated as `Repr` arithmetic in a `const` item, so any overflow (or
+/// division by zero) is a compile error.
+///
+/// ```
+/// #![cfg_attr(
+///     feature = "unstable_generic_const_own_type",
+///     feature(generic_const_exprs)
+/// )]
+///
+/// use phantom_newtype::amount_expr;
+///
+/// mod fees {
+///     pub enum Gwei {}
+///     pub type Fee = phantom_newtype::Amount<Gwei, u64>;
+///
+///     pub const BASE: Fee = Fee::new(21_000);
+///     pub const CAP: Fee = Fee::new(100_000);
+/// }
+/// use fees::Fee;
+///
+/// const SURCHARGE: Fee = Fee::new(500);
+/// const TOTAL: Fee = amount_expr!(Fee: 3 * fees::BASE + SURCHARGE);
+/// assert_eq!(TOTAL, Fee::from(63_500));
+/// assert_eq!(amount_expr!(Fee: (fees::CAP - TOTAL) / 2 - 1), Fee::from(18_249));
+/// ```
+///
+/// Overflow fails the build:
+///
+/// ```compile_fail
+/// use phantom_newtype::{amount_expr, Amount};
+///
+/// enum Gwei {}
+/// type Fee = Amount<Gwei, u8>;
+///
+/// const BASE: Fee = Fee::new(200);
+/// const TOTAL: Fee = amount_expr!(Fee: 2 * BASE);
+/// ```
+///
+/// For a single operation, the `const fn`s `const_add`, `const_sub` and `const_mul` (of amounts and
+/// instants with a primitive integer `Repr`) are shorter. They overflow like the operators do: a
+/// compile error in a `const` item, a panic (in debug builds) otherwise.
+///
+/// ```
+/// #![cfg_attr(
+///     feature = "unstable_generic_const_own_type",
+///     feature(generic_const_exprs)
+/// )]
+///
+/// use phantom_newtype::{Amount, Instant};
+///
+/// enum Ms {}
+/// type Millis = Amount<Ms, u64>;
+/// type Timestamp = Instant<Ms, u64>;
+///
+/// const BASE: Millis = Millis::new(1_000);
+/// const TIMEOUT: Millis = BASE.const_add(Millis::new(500));
+/// const BACKOFF: Millis = TIMEOUT.const_mul(3).const_sub(BASE);
+/// const DEADLINE: Timestamp = Timestamp::new(10_000).const_add(TIMEOUT);
+///
+/// assert_eq!(TIMEOUT, Millis::from(1_500));
+/// assert_eq!(BACKOFF, Millis::from(3_500));
+/// assert_eq!(DEADLINE, Timestamp::from(11_500));
+/// ```
+///
+/// So does mixing units:
+///
+/// ```compile_fail
+/// use phantom_newtype::{amount_expr, Amount};
+///
+/// enum Gwei {}
+/// enum Wei {}
+///
+/// const BASE: Amount<Gwei, u64> = Amount::new(1);
+/// const TIP: Amount<Wei, u64> = Amount::new(1);
+/// const TOTAL: Amount<Gwei, u64> = amount_expr!(Amount<Gwei, u64>: BASE + TIP);
+/// ```
+#[macro_export]
+macro_rules! amount_expr {
+    ($amount:ty : $($expr:tt)+) => {{
+        const VALUE: $amount = <$amount>::new($crate::__amount_expr_repr!($amount; []; $($expr)+));
+        VALUE
+    }};
+}
+
+/// Implementation of [amount_expr]: a token muncher replacing each constant (path) with its
+/// `Repr`. The middle part (in brackets) is the output so far.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __amount_expr_repr {
+    // Within a path: the part in the second brackets is the path so far.
+    (@path $amount:ty; [$($out:tt)*]; [$($path:tt)+]; $segment:ident :: $($rest:tt)+) => {
+        $crate::__amount_expr_repr!(@path $amount; [$($out)*]; [$($path)+ $segment ::]; $($rest)+)
+    };
+    (@path $amount:ty; [$($out:tt)*]; [$($path:tt)+]; $name:ident $($rest:tt)*) => {
+        $crate::__amount_expr_repr!($amount; [$($out)* <$amount>::get_copy(&$($path)+ $name)]; $($rest)*)
+    };
+    ($amount:ty; [$($out:tt)*];) => {
+        $($out)*
+    };
+    ($amount:ty; [$($out:tt)*]; ($($inner:tt)+) $($rest:tt)*) => {
+        $crate::__amount_expr_repr!(
+            $amount;
+            [$($out)* ($crate::__amount_expr_repr!($amount; []; $($inner)+))];
+            $($rest)*
+        )
+    };
+    ($amount:ty; [$($out:tt)*]; $segment:ident :: $($rest:tt)+) => {
+        $crate::__amount_expr_repr!(@path $amount; [$($out)*]; [$segment ::]; $($rest)+)
+    };
+    ($amount:ty; [$($out:tt)*]; $name:ident $($rest:tt)*) => {
+        $crate::__amount_expr_repr!($amount; [$($out)* <$amount>::get_copy(&$name)]; $($rest)*)
+    };
+    // Operators and literals. (Not a `literal` fragment: that would try to parse `- CONSTANT`.)
+    ($amount:ty; [$($out:tt)*]; $operator:tt $($rest:tt)*) => {
+        $crate::__amount_expr_repr!($amount; [$($out)* $operator]; $($rest)*)
+    };
+}
+
+macro_rules! impl_const_arithmetic {
+    ($($t:ty)*) => {
+        $(
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<const TF: TraitFlags, Unit> Amount<TF, Unit, $t> {
+                /// Like `self + rhs`, but usable in `const` items (see [crate::amount_expr]).
+                pub const fn const_add(self, rhs: Self) -> Self {
+                    Self::new(self.0 + rhs.0)
+                }
+
+                /// Like `self - rhs`, but usable in `const` items (see [crate::amount_expr]).
+                pub const fn const_sub(self, rhs: Self) -> Self {
+                    Self::new(self.0 - rhs.0)
+                }
+
+                /// Like `self * rhs`, but usable in `const` items (see [crate::amount_expr]).
+                pub const fn const_mul(self, rhs: $t) -> Self {
+                    Self::new(self.0 * rhs)
+                }
+            }
+
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<const TF: TraitFlags, Unit> Instant<TF, Unit, $t> {
+                /// Like `self + rhs`, but usable in `const` items (see [crate::amount_expr]).
+                pub const fn const_add(self, rhs: Amount<TF, Unit, $t>) -> Self {
+                    Self::new(self.0 + rhs.0)
+                }
+
+                /// Like `self - rhs`, but usable in `const` items (see [crate::amount_expr]).
+                pub const fn const_sub(self, rhs: Amount<TF, Unit, $t>) -> Self {
+                    Self::new(self.0 - rhs.0)
+                }
+            }
+        )*
+    };
+}
+impl_const_arithmetic!(u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize);
@@ -0,0 +1,182 @@
+// Copyright 2024 Peter Lyons Kehl
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::repr::FlagsRepr;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use core::ops::{BitAnd, BitOr, Not};
+
+/// Names of the bits of [TypedFlags] of `Self` unit, used by `Debug`.
+#[diagnostic::on_unimplemented(
+    message = "unit `{Self}` has no names for its flags",
+    label = "no `FlagNames` for `{Self}`",
+    note = "`Debug` of `TypedFlags` needs `impl FlagNames for {Self}`"
+)]
+pub trait FlagNames {
+    /// Name of bit number `i` is `NAMES[i]`. Bits beyond `NAMES` are unnamed.
+    const NAMES: &'static [&'static str];
+}
+
+/// A set of bits (like permissions or features) of `Unit`, stored in `Repr` (an unsigned integer).
+/// Sets of different units can't be mixed.
+///
+/// ```
+/// use phantom_newtype::{FlagNames, TypedFlags};
+///
+/// enum FilePermission {}
+/// impl FlagNames for FilePermission {
+///     const NAMES: &'static [&'static str] = &["READ", "WRITE", "EXECUTE"];
+/// }
+/// type FilePermissions = TypedFlags<FilePermission, u8>;
+/// const READ: u32 = 0;
+/// const WRITE: u32 = 1;
+///
+/// let mut permissions = FilePermissions::empty();
+/// permissions.set(READ);
+/// permissions.set(WRITE);
+/// permissions.set(7);
+/// permissions.clear(WRITE);
+/// assert!(permissions.contains(READ));
+/// assert!(!permissions.contains(WRITE));
+/// assert_eq!(permissions.iter().collect::<Vec<_>>(), [0, 7]);
+/// assert_eq!(permissions.bits(), 0b1000_0001);
+/// assert_eq!(format!("{:?}", permissions), "TypedFlags(READ | 7)");
+/// ```
+///
+/// Sets of different units are different types:
+///
+/// ```compile_fail
+/// use phantom_newtype::TypedFlags;
+///
+/// enum FilePermission {}
+/// enum Feature {}
+///
+/// let both = TypedFlags::<FilePermission, u8>::empty() | TypedFlags::<Feature, u8>::empty();
+/// ```
+#[repr(transparent)]
+pub struct TypedFlags<Unit, Repr>(Repr, PhantomData<fn() -> Unit>);
+
+impl<Unit, Repr: FlagsRepr> TypedFlags<Unit, Repr> {
+    /// No bits set.
+    pub fn empty() -> Self {
+        Self::from_bits(Repr::EMPTY)
+    }
+
+    pub const fn from_bits(bits: Repr) -> Self {
+        Self(bits, PhantomData)
+    }
+
+    pub fn bits(&self) -> Repr {
+        self.0
+    }
+
+    /// Set bit number `index`. Panics if `index` is not less than [FlagsRepr::BITS].
+    pub fn set(&mut self, index: u32) {
+        self.0 = self.0.union(Self::checked_bit(index));
+    }
+
+    /// Clear bit number `index`. Panics if `index` is not less than [FlagsRepr::BITS].
+    pub fn clear(&mut self, index: u32) {
+        self.0 = self.0.intersection(Self::checked_bit(index).complement());
+    }
+
+    /// Whether bit number `index` is set. Panics if `index` is not less than [FlagsRepr::BITS].
+    pub fn contains(&self, index: u32) -> bool {
+        self.0.intersection(Self::checked_bit(index)) != Repr::EMPTY
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == Repr::EMPTY
+    }
+
+    /// Numbers of the set bits, in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = u32> {
+        let bits = self.0;
+        (0..Repr::BITS).filter(move |&index| bits.intersection(Repr::bit(index)) != Repr::EMPTY)
+    }
+
+    fn checked_bit(index: u32) -> Repr {
+        assert!(index < Repr::BITS, "bit index out of range");
+        Repr::bit(index)
+    }
+}
+
+impl<Unit, Repr: Clone> Clone for TypedFlags<Unit, Repr> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone(), PhantomData)
+    }
+}
+
+impl<Unit, Repr: Copy> Copy for TypedFlags<Unit, Repr> {}
+
+impl<Unit, Repr: FlagsRepr> Default for TypedFlags<Unit, Repr> {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+impl<Unit, Repr: PartialEq> PartialEq for TypedFlags<Unit, Repr> {
+    fn eq(&self, rhs: &Self) -> bool {
+        self.0.eq(&rhs.0)
+    }
+}
+
+impl<Unit, Repr: Eq> Eq for TypedFlags<Unit, Repr> {}
+
+impl<Unit, Repr: Hash> Hash for TypedFlags<Unit, Repr> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state)
+    }
+}
+
+impl<Unit, Repr: FlagsRepr> BitOr for TypedFlags<Unit, Repr> {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self::from_bits(self.0.union(rhs.0))
+    }
+}
+
+impl<Unit, Repr: FlagsRepr> BitAnd for TypedFlags<Unit, Repr> {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self {
+        Self::from_bits(self.0.intersection(rhs.0))
+    }
+}
+
+impl<Unit, Repr: FlagsRepr> Not for TypedFlags<Unit, Repr> {
+    type Output = Self;
+
+    fn not(self) -> Self {
+        Self::from_bits(self.0.complement())
+    }
+}
+
+impl<Unit: FlagNames, Repr: FlagsRepr> fmt::Debug for TypedFlags<Unit, Repr> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("TypedFlags(")?;
+        for (i, index) in self.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" | ")?;
+            }
+            match Unit::NAMES.get(index as usize) {
+                Some(name) => f.write_str(name)?,
+                None => write!(f, "{}", index)?,
+            }
+        }
+        f.write_str(")")
+    }
+}
@@ -0,0 +1,65 @@
+// Copyright 2024 Peter Lyons Kehl
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Formatting traits of [Amount]s, [Id]s and [Instant]s, forwarded to `Repr`.
+//!
+//! They pass the `Formatter` on as it is (rather than through `write!`), so width, fill, alignment,
+//! sign, precision and `#` apply exactly as they would to `Repr`:
+//!
+//! ```
+//! #![cfg_attr(
+//!     feature = "unstable_generic_const_own_type",
+//!     feature(generic_const_exprs)
+//! )]
+//!
+//! use phantom_newtype::{Amount, Id};
+//!
+//! enum User {}
+//! enum Meters {}
+//!
+//! assert_eq!(format!("{:>6}", Id::<User, u32>::from(42)), "    42");
+//! assert_eq!(format!("{:.3}", Amount::<Meters, f64>::new(1.5)), "1.500");
+//! assert_eq!(format!("{:#06x}", Id::<User, u32>::from(255)), "0x00ff");
+//! ```
+//!
+//! `Debug` is in the wrappers' modules, because of its alternate (`{:#?}`) form.
+
+use crate::amount::Amount;
+use crate::id::Id;
+use crate::instant::Instant;
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+use crate::trait_flag::TraitFlags;
+use core::fmt;
+
+macro_rules! impl_forward_fmt {
+    ($($wrapper:ident)*) => {
+        $(
+            impl_forward_fmt!(@traits $wrapper: Display LowerHex UpperHex Octal Binary LowerExp UpperExp);
+        )*
+    };
+    (@traits $wrapper:ident: $($trait:ident)*) => {
+        $(
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<const TF: TraitFlags, Unit, Repr> fmt::$trait for $wrapper<TF, Unit, Repr>
+            where
+                Repr: fmt::$trait,
+            {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    fmt::$trait::fmt(&self.0, f)
+                }
+            }
+        )*
+    };
+}
+impl_forward_fmt!(Amount Id Instant);
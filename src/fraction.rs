@@ -0,0 +1,106 @@
+// Copyright 2024 Peter Lyons Kehl
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Fractions (like fees, shares and rates) of [Amount]s.
+
+use crate::amount::Amount;
+use crate::repr::MulDiv;
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+use crate::trait_flag::TraitFlags;
+
+/// A dimensionless ratio `numerator / denominator` to take of an [Amount] with [Fraction::apply]:
+/// it multiplies (into an integer of twice the width) before it divides, so neither a large amount
+/// nor a fine-grained ratio overflows, and the result rounds only once (toward zero).
+///
+/// ```
+/// #![cfg_attr(
+///     feature = "unstable_generic_const_own_type",
+///     feature(generic_const_exprs)
+/// )]
+///
+/// use phantom_newtype::{Amount, Fraction};
+///
+/// enum Wei {}
+/// type Balance = Amount<Wei, u64>;
+///
+/// // 30 basis points, that is 0.3%.
+/// const FEE: Fraction<u64> = Fraction::new(30, 10_000);
+///
+/// // `u64::MAX * 30` would overflow `u64`.
+/// let whale = Balance::from(u64::MAX);
+/// assert_eq!(FEE.apply(whale), Some(Balance::from(55_340_232_221_128_654)));
+/// assert_eq!(FEE, Fraction::basis_points(30));
+/// assert_eq!(Fraction::percent(5).apply(Balance::from(999)), Some(Balance::from(49)));
+/// assert_eq!(Fraction::new(1, 3).apply(Balance::from(10)), Some(Balance::from(3)));
+///
+/// // More than the whole: the result may not fit.
+/// assert_eq!(Fraction::new(3, 2).apply(whale), None);
+/// assert_eq!(Fraction::new(1, 0).apply(whale), None);
+/// ```
+///
+/// `PartialEq`, `Eq` and `Hash` compare the numerator and the denominator, so `1/2` and `2/4`
+/// differ.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Fraction<Repr> {
+    numerator: Repr,
+    denominator: Repr,
+}
+
+impl<Repr> Fraction<Repr> {
+    /// `numerator / denominator`. A zero `denominator` is allowed here, but then [Fraction::apply]
+    /// gives `None`.
+    pub const fn new(numerator: Repr, denominator: Repr) -> Self {
+        Self {
+            numerator,
+            denominator,
+        }
+    }
+
+    pub const fn numerator(&self) -> &Repr {
+        &self.numerator
+    }
+
+    pub const fn denominator(&self) -> &Repr {
+        &self.denominator
+    }
+}
+
+impl<Repr: From<u8>> Fraction<Repr> {
+    /// `percent / 100`.
+    pub fn percent(percent: Repr) -> Self {
+        Self::new(percent, Repr::from(100))
+    }
+}
+
+impl<Repr: From<u16>> Fraction<Repr> {
+    /// `basis_points / 10_000` (a basis point is a hundredth of a percent).
+    pub fn basis_points(basis_points: Repr) -> Self {
+        Self::new(basis_points, Repr::from(10_000))
+    }
+}
+
+impl<Repr: MulDiv> Fraction<Repr> {
+    /// `amount * numerator / denominator`, rounding toward zero. `None` if the denominator is zero
+    /// or the result doesn't fit in `Repr`.
+    #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+    pub fn apply<const TF: TraitFlags, Unit>(
+        &self,
+        amount: Amount<TF, Unit, Repr>,
+    ) -> Option<Amount<TF, Unit, Repr>> {
+        amount
+            .0
+            .mul_div(self.numerator, self.denominator)
+            .map(Amount::new)
+    }
+}
@@ -13,13 +13,20 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::displayer::{DisplayProxy, DisplayerOf};
+use crate::displayer::{unit_name, DebugProxy, DebugerOf, DisplayProxy, DisplayerOf};
+use crate::repr::{NonZeroRepr, Redacted};
 #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
 use crate::trait_flag::{self, TraitFlags};
+use core::borrow::Borrow;
 use core::cmp::Ordering;
+use core::convert::TryFrom;
 use core::fmt;
 use core::hash::{Hash, Hasher};
 use core::marker::PhantomData;
+use core::num::{
+    NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
+    NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize, TryFromIntError,
+};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
@@ -48,15 +55,7 @@ use serde::{Deserialize, Deserializer, Serialize, Serializer};
 /// marker that differentiate incompatible ids.
 ///
 /// ```compile_fail
-/// use phantom_newtype::Id;
-///
-/// enum Recepient {}
-/// enum Message {}
-///
-/// type RecepientId = Id<Recepient, u64>;
-/// type MessageId = Id<Message, u64>;
-///
-/// assert_eq!(RecepientId::from(15), MessageId::from(15));
+#[doc = include_str!("../tests/ui/mixing_ids.rs")]
 /// ```
 ///
 /// `Id` is cheap to copy if `Repr` is:
@@ -143,6 +142,16 @@ use serde::{Deserialize, Deserializer, Serialize, Serializer};
 /// assert_eq!(ID, *id_from_thread);
 /// ```
 ///
+/// But not if the `Repr` doesn't allow it:
+///
+/// ```compile_fail
+/// use phantom_newtype::Id;
+/// use std::rc::Rc;
+///
+/// let shared = Id::<(), Rc<u8>>::new(Rc::new(1));
+/// std::thread::spawn(move || shared);
+/// ```
+///
 /// Ids can be serialized and deserialized with `serde`. Serialized
 /// forms of `Id<Entity, Repr>` and `Repr` are identical.
 ///
@@ -163,12 +172,40 @@ use serde::{Deserialize, Deserializer, Serialize, Serializer};
 /// assert_eq!(serde_json::to_string(&user_id).unwrap(), serde_json::to_string(&repr).unwrap());
 /// }
 /// ```
+///
+/// With feature `zerocopy`, ids are `FromBytes`, `IntoBytes` (formerly `AsBytes`), `Unaligned`,
+/// `KnownLayout` and `Immutable` whenever `Repr` is, so packet fields can be read as typed ids in
+/// place.
+///
+/// ```
+/// #![cfg_attr(
+///     feature = "unstable_generic_const_own_type",
+///     feature(generic_const_exprs)
+/// )]
+///
+/// #[cfg(feature = "zerocopy")] {
+/// use phantom_newtype::Id;
+/// use zerocopy::{big_endian::U32, FromBytes};
+/// enum Session {}
+///
+/// let packet = [0u8, 0, 1, 2];
+/// let id = Id::<Session, U32>::ref_from_bytes(&packet[..]).unwrap();
+/// assert_eq!(id.get().get(), 0x0102);
+/// }
+/// ```
 #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+#[cfg_attr(
+    feature = "zerocopy",
+    derive(
+        zerocopy::FromBytes,
+        zerocopy::IntoBytes,
+        zerocopy::Unaligned,
+        zerocopy::KnownLayout,
+        zerocopy::Immutable
+    )
+)]
 #[repr(transparent)]
-pub struct Id<const TF: TraitFlags, Entity, Repr>(
-    Repr,
-    PhantomData<core::sync::atomic::AtomicPtr<Entity>>,
-);
+pub struct Id<const TF: TraitFlags, Entity, Repr>(pub(crate) Repr, PhantomData<fn() -> Entity>);
 
 #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
 impl<const TF: TraitFlags, Entity, Repr> Id<TF, Entity, Repr> {
@@ -214,6 +251,30 @@ impl<const TF: TraitFlags, Entity, Repr> Id<TF, Entity, Repr> {
     }
 }
 
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+impl<const TF: TraitFlags, Entity, Repr: Copy> Id<TF, Entity, Repr> {
+    /// `get_copy` returns a copy of the underlying representation of the identifier.
+    ///
+    /// ```
+    /// #![cfg_attr(
+    ///     feature = "unstable_generic_const_own_type",
+    ///     feature(generic_const_exprs)
+    /// )]
+    ///
+    /// use phantom_newtype::Id;
+    ///
+    /// enum User {}
+    /// type UserId = Id<User, u64>;
+    ///
+    /// const ADMIN_ID: UserId = UserId::new(42);
+    /// const ADMIN: u64 = ADMIN_ID.get_copy();
+    /// assert_eq!(ADMIN, 42);
+    /// ```
+    pub const fn get_copy(&self) -> Repr {
+        self.0
+    }
+}
+
 #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
 impl<const TF: TraitFlags, Entity, Repr> Id<TF, Entity, Repr>
 where
@@ -256,6 +317,249 @@ where
     pub fn display(&self) -> DisplayProxy<'_, Self, Entity> {
         DisplayProxy::new(self)
     }
+
+    /// Write with the displayer (as per [Self::display]) straight into `out`, for example a
+    /// reused buffer, without an intermediate `String`.
+    ///
+    /// ```
+    /// #![cfg_attr(
+    ///     feature = "unstable_generic_const_own_type",
+    ///     feature(generic_const_exprs),
+    /// )]
+    ///
+    /// use phantom_newtype::DisplayerOf;
+    /// use core::fmt;
+    ///
+    /// enum Blob {}
+    /// #[cfg(not(feature = "unstable_generic_const_own_type"))]
+    /// type BlobId = phantom_newtype::Id<Blob, [u8; 64]>;
+    /// #[cfg(feature = "unstable_generic_const_own_type")]
+    /// type BlobId = phantom_newtype::IdForFlags<{phantom_newtype::trait_flag::TRAIT_FLAGS_NO_COPY_NO_DEFAULT}, Blob, [u8; 64]>;
+    ///
+    /// impl DisplayerOf<BlobId> for Blob {
+    ///   fn display(id: &BlobId, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    ///     id.get().iter().try_for_each(|b| write!(f, "{:02x}", b))
+    ///   }
+    /// }
+    ///
+    /// let mut line = String::with_capacity(256);
+    /// for fill in [0xab, 0xcd] {
+    ///     line.clear();
+    ///     BlobId::from([fill; 64]).display_into(&mut line).unwrap();
+    ///     assert_eq!(line.len(), 128);
+    /// }
+    /// assert!(line.starts_with("cdcd"));
+    /// ```
+    pub fn display_into<W: fmt::Write + ?Sized>(&self, out: &mut W) -> fmt::Result {
+        write!(out, "{}", self.display())
+    }
+}
+
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+impl<const TF: TraitFlags, Entity, Repr> Id<TF, Entity, Repr>
+where
+    Entity: DebugerOf<Self>,
+{
+    /// `debug` attaches a custom `Debug` formatting (as per [DebugerOf]), separate from
+    /// [Self::display].
+    ///
+    /// ```
+    /// #![cfg_attr(
+    ///     feature = "unstable_generic_const_own_type",
+    ///     feature(generic_const_exprs),
+    /// )]
+    ///
+    /// use phantom_newtype::{DebugerOf, DisplayerOf, Id};
+    /// use core::fmt;
+    ///
+    /// enum Account {}
+    /// type AccountId = Id<Account, u32>;
+    ///
+    /// impl DisplayerOf<AccountId> for Account {
+    ///     fn display(id: &AccountId, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    ///         write!(f, "account #{}", id.get())
+    ///     }
+    /// }
+    /// impl DebugerOf<AccountId> for Account {
+    ///     fn debug(id: &AccountId, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    ///         write!(f, "Account({:#010x})", id.get())
+    ///     }
+    /// }
+    ///
+    /// let id = AccountId::from(255);
+    /// assert_eq!(id.display().to_string(), "account #255");
+    /// assert_eq!(format!("{:?}", id.debug()), "Account(0x000000ff)");
+    /// ```
+    pub fn debug(&self) -> DebugProxy<'_, Self, Entity> {
+        DebugProxy::new(self)
+    }
+}
+
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+impl<const TF: TraitFlags, Entity, Repr: NonZeroRepr> Id<TF, Entity, Repr> {
+    /// Create an id from a primitive integer, or `None` if it is zero.
+    ///
+    /// ```
+    /// #![cfg_attr(
+    ///     feature = "unstable_generic_const_own_type",
+    ///     feature(generic_const_exprs)
+    /// )]
+    ///
+    /// use phantom_newtype::NonZeroId;
+    ///
+    /// enum User {}
+    /// type UserId = NonZeroId<User, u64>;
+    ///
+    /// assert_eq!(UserId::new_checked(7).map(|id| id.get_nonzero().get()), Some(7));
+    /// assert!(UserId::new_checked(0).is_none());
+    /// assert_eq!(
+    ///     core::mem::size_of::<Option<UserId>>(),
+    ///     core::mem::size_of::<u64>()
+    /// );
+    /// ```
+    pub fn new_checked(value: Repr::Primitive) -> Option<Self> {
+        Repr::new_checked(value).map(Self::new)
+    }
+
+    /// The underlying non-zero integer, by value.
+    pub fn get_nonzero(&self) -> Repr {
+        *self.get()
+    }
+}
+
+/// Hierarchical (namespaced) ids: an id of a `Child` within a `Parent`, like a document within a
+/// tenant, is an `Id<(Parent, Child), (P, C)>`.
+///
+/// Such ids order by parent first, then by child, and (with `serde`) serialize as a 2-tuple.
+///
+/// ```
+/// #![cfg_attr(
+///     feature = "unstable_generic_const_own_type",
+///     feature(generic_const_exprs)
+/// )]
+///
+/// use phantom_newtype::Id;
+///
+/// enum Tenant {}
+/// enum Document {}
+/// type TenantId = Id<Tenant, u32>;
+/// type DocumentId = Id<(Tenant, Document), (u32, u64)>;
+///
+/// let doc = DocumentId::from_parts(TenantId::from(1), Id::from(20));
+/// assert_eq!(*doc.parent(), TenantId::from(1));
+/// assert_eq!(*doc.child(), Id::<Document, u64>::from(20));
+/// assert!(doc < DocumentId::from_parts(TenantId::from(2), Id::from(10)));
+///
+/// let (tenant, _document) = doc.into_parts();
+/// assert_eq!(tenant, TenantId::from(1));
+///
+/// #[cfg(feature = "serde")]
+/// assert_eq!(serde_json::to_string(&doc).unwrap(), "[1,20]");
+/// ```
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+impl<const TF: TraitFlags, Parent, Child, P, C> Id<TF, (Parent, Child), (P, C)> {
+    pub fn from_parts(parent: Id<TF, Parent, P>, child: Id<TF, Child, C>) -> Self {
+        Self::new((parent.0, child.0))
+    }
+
+    pub fn into_parts(self) -> (Id<TF, Parent, P>, Id<TF, Child, C>) {
+        let (parent, child) = self.0;
+        (Id::new(parent), Id::new(child))
+    }
+
+    pub const fn parent(&self) -> &Id<TF, Parent, P> {
+        // SAFETY: `Id` is `#[repr(transparent)]` over its `Repr`.
+        unsafe { &*(&self.0 .0 as *const P as *const Id<TF, Parent, P>) }
+    }
+
+    pub const fn child(&self) -> &Id<TF, Child, C> {
+        // SAFETY: `Id` is `#[repr(transparent)]` over its `Repr`.
+        unsafe { &*(&self.0 .1 as *const C as *const Id<TF, Child, C>) }
+    }
+}
+
+/// Indicator trait: every `Self` entity is also a `Parent` entity, with ids from the same space.
+/// Then an id of `Self` can be [Id::upcast] to an id of `Parent`, and an id of `Parent` can be
+/// [Id::downcast] (if it passes a check) to an id of `Self`.
+///
+/// ```
+/// #![cfg_attr(
+///     feature = "unstable_generic_const_own_type",
+///     feature(generic_const_exprs)
+/// )]
+///
+/// use phantom_newtype::{Id, SubEntityOf};
+///
+/// enum Principal {}
+/// enum Canister {}
+/// impl SubEntityOf<Principal> for Canister {}
+///
+/// type PrincipalId = Id<Principal, u64>;
+/// type CanisterId = Id<Canister, u64>;
+///
+/// // In this example, canister ids are odd.
+/// let is_canister = |id: &u64| id % 2 == 1;
+///
+/// let principal: PrincipalId = CanisterId::from(7).upcast();
+/// assert_eq!(principal, PrincipalId::from(7));
+/// assert_eq!(principal.downcast::<Canister>(is_canister), Ok(CanisterId::from(7)));
+/// assert_eq!(
+///     PrincipalId::from(8).downcast::<Canister>(is_canister),
+///     Err(PrincipalId::from(8))
+/// );
+/// ```
+///
+/// Without the opt-in, there is no upcast:
+///
+/// ```compile_fail
+/// use phantom_newtype::Id;
+///
+/// enum Principal {}
+/// enum Canister {}
+///
+/// let principal: Id<Principal, u64> = Id::<Canister, u64>::from(7).upcast();
+/// ```
+#[diagnostic::on_unimplemented(
+    message = "entity `{Self}` is not a sub entity of `{Parent}`",
+    label = "no `SubEntityOf<{Parent}>` for `{Self}`",
+    note = "opt in with `impl SubEntityOf<{Parent}> for {Self} {{}}`, if every `{Self}` is a `{Parent}`"
+)]
+pub trait SubEntityOf<Parent> {}
+
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+impl<const TF: TraitFlags, Entity, Repr> Id<TF, Entity, Repr> {
+    /// Convert to an id of a super entity, as allowed by [SubEntityOf].
+    pub fn upcast<Parent>(self) -> Id<TF, Parent, Repr>
+    where
+        Entity: SubEntityOf<Parent>,
+    {
+        Id::new(self.0)
+    }
+
+    /// Convert a reference to an id of a super entity, as allowed by [SubEntityOf].
+    pub const fn upcast_ref<Parent>(&self) -> &Id<TF, Parent, Repr>
+    where
+        Entity: SubEntityOf<Parent>,
+    {
+        // SAFETY: `Id` is `#[repr(transparent)]` over its `Repr`.
+        unsafe { &*(&self.0 as *const Repr as *const Id<TF, Parent, Repr>) }
+    }
+
+    /// Convert to an id of a sub entity (as allowed by [SubEntityOf]) if `is_sub` accepts the
+    /// value. Otherwise return `self` back as `Err`.
+    pub fn downcast<Sub>(
+        self,
+        is_sub: impl FnOnce(&Repr) -> bool,
+    ) -> Result<Id<TF, Sub, Repr>, Self>
+    where
+        Sub: SubEntityOf<Entity>,
+    {
+        if is_sub(&self.0) {
+            Ok(Id::new(self.0))
+        } else {
+            Err(self)
+        }
+    }
 }
 
 #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
@@ -289,6 +593,7 @@ impl<Unit, Repr: Default> Default
 
 #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
 impl<const TF: TraitFlags, Entity, Repr: PartialEq> PartialEq for Id<TF, Entity, Repr> {
+    #[allow(clippy::needless_borrow)]
     fn eq(&self, rhs: &Self) -> bool {
         self.get().eq(&rhs.get())
     }
@@ -296,6 +601,7 @@ impl<const TF: TraitFlags, Entity, Repr: PartialEq> PartialEq for Id<TF, Entity,
 
 #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
 impl<const TF: TraitFlags, Entity, Repr: PartialOrd> PartialOrd for Id<TF, Entity, Repr> {
+    #[allow(clippy::needless_borrow)]
     fn partial_cmp(&self, rhs: &Self) -> Option<Ordering> {
         self.get().partial_cmp(&rhs.get())
     }
@@ -303,6 +609,7 @@ impl<const TF: TraitFlags, Entity, Repr: PartialOrd> PartialOrd for Id<TF, Entit
 
 #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
 impl<const TF: TraitFlags, Entity, Repr: Ord> Ord for Id<TF, Entity, Repr> {
+    #[allow(clippy::needless_borrow)]
     fn cmp(&self, rhs: &Self) -> Ordering {
         self.get().cmp(&rhs.get())
     }
@@ -315,6 +622,15 @@ impl<const TF: TraitFlags, Entity, Repr: Hash> Hash for Id<TF, Entity, Repr> {
     }
 }
 
+// `Eq`, `Ord` and `Hash` of `Id` agree with those of `Repr`, so maps and sets keyed by ids can be
+// queried by `&Repr`. See also [crate::IdMap].
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+impl<const TF: TraitFlags, Entity, Repr> Borrow<Repr> for Id<TF, Entity, Repr> {
+    fn borrow(&self) -> &Repr {
+        &self.0
+    }
+}
+
 #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
 impl<const TF: TraitFlags, Entity, Repr> From<Repr> for Id<TF, Entity, Repr> {
     fn from(repr: Repr) -> Self {
@@ -322,20 +638,102 @@ impl<const TF: TraitFlags, Entity, Repr> From<Repr> for Id<TF, Entity, Repr> {
     }
 }
 
+// Conversions between ids with a `NonZero*` `Repr` and ids with the matching primitive `Repr`.
+macro_rules! impl_non_zero_conversions {
+    ($($t:ty => $non_zero:ty)*) => {
+        $(
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<const TF: TraitFlags, Entity> From<Id<TF, Entity, $non_zero>>
+                for Id<TF, Entity, $t>
+            {
+                fn from(id: Id<TF, Entity, $non_zero>) -> Self {
+                    Self::new(id.get().get())
+                }
+            }
+
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<const TF: TraitFlags, Entity> TryFrom<Id<TF, Entity, $t>>
+                for Id<TF, Entity, $non_zero>
+            {
+                type Error = TryFromIntError;
+
+                fn try_from(id: Id<TF, Entity, $t>) -> Result<Self, Self::Error> {
+                    Self::try_from(*id.get())
+                }
+            }
+
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<const TF: TraitFlags, Entity> TryFrom<$t> for Id<TF, Entity, $non_zero> {
+                type Error = TryFromIntError;
+
+                fn try_from(value: $t) -> Result<Self, Self::Error> {
+                    <$non_zero>::try_from(value).map(Self::new)
+                }
+            }
+        )*
+    };
+}
+impl_non_zero_conversions! {
+    u8 => NonZeroU8
+    u16 => NonZeroU16
+    u32 => NonZeroU32
+    u64 => NonZeroU64
+    u128 => NonZeroU128
+    usize => NonZeroUsize
+    i8 => NonZeroI8
+    i16 => NonZeroI16
+    i32 => NonZeroI32
+    i64 => NonZeroI64
+    i128 => NonZeroI128
+    isize => NonZeroIsize
+}
+
 #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
 impl<const TF: TraitFlags, Entity, Repr: Eq> Eq for Id<TF, Entity, Repr> {}
 
+/// `{:?}` shows the `Repr` only. Alternate `{:#?}` shows the entity's name, too (like amounts and
+/// instants show their unit's name), so that mixed dumps of typed values keep their meaning.
+///
+/// ```
+/// #![cfg_attr(
+///     feature = "unstable_generic_const_own_type",
+///     feature(generic_const_exprs)
+/// )]
+///
+/// use phantom_newtype::{Amount, Id};
+///
+/// enum BlockHeight {}
+/// enum Cycles {}
+///
+/// let height = Id::<BlockHeight, u64>::from(42);
+/// assert_eq!(format!("{:?}", height), "42");
+/// assert_eq!(format!("{:#?}", height), "BlockHeight(\n    42,\n)");
+/// assert_eq!(format!("{:#?}", Amount::<Cycles, u8>::from(7)), "Cycles(\n    7,\n)");
+/// ```
 #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
 impl<const TF: TraitFlags, Entity, Repr: fmt::Debug> fmt::Debug for Id<TF, Entity, Repr> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{:?}", self.get())
+        if f.alternate() {
+            f.debug_tuple(unit_name::<Entity>()).field(&self.0).finish()
+        } else {
+            fmt::Debug::fmt(&self.0, f)
+        }
+    }
+}
+
+// `Redacted` implements neither `Debug` nor `Display`, so these don't overlap the impls for any
+// other `Repr`.
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+impl<const TF: TraitFlags, Entity, Repr> fmt::Debug for Id<TF, Entity, Redacted<Repr>> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<redacted {}>", unit_name::<Entity>())
     }
 }
 
 #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
-impl<const TF: TraitFlags, Entity, Repr: fmt::Display> fmt::Display for Id<TF, Entity, Repr> {
+impl<const TF: TraitFlags, Entity, Repr> fmt::Display for Id<TF, Entity, Redacted<Repr>> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.get())
+        fmt::Debug::fmt(self, f)
     }
 }
 
@@ -360,3 +758,16 @@ where
         Repr::deserialize(deserializer).map(Self::from)
     }
 }
+
+// `subtle::ConstantTimeEq` of byte-backed ids (like `[u8; 32]`, `Vec<u8>` or `repr::CtEq`), for
+// comparing tokens without a timing side channel. `==` stays as it is for `Repr`; use
+// `crate::IdCtEq` to make `==` constant-time, too.
+#[cfg(feature = "subtle")]
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+impl<const TF: TraitFlags, Entity, Repr: AsRef<[u8]>> subtle::ConstantTimeEq
+    for Id<TF, Entity, Repr>
+{
+    fn ct_eq(&self, other: &Self) -> subtle::Choice {
+        self.0.as_ref().ct_eq(other.0.as_ref())
+    }
+}
@@ -0,0 +1,58 @@
+// Copyright 2024 Peter Lyons Kehl
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! `HashMap`s and `HashSet`s keyed by [Id]s of one entity. Requires feature `std`.
+
+use crate::Id;
+use std::collections::hash_map::RandomState;
+use std::collections::{HashMap, HashSet};
+
+/// A `HashMap` keyed by [Id]`<Entity, Repr>`, so that all its keys are ids of the same entity.
+/// As [Id] implements `Borrow<Repr>`, it can be queried by `&Repr`, too.
+///
+/// ```
+/// #![cfg_attr(
+///     feature = "unstable_generic_const_own_type",
+///     feature(generic_const_exprs)
+/// )]
+///
+/// use phantom_newtype::{Id, IdMap, IdSet};
+///
+/// enum User {}
+/// type UserId = Id<User, u64>;
+///
+/// let mut names = IdMap::<User, u64, &str>::new();
+/// names.insert(UserId::from(7), "Ada");
+/// assert_eq!(names.get(&UserId::from(7)), Some(&"Ada"));
+/// assert_eq!(names.get(&7), Some(&"Ada"));
+///
+/// let admins: IdSet<User, u64> = [UserId::from(1), UserId::from(7)].iter().copied().collect();
+/// assert!(admins.contains(&7));
+/// ```
+///
+/// Keys of a different entity don't compile:
+///
+/// ```compile_fail
+/// use phantom_newtype::{Id, IdMap};
+///
+/// enum User {}
+/// enum Group {}
+///
+/// let mut names = IdMap::<User, u64, &str>::new();
+/// names.insert(Id::<Group, u64>::from(7), "admins");
+/// ```
+pub type IdMap<Entity, Repr, V, S = RandomState> = HashMap<Id<Entity, Repr>, V, S>;
+
+/// A `HashSet` of [Id]`<Entity, Repr>`, queryable by `&Repr`, too. See [IdMap].
+pub type IdSet<Entity, Repr, S = RandomState> = HashSet<Id<Entity, Repr>, S>;
@@ -0,0 +1,86 @@
+// Copyright 2024 Peter Lyons Kehl
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Indexing into, and iterating over, the `Repr` of [Amount]s, [Id]s and [Instant]s that is a
+//! collection (like a byte array `Repr` of a hash id), in place.
+//!
+//! ```
+//! #![cfg_attr(
+//!     feature = "unstable_generic_const_own_type",
+//!     feature(generic_const_exprs)
+//! )]
+//!
+//! use phantom_newtype::Id;
+//!
+//! enum Block {}
+//! type BlockHash = Id<Block, [u8; 4]>;
+//!
+//! let hash = BlockHash::from([0xAB, 0, 0, 0x01]);
+//! assert_eq!(hash[0], 0xAB);
+//! assert_eq!(hash[1..3], [0, 0]);
+//! assert_eq!(hash.iter().filter(|&&b| b == 0).count(), 2);
+//! for byte in &hash {
+//!     assert!(*byte <= 0xAB);
+//! }
+//! ```
+
+use crate::amount::Amount;
+use crate::id::Id;
+use crate::instant::Instant;
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+use crate::trait_flag::TraitFlags;
+use core::ops::Index;
+
+macro_rules! impl_index {
+    ($($wrapper:ident)*) => {
+        $(
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<const TF: TraitFlags, Unit, Repr: Index<Idx>, Idx> Index<Idx>
+                for $wrapper<TF, Unit, Repr>
+            {
+                type Output = Repr::Output;
+
+                fn index(&self, index: Idx) -> &Self::Output {
+                    &self.0[index]
+                }
+            }
+
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<'a, const TF: TraitFlags, Unit, Repr> IntoIterator for &'a $wrapper<TF, Unit, Repr>
+            where
+                &'a Repr: IntoIterator,
+            {
+                type Item = <&'a Repr as IntoIterator>::Item;
+                type IntoIter = <&'a Repr as IntoIterator>::IntoIter;
+
+                fn into_iter(self) -> Self::IntoIter {
+                    self.0.into_iter()
+                }
+            }
+
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<const TF: TraitFlags, Unit, Repr> $wrapper<TF, Unit, Repr> {
+                /// Iterate over the items of `Repr` by reference, like `self.get().iter()` does
+                /// for arrays and `Vec`s.
+                pub fn iter<'a>(&'a self) -> <&'a Repr as IntoIterator>::IntoIter
+                where
+                    &'a Repr: IntoIterator,
+                {
+                    self.0.into_iter()
+                }
+            }
+        )*
+    };
+}
+impl_index!(Amount Id Instant);
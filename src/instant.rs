@@ -14,7 +14,11 @@
 // limitations under the License.
 
 use crate::amount::Amount;
-use crate::displayer::{DisplayProxy, DisplayerOf};
+use crate::displayer::{
+    unit_name, DebugProxy, DebugerOf, DisplayProxy, DisplayerOf, GroupedDisplay,
+};
+use crate::ops::{AddTo, SubFrom};
+use crate::repr::ConstDefault;
 #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
 use crate::trait_flag::{self, TraitFlags};
 use core::cmp::Ordering;
@@ -54,7 +58,8 @@ use serde::{Deserialize, Deserializer, Serialize, Serializer};
 ///
 /// Instants support basic arithmetics, you can:
 /// * Subtract an instant from another instant to get amount of units between them.
-/// * Add/subtract amount of units to/from an instant to get another instant.
+/// * Add/subtract amount of units to/from an instant to get another instant (and add them in
+///   either order).
 ///
 /// ```
 /// #![cfg_attr(
@@ -76,6 +81,7 @@ use serde::{Deserialize, Deserializer, Serialize, Serializer};
 /// assert_eq!(some_date - epoch, diff);
 /// assert_eq!(some_date - diff, epoch);
 /// assert_eq!(epoch + diff, some_date);
+/// assert_eq!(diff + epoch, some_date);
 /// ```
 ///
 /// Direct multiplication of instants is not supported, however, you
@@ -175,19 +181,36 @@ use serde::{Deserialize, Deserializer, Serialize, Serializer};
 /// let instant_from_thread = std::thread::spawn(|| &I).join().unwrap();
 /// assert_eq!(I, *instant_from_thread);
 /// ```
+///
+/// But not if the `Repr` doesn't allow it:
+///
+/// ```compile_fail
+/// use phantom_newtype::Instant;
+/// use std::rc::Rc;
+///
+/// let shared = Instant::<(), Rc<u8>>::new(Rc::new(1));
+/// std::thread::spawn(move || shared);
+/// ```
 #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+#[cfg_attr(
+    feature = "zerocopy",
+    derive(
+        zerocopy::FromBytes,
+        zerocopy::IntoBytes,
+        zerocopy::Unaligned,
+        zerocopy::KnownLayout,
+        zerocopy::Immutable
+    )
+)]
 #[repr(transparent)]
 //`pub struct Instant<Unit, Repr>(Repr, PhantomData<*const Unit>);
 //pub struct Instant<Unit, Repr>(Repr, PhantomData<core::sync::Exclusive<Unit>>);
-pub struct Instant<const TF: TraitFlags, Unit, Repr>(
-    Repr,
-    PhantomData<core::sync::atomic::AtomicPtr<Unit>>,
-);
+pub struct Instant<const TF: TraitFlags, Unit, Repr>(pub(crate) Repr, PhantomData<fn() -> Unit>);
 #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
-impl<const TF: TraitFlags, Unit, Repr: Copy> Instant<TF, Unit, Repr> {
+impl<const TF: TraitFlags, Unit, Repr> Instant<TF, Unit, Repr> {
     // @TODO
 
-    /// Returns the wrapped value.
+    /// Returns a reference to the wrapped value.
     ///
     /// ```
     /// #![cfg_attr(
@@ -200,9 +223,32 @@ impl<const TF: TraitFlags, Unit, Repr: Copy> Instant<TF, Unit, Repr> {
     /// enum Apples {}
     ///
     /// let three_apples = Instant::<Apples, u64>::from(3);
-    /// assert_eq!(9, (three_apples * 3).get());
+    /// assert_eq!(9, *(three_apples * 3).get());
+    /// ```
+    pub const fn get(&self) -> &Repr {
+        &self.0
+    }
+}
+
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+impl<const TF: TraitFlags, Unit, Repr: Copy> Instant<TF, Unit, Repr> {
+    /// Returns a copy of the wrapped value.
+    ///
     /// ```
-    pub fn get(&self) -> Repr {
+    /// #![cfg_attr(
+    ///     feature = "unstable_generic_const_own_type",
+    ///     feature(generic_const_exprs)
+    /// )]
+    ///
+    /// use phantom_newtype::Instant;
+    ///
+    /// enum Apples {}
+    ///
+    /// const THREE_APPLES: Instant<Apples, u64> = Instant::new(3);
+    /// const THREE: u64 = THREE_APPLES.get_copy();
+    /// assert_eq!(3, THREE);
+    /// ```
+    pub const fn get_copy(&self) -> Repr {
         self.0
     }
 }
@@ -217,6 +263,38 @@ impl<const TF: TraitFlags, Unit, Repr> Instant<TF, Unit, Repr> {
     }
 }
 
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+impl<const TF: TraitFlags, Unit, Repr: ConstDefault> Instant<TF, Unit, Repr> {
+    /// The instant at zero (the epoch), that is the [ConstDefault] of `Repr`, usable in patterns'
+    /// guards and in `const` and `static` initializers. Unlike `default()`, available regardless of
+    /// the trait flags:
+    ///
+    /// ```
+    /// #![cfg_attr(
+    ///     feature = "unstable_generic_const_own_type",
+    ///     feature(generic_const_exprs)
+    /// )]
+    ///
+    /// use phantom_newtype::{Amount, Instant, InstantNoDefault};
+    ///
+    /// enum SecondsFromEpoch {}
+    ///
+    /// struct Lease {
+    ///     granted: Instant<SecondsFromEpoch, i64>,
+    ///     expires: InstantNoDefault<SecondsFromEpoch, i64>,
+    /// }
+    ///
+    /// static UNGRANTED: Lease = Lease {
+    ///     granted: Instant::DEFAULT,
+    ///     expires: InstantNoDefault::ZERO,
+    /// };
+    /// assert_eq!(UNGRANTED.granted, Instant::ZERO);
+    /// assert_eq!(UNGRANTED.granted + Amount::from(60), Instant::from(60));
+    /// assert_eq!(UNGRANTED.expires.get(), &0);
+    /// ```
+    pub const ZERO: Self = Instant(Repr::DEFAULT, PhantomData);
+}
+
 #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
 impl<const TF: TraitFlags, Unit: Default, Repr> Instant<TF, Unit, Repr> {
     /// Provides a useful shortcut to access units of an instant if
@@ -277,6 +355,48 @@ where
     pub fn display(&self) -> DisplayProxy<'_, Self, Unit> {
         DisplayProxy::new(self)
     }
+
+    /// Write with the displayer (as per [Self::display]) straight into `out`, for example a
+    /// reused buffer, without an intermediate `String`.
+    pub fn display_into<W: fmt::Write + ?Sized>(&self, out: &mut W) -> fmt::Result {
+        write!(out, "{}", self.display())
+    }
+}
+
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+impl<const TF: TraitFlags, Unit, Repr> Instant<TF, Unit, Repr>
+where
+    Unit: DebugerOf<Self>,
+{
+    /// `debug` attaches a custom `Debug` formatting (as per [DebugerOf]), separate from
+    /// [Self::display].
+    pub fn debug(&self) -> DebugProxy<'_, Self, Unit> {
+        DebugProxy::new(self)
+    }
+}
+
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+impl<const TF: TraitFlags, Unit, Repr: fmt::Display> Instant<TF, Unit, Repr> {
+    /// Display with the digits of the integer part in groups of three, like `1,234,567`. See
+    /// [GroupedDisplay].
+    ///
+    /// ```
+    /// #![cfg_attr(
+    ///     feature = "unstable_generic_const_own_type",
+    ///     feature(generic_const_exprs)
+    /// )]
+    ///
+    /// use phantom_newtype::Instant;
+    ///
+    /// enum Block {}
+    ///
+    /// let height = Instant::<Block, u64>::from(19_000_000);
+    /// assert_eq!(height.grouped_display().to_string(), "19,000,000");
+    /// assert_eq!(height.grouped_display().separator(' ').to_string(), "19 000 000");
+    /// ```
+    pub fn grouped_display(&self) -> GroupedDisplay<'_, Repr> {
+        GroupedDisplay::new(&self.0)
+    }
 }
 
 #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
@@ -313,6 +433,11 @@ impl<Unit, Repr: Default> Default
     }
 }
 #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+impl<Unit, Repr: ConstDefault> Instant<{ trait_flag::TRAIT_FLAGS_IS_COPY_IS_DEFAULT }, Unit, Repr> {
+    /// `const` counterpart of `default()`, for instants that implement `Default`. See [Self::ZERO].
+    pub const DEFAULT: Self = Self(Repr::DEFAULT, PhantomData);
+}
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
 impl<Unit, Repr: Default> Default
     for Instant<{ trait_flag::TRAIT_FLAGS_NO_COPY_IS_DEFAULT }, Unit, Repr>
 {
@@ -320,6 +445,11 @@ impl<Unit, Repr: Default> Default
         Self(Default::default(), PhantomData)
     }
 }
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+impl<Unit, Repr: ConstDefault> Instant<{ trait_flag::TRAIT_FLAGS_NO_COPY_IS_DEFAULT }, Unit, Repr> {
+    /// `const` counterpart of `default()`, for instants that implement `Default`. See [Self::ZERO].
+    pub const DEFAULT: Self = Self(Repr::DEFAULT, PhantomData);
+}
 
 #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
 impl<const TF: TraitFlags, Unit, Repr: PartialEq> PartialEq for Instant<TF, Unit, Repr> {
@@ -353,16 +483,41 @@ impl<const TF: TraitFlags, Unit, Repr: Hash> Hash for Instant<TF, Unit, Repr> {
 }
 
 #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
-impl<const TF: TraitFlags, Unit, Repr, Repr2> Add<Amount<TF, Unit, Repr2>>
+impl<const TF: TraitFlags, Unit, Repr, Rhs> Add<Rhs> for Instant<TF, Unit, Repr>
+where
+    Rhs: AddTo<Self>,
+{
+    type Output = Rhs::Output;
+    fn add(self, rhs: Rhs) -> Self::Output {
+        rhs.add_to(self)
+    }
+}
+
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+impl<const TF: TraitFlags, Unit, Repr, Repr2> AddTo<Instant<TF, Unit, Repr>>
+    for Amount<TF, Unit, Repr2>
+where
+    Repr: AddAssign<Repr2> + Copy,
+    Repr2: Copy,
+{
+    type Output = Instant<TF, Unit, Repr>;
+    fn add_to(self, mut lhs: Instant<TF, Unit, Repr>) -> Instant<TF, Unit, Repr> {
+        lhs.add_assign(self);
+        lhs
+    }
+}
+
+// The same as `Instant + Amount`.
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+impl<const TF: TraitFlags, Unit, Repr, Repr2> AddTo<Amount<TF, Unit, Repr2>>
     for Instant<TF, Unit, Repr>
 where
     Repr: AddAssign<Repr2> + Copy,
     Repr2: Copy,
 {
     type Output = Self;
-    fn add(mut self, rhs: Amount<TF, Unit, Repr2>) -> Self {
-        self.add_assign(rhs);
-        self
+    fn add_to(self, lhs: Amount<TF, Unit, Repr2>) -> Self {
+        lhs.add_to(self)
     }
 }
 
@@ -374,7 +529,7 @@ where
     Repr2: Copy,
 {
     fn add_assign(&mut self, rhs: Amount<TF, Unit, Repr2>) {
-        self.0 += rhs.get()
+        self.0 += rhs.0
     }
 }
 
@@ -386,34 +541,82 @@ where
     Repr2: Copy,
 {
     fn sub_assign(&mut self, rhs: Amount<TF, Unit, Repr2>) {
-        self.0 -= rhs.get()
+        self.0 -= rhs.0
+    }
+}
+
+// `abs_diff` of integer `Repr`s: the distance between instants, regardless of their order.
+macro_rules! impl_abs_diff {
+    ($($int:ident => $unsigned:ident)*) => {
+        $(
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<const TF: TraitFlags, Unit> Instant<TF, Unit, $int> {
+                #[doc = concat!("The (unsigned) amount between `self` and `other`, as per [", stringify!($int), "::abs_diff].")]
+                ///
+                /// ```
+                /// #![cfg_attr(
+                ///     feature = "unstable_generic_const_own_type",
+                ///     feature(generic_const_exprs)
+                /// )]
+                ///
+                /// use phantom_newtype::{Amount, Instant};
+                ///
+                /// enum Millis {}
+                #[doc = concat!("type Timestamp = Instant<Millis, ", stringify!($int), ">;")]
+                ///
+                /// let (sent, received) = (Timestamp::from(40), Timestamp::from(25));
+                #[doc = concat!("let skew: Amount<Millis, ", stringify!($unsigned), "> = sent.abs_diff(received);")]
+                /// assert_eq!(skew, received.abs_diff(sent));
+                /// assert_eq!(skew.get_copy(), 15);
+                /// ```
+                pub const fn abs_diff(self, other: Self) -> Amount<TF, Unit, $unsigned> {
+                    Amount::new(self.0.abs_diff(other.0))
+                }
+            }
+        )*
+    };
+}
+impl_abs_diff! {
+    u8 => u8 u16 => u16 u32 => u32 u64 => u64 u128 => u128 usize => usize
+    i8 => u8 i16 => u16 i32 => u32 i64 => u64 i128 => u128 isize => usize
+}
+
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+impl<const TF: TraitFlags, Unit, Repr, Rhs> Sub<Rhs> for Instant<TF, Unit, Repr>
+where
+    Rhs: SubFrom<Self>,
+{
+    type Output = Rhs::Output;
+
+    fn sub(self, rhs: Rhs) -> Self::Output {
+        rhs.sub_from(self)
     }
 }
 
 #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
-impl<const TF: TraitFlags, Unit, Repr> Sub for Instant<TF, Unit, Repr>
+impl<const TF: TraitFlags, Unit, Repr> SubFrom<Self> for Instant<TF, Unit, Repr>
 where
     Repr: Sub + Copy,
 {
     type Output = Amount<TF, Unit, <Repr as Sub>::Output>;
 
-    fn sub(self, rhs: Self) -> Self::Output {
-        Amount::<TF, Unit, <Repr as Sub>::Output>::new(self.0 - rhs.0)
+    fn sub_from(self, lhs: Self) -> Self::Output {
+        Amount::<TF, Unit, <Repr as Sub>::Output>::new(lhs.0 - self.0)
     }
 }
 
 #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
-impl<const TF: TraitFlags, Unit, Repr, Repr2> Sub<Amount<TF, Unit, Repr2>>
-    for Instant<TF, Unit, Repr>
+impl<const TF: TraitFlags, Unit, Repr, Repr2> SubFrom<Instant<TF, Unit, Repr>>
+    for Amount<TF, Unit, Repr2>
 where
     Repr: SubAssign<Repr2> + Copy,
     Repr2: Copy,
 {
-    type Output = Self;
+    type Output = Instant<TF, Unit, Repr>;
 
-    fn sub(mut self, rhs: Amount<TF, Unit, Repr2>) -> Self {
-        self.sub_assign(rhs);
-        self
+    fn sub_from(self, mut lhs: Instant<TF, Unit, Repr>) -> Instant<TF, Unit, Repr> {
+        lhs.sub_assign(self);
+        lhs
     }
 }
 
@@ -458,17 +661,11 @@ where
     Repr: fmt::Debug,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        self.0.fmt(f)
-    }
-}
-
-#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
-impl<const TF: TraitFlags, Unit, Repr> fmt::Display for Instant<TF, Unit, Repr>
-where
-    Repr: fmt::Display,
-{
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        self.0.fmt(f)
+        if f.alternate() {
+            f.debug_tuple(unit_name::<Unit>()).field(&self.0).finish()
+        } else {
+            fmt::Debug::fmt(&self.0, f)
+        }
     }
 }
 
@@ -491,11 +688,29 @@ where
     }
 }
 
+// `num-traits` `Bounded` delegates to `Repr`, for the earliest and the latest instant. Not `Zero`:
+// it requires `Add<Self>`, and instants don't add up.
+#[cfg(feature = "num-traits")]
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+impl<const TF: TraitFlags, Unit, Repr> num_traits::Bounded for Instant<TF, Unit, Repr>
+where
+    Repr: num_traits::Bounded,
+{
+    fn min_value() -> Self {
+        Self::new(Repr::min_value())
+    }
+
+    fn max_value() -> Self {
+        Self::new(Repr::max_value())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::*;
 
     #[test]
+    #[allow(clippy::upper_case_acronyms)]
     fn test_complex_instant_arithmetics() {
         enum Seconds {}
         enum UTC {}
@@ -0,0 +1,298 @@
+// Copyright 2024 Peter Lyons Kehl
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Iterator adapters over sequences of typed values, and iterators generating them.
+
+use crate::amount::Amount;
+use crate::id::Id;
+use crate::instant::Instant;
+use crate::repr::{ArithmeticOverflow, CheckedAdd, CheckedSub};
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+use crate::trait_flag::TraitFlags;
+use core::iter::Map;
+
+/// Iterator of differences between successive [crate::Instant]s. See
+/// [InstantIteratorExt::deltas].
+pub struct Deltas<I: Iterator> {
+    iter: I,
+    previous: Option<I::Item>,
+}
+
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+impl<I, const TF: TraitFlags, Unit, Repr> Iterator for Deltas<I>
+where
+    I: Iterator<Item = Instant<TF, Unit, Repr>>,
+    Repr: CheckedSub + Copy,
+{
+    type Item = Result<Amount<TF, Unit, Repr>, ArithmeticOverflow>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.previous.is_none() {
+            self.previous = Some(self.iter.next()?);
+        }
+        let current = self.iter.next()?;
+        let current_repr = current.get_copy();
+        let previous = self.previous.replace(current)?;
+        Some(
+            current_repr
+                .checked_sub(previous.get_copy())
+                .map(Amount::new)
+                .ok_or(ArithmeticOverflow),
+        )
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        if self.previous.is_some() {
+            (lower, upper)
+        } else {
+            (
+                lower.saturating_sub(1),
+                upper.map(|upper| upper.saturating_sub(1)),
+            )
+        }
+    }
+}
+
+/// Iterator of [crate::Instant]s rebuilt from a start and successive differences. See
+/// [AmountIteratorExt::reconstruct].
+pub struct Reconstruct<I, Current> {
+    iter: I,
+    /// `None` once an overflow has been reported.
+    current: Option<Current>,
+}
+
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+impl<I, const TF: TraitFlags, Unit, Repr> Iterator for Reconstruct<I, Instant<TF, Unit, Repr>>
+where
+    I: Iterator<Item = Amount<TF, Unit, Repr>>,
+    Repr: CheckedAdd + Copy,
+{
+    type Item = Result<Instant<TF, Unit, Repr>, ArithmeticOverflow>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.current.as_ref()?.get_copy();
+        let delta = self.iter.next()?;
+        match current.checked_add(delta.get_copy()) {
+            Some(next) => {
+                let next = Instant::new(next);
+                self.current = Some(Instant::new(next.get_copy()));
+                Some(Ok(next))
+            }
+            None => {
+                self.current = None;
+                Some(Err(ArithmeticOverflow))
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.current.is_some() {
+            self.iter.size_hint()
+        } else {
+            (0, Some(0))
+        }
+    }
+}
+
+/// Adapters for iterators of [crate::Instant]s.
+pub trait InstantIteratorExt: Iterator + Sized {
+    /// Differences between successive instants, as [crate::Amount]s. A sequence of `n` instants
+    /// yields `n - 1` differences. A difference that doesn't fit `Repr` yields
+    /// [ArithmeticOverflow], but the following differences are still computed.
+    ///
+    /// ```
+    /// #![cfg_attr(
+    ///     feature = "unstable_generic_const_own_type",
+    ///     feature(generic_const_exprs)
+    /// )]
+    ///
+    /// use phantom_newtype::{Amount, Instant, InstantIteratorExt};
+    ///
+    /// enum Millis {}
+    /// type Timestamp = Instant<Millis, u64>;
+    /// type Elapsed = Amount<Millis, u64>;
+    ///
+    /// let samples = [Timestamp::from(100), Timestamp::from(130), Timestamp::from(190)];
+    /// let deltas: Result<Vec<_>, _> = samples.iter().copied().deltas().collect();
+    /// assert_eq!(deltas.unwrap(), [Elapsed::from(30), Elapsed::from(60)]);
+    ///
+    /// // Going back in time doesn't fit an unsigned `Repr`:
+    /// let backwards = [Timestamp::from(5), Timestamp::from(3)];
+    /// let mut deltas = backwards.iter().copied().deltas();
+    /// assert!(deltas.next().unwrap().is_err());
+    /// ```
+    fn deltas(self) -> Deltas<Self> {
+        Deltas {
+            iter: self,
+            previous: None,
+        }
+    }
+}
+
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+impl<I, const TF: TraitFlags, Unit, Repr> InstantIteratorExt for I where
+    I: Iterator<Item = Instant<TF, Unit, Repr>>
+{
+}
+
+/// Adapters for iterators of [crate::Amount]s.
+pub trait AmountIteratorExt: Iterator + Sized {
+    /// The inverse of [InstantIteratorExt::deltas]: instants obtained by adding successive
+    /// amounts to `start`. `start` itself is not yielded. Once an addition overflows, the iterator
+    /// yields [ArithmeticOverflow] and then ends.
+    ///
+    /// ```
+    /// #![cfg_attr(
+    ///     feature = "unstable_generic_const_own_type",
+    ///     feature(generic_const_exprs)
+    /// )]
+    ///
+    /// use phantom_newtype::{Amount, AmountIteratorExt, Instant, InstantIteratorExt};
+    ///
+    /// enum Millis {}
+    /// type Timestamp = Instant<Millis, u64>;
+    /// type Elapsed = Amount<Millis, u64>;
+    ///
+    /// let samples = [Timestamp::from(100), Timestamp::from(130), Timestamp::from(190)];
+    /// let rebuilt: Result<Vec<_>, _> = samples
+    ///     .iter()
+    ///     .copied()
+    ///     .deltas()
+    ///     .map(Result::unwrap)
+    ///     .reconstruct(samples[0])
+    ///     .collect();
+    /// assert_eq!(rebuilt.unwrap(), samples[1..]);
+    ///
+    /// let huge = [Elapsed::from(u64::MAX), Elapsed::from(1)];
+    /// let mut overflowing = huge.iter().copied().reconstruct(Timestamp::from(1));
+    /// assert!(overflowing.next().unwrap().is_err());
+    /// assert!(overflowing.next().is_none());
+    /// ```
+    #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+    fn reconstruct<const TF: TraitFlags, Unit, Repr>(
+        self,
+        start: Instant<TF, Unit, Repr>,
+    ) -> Reconstruct<Self, Instant<TF, Unit, Repr>>
+    where
+        Self: Iterator<Item = Amount<TF, Unit, Repr>>,
+    {
+        Reconstruct {
+            iter: self,
+            current: Some(start),
+        }
+    }
+}
+
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+impl<I, const TF: TraitFlags, Unit, Repr> AmountIteratorExt for I where
+    I: Iterator<Item = Amount<TF, Unit, Repr>>
+{
+}
+
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+impl<const TF: TraitFlags, Entity, Repr: CheckedAdd + Copy> Id<TF, Entity, Repr> {
+    /// Ids `self`, `self + step`, `self + 2 * step`... The sequence ends (only) when the next id
+    /// would overflow `Repr`.
+    ///
+    /// ```
+    /// #![cfg_attr(
+    ///     feature = "unstable_generic_const_own_type",
+    ///     feature(generic_const_exprs)
+    /// )]
+    ///
+    /// use phantom_newtype::Id;
+    ///
+    /// enum Shard {}
+    /// type ShardId = Id<Shard, u8>;
+    ///
+    /// let ids: Vec<_> = ShardId::from(1).successors(2).take(3).collect();
+    /// assert_eq!(ids, [ShardId::from(1), ShardId::from(3), ShardId::from(5)]);
+    /// assert_eq!(ShardId::from(250).successors(5).count(), 2);
+    /// ```
+    pub fn successors(self, step: Repr) -> impl Iterator<Item = Self> {
+        core::iter::successors(Some(self), move |id| {
+            id.get().checked_add(step).map(Self::new)
+        })
+    }
+}
+
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+impl<const TF: TraitFlags, Unit, Repr: CheckedAdd + Copy> Instant<TF, Unit, Repr> {
+    /// Instants `self`, `self + step`, `self + 2 * step`... The sequence ends (only) when the next
+    /// instant would overflow `Repr`.
+    ///
+    /// ```
+    /// #![cfg_attr(
+    ///     feature = "unstable_generic_const_own_type",
+    ///     feature(generic_const_exprs)
+    /// )]
+    ///
+    /// use phantom_newtype::{Amount, Instant};
+    ///
+    /// enum Minutes {}
+    /// type Time = Instant<Minutes, u32>;
+    ///
+    /// let schedule: Vec<_> = Time::from(60).ticks_every(Amount::from(15)).take(3).collect();
+    /// assert_eq!(schedule, [Time::from(60), Time::from(75), Time::from(90)]);
+    /// ```
+    pub fn ticks_every(self, step: Amount<TF, Unit, Repr>) -> impl Iterator<Item = Self> {
+        let step = step.get_copy();
+        core::iter::successors(Some(self), move |instant| {
+            instant.get_copy().checked_add(step).map(Self::new)
+        })
+    }
+}
+
+macro_rules! impl_wrap_iter {
+    ($($wrapper:ident)*) => {
+        $(
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<const TF: TraitFlags, Unit, Repr> $wrapper<TF, Unit, Repr> {
+                /// Wrap each `Repr` of `iter`, for example to collect or extend typed collections
+                /// from raw values. See also `wrap_slice`.
+                ///
+                /// ```
+                /// #![cfg_attr(
+                ///     feature = "unstable_generic_const_own_type",
+                ///     feature(generic_const_exprs)
+                /// )]
+                ///
+                #[doc = concat!("use phantom_newtype::", stringify!($wrapper), ";")]
+                ///
+                /// enum Unit {}
+                #[doc = concat!("type T = ", stringify!($wrapper), "<Unit, u32>;")]
+                ///
+                /// let mut typed: Vec<T> = T::wrap_iter(1..=2).collect();
+                /// typed.extend(T::wrap_iter(vec![3]));
+                /// assert_eq!(typed, [T::from(1), T::from(2), T::from(3)]);
+                /// assert_eq!(T::peel_iter(typed).sum::<u32>(), 6);
+                /// ```
+                pub fn wrap_iter<I: IntoIterator<Item = Repr>>(
+                    iter: I,
+                ) -> Map<I::IntoIter, fn(Repr) -> Self> {
+                    iter.into_iter().map(Self::new)
+                }
+
+                /// The `Repr` of each item of `iter`. The inverse of `wrap_iter`.
+                pub fn peel_iter<I: IntoIterator<Item = Self>>(
+                    iter: I,
+                ) -> Map<I::IntoIter, fn(Self) -> Repr> {
+                    iter.into_iter().map(|value| value.0)
+                }
+            }
+        )*
+    };
+}
+impl_wrap_iter!(Amount Id Instant);
@@ -0,0 +1,139 @@
+// Copyright 2024 Peter Lyons Kehl
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Layout checks usable in `const` contexts.
+//!
+//! [crate::Amount], [crate::Id] and [crate::Instant] are `#[repr(transparent)]`. Downstream crates
+//! can embed that guarantee about their own aliases into their own compilation, so that any
+//! change of layout (for example a field added by a future flag) fails their build immediately:
+//!
+//! ```
+//! #![cfg_attr(
+//!     feature = "unstable_generic_const_own_type",
+//!     feature(generic_const_exprs)
+//! )]
+//!
+//! use phantom_newtype::{static_assert_transparent, Id};
+//!
+//! enum User {}
+//! type UserId = Id<User, u64>;
+//!
+//! static_assert_transparent!(UserId, u64);
+//! ```
+//!
+//! A mismatch is a compile error:
+//!
+//! ```compile_fail
+//! use phantom_newtype::{static_assert_transparent, Id};
+//!
+//! enum User {}
+//! type UserId = Id<User, u64>;
+//!
+//! static_assert_transparent!(UserId, u32);
+//! ```
+
+use core::mem;
+
+/// Whether `A` and `B` have the same size and the same alignment.
+///
+/// This is a `const fn`, so you can use it in `const _: () = assert!(...);` items.
+///
+/// ```
+/// #![cfg_attr(
+///     feature = "unstable_generic_const_own_type",
+///     feature(generic_const_exprs)
+/// )]
+///
+/// use phantom_newtype::{layout, Amount};
+///
+/// enum Bytes {}
+///
+/// const _: () = assert!(layout::same_size_and_align::<Amount<Bytes, u64>, u64>());
+/// assert!(!layout::same_size_and_align::<Amount<Bytes, u64>, u32>());
+/// ```
+pub const fn same_size_and_align<A, B>() -> bool {
+    mem::size_of::<A>() == mem::size_of::<B>() && mem::align_of::<A>() == mem::align_of::<B>()
+}
+
+/// Panic unless `A` and `B` have the same size and the same alignment. In a `const` item, that
+/// panic is a compile error, so this asserts the layout statically, like
+/// [crate::static_assert_transparent] does (but with a less specific message, naming no types).
+///
+/// ```
+/// #![cfg_attr(
+///     feature = "unstable_generic_const_own_type",
+///     feature(generic_const_exprs)
+/// )]
+///
+/// use phantom_newtype::{layout, Instant};
+///
+/// enum Millis {}
+/// type Timestamp = Instant<Millis, u64>;
+///
+/// const _: () = layout::assert_same_layout::<Timestamp, u64>();
+/// ```
+///
+/// ```compile_fail
+/// use phantom_newtype::{layout, Instant};
+///
+/// enum Millis {}
+/// type Timestamp = Instant<Millis, u64>;
+///
+/// const _: () = layout::assert_same_layout::<Timestamp, i32>();
+/// ```
+pub const fn assert_same_layout<A, B>() {
+    assert!(
+        same_size_and_align::<A, B>(),
+        "the types don't have the same size and alignment"
+    );
+}
+
+/// Assert at compile time that `$alias` has the same size and alignment as `$repr`.
+///
+/// Expands to a `const _: () = ...;` item, so use it at item level (in a module, not inside an
+/// expression). See [crate::layout].
+#[macro_export]
+macro_rules! static_assert_transparent {
+    ($alias:ty, $repr:ty $(,)?) => {
+        const _: () = assert!(
+            $crate::layout::same_size_and_align::<$alias, $repr>(),
+            // Not a literal format string: the types may contain braces (like `{ FLAGS }`).
+            "{}",
+            concat!(
+                "`",
+                stringify!($alias),
+                "` doesn't have the same layout as `",
+                stringify!($repr),
+                "`"
+            )
+        );
+    };
+}
+
+// Our own guarantees, for each archetype and for both a "small" and a "big" `Repr`.
+static_assert_transparent!(crate::Amount<(), u8>, u8);
+static_assert_transparent!(crate::Amount<(), u128>, u128);
+static_assert_transparent!(crate::AmountNoCopyNoDefault<(), [u8; 32]>, [u8; 32]);
+static_assert_transparent!(crate::Id<(), u8>, u8);
+static_assert_transparent!(crate::Id<(), u128>, u128);
+static_assert_transparent!(crate::IdNoCopyNoDefault<(), [u8; 32]>, [u8; 32]);
+static_assert_transparent!(crate::Instant<(), u8>, u8);
+static_assert_transparent!(crate::Instant<(), u128>, u128);
+static_assert_transparent!(crate::InstantNoCopyNoDefault<(), [u8; 32]>, [u8; 32]);
+// `NonZero*` ids keep the niche.
+static_assert_transparent!(Option<crate::NonZeroId<(), u64>>, u64);
+static_assert_transparent!(
+    Option<crate::IdNoCopyNoDefault<(), core::num::NonZeroU8>>,
+    u8
+);
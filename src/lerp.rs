@@ -0,0 +1,102 @@
+// Copyright 2024 Peter Lyons Kehl
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Linear interpolation of [Amount]s and [Instant]s. Requires feature `lerp`.
+
+use crate::amount::Amount;
+use crate::instant::Instant;
+use crate::repr::{FromF64, ToF64};
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+use crate::trait_flag::TraitFlags;
+
+/// `(1 - t) * start + t * end`, computed in `f64` (see [ToF64]) and rounded back to `Repr` (see
+/// [FromF64]), and extrapolating for `t` outside `0..=1`. `start` at `t == 0` and `end` at `t == 1`
+/// as they are, without the round trip through `f64`.
+fn lerp_repr<Repr: ToF64 + FromF64>(start: Repr, end: Repr, t: f64) -> Repr {
+    if t == 0.0 {
+        start
+    } else if t == 1.0 {
+        end
+    } else {
+        Repr::from_f64((1.0 - t) * start.to_f64() + t * end.to_f64())
+    }
+}
+
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+impl<const TF: TraitFlags, Unit, Repr: ToF64 + FromF64> Amount<TF, Unit, Repr> {
+    /// The amount at fraction `t` of the way from `self` to `end`: exactly `self` at `t == 0.0`, and
+    /// `end` at `t == 1.0`. `t` outside `0.0..=1.0` extrapolates (clamp it first if that's
+    /// unwanted). Otherwise computed in `f64`, so integer results are rounded (half away from
+    /// zero), and `Repr`s beyond 2^53 lose precision. Requires feature `lerp`.
+    ///
+    /// ```
+    /// #![cfg_attr(
+    ///     feature = "unstable_generic_const_own_type",
+    ///     feature(generic_const_exprs)
+    /// )]
+    ///
+    /// use phantom_newtype::Amount;
+    ///
+    /// enum Pixels {}
+    /// type Width = Amount<Pixels, u32>;
+    ///
+    /// let (collapsed, expanded) = (Width::from(40), Width::from(240));
+    /// assert_eq!(collapsed.lerp(expanded, 0.0), collapsed);
+    /// assert_eq!(collapsed.lerp(expanded, 0.25), Width::from(90));
+    /// assert_eq!(Width::lerp(collapsed, expanded, 1.0), expanded);
+    /// ```
+    pub fn lerp(self, end: Self, t: f64) -> Self {
+        Self::new(lerp_repr(self.0, end.0, t))
+    }
+}
+
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+impl<const TF: TraitFlags, Unit, Repr: ToF64 + FromF64> Instant<TF, Unit, Repr> {
+    /// The instant at fraction `t` of the way from `self` to `end`, like [Amount::lerp]. Requires
+    /// feature `lerp`.
+    ///
+    /// ```
+    /// #![cfg_attr(
+    ///     feature = "unstable_generic_const_own_type",
+    ///     feature(generic_const_exprs)
+    /// )]
+    ///
+    /// use phantom_newtype::Instant;
+    ///
+    /// enum Seconds {}
+    /// type Time = Instant<Seconds, f64>;
+    ///
+    /// let (takeoff, landing) = (Time::from(100.0), Time::from(400.0));
+    /// assert_eq!(Time::lerp(takeoff, landing, 0.5), Time::from(250.0));
+    /// // Extrapolates, too.
+    /// assert_eq!(takeoff.lerp(landing, -0.5), Time::from(-50.0));
+    /// ```
+    pub fn lerp(self, end: Self, t: f64) -> Self {
+        Self::new(lerp_repr(self.0, end.0, t))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::lerp_repr;
+
+    #[test]
+    fn ends_are_exact_beyond_f64_precision() {
+        let (start, end) = ((1u64 << 53) + 1, (1u64 << 52) + 1);
+        assert_eq!(lerp_repr(start, end, 0.0), start);
+        assert_eq!(lerp_repr(start, end, -0.0), start);
+        assert_eq!(lerp_repr(start, end, 1.0), end);
+        assert_eq!(lerp_repr(u128::MAX, 0, 0.0), u128::MAX);
+    }
+}
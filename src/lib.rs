@@ -27,31 +27,219 @@
     feature(generic_const_exprs)
 )]
 #![cfg_attr(feature = "unstable_transmute_unchecked", feature(core_intrinsics))]
+#![cfg_attr(feature = "unstable_simd", feature(portable_simd))]
 
 //#![feature(unsized_const_params)] // https://github.com/rust-lang/rust/issues/95174
 
 mod amount;
+#[cfg(feature = "arena")]
+pub mod arena;
+#[cfg(feature = "atomics")]
+mod atomic;
+mod backoff;
+mod bounded;
+#[cfg(feature = "candid")]
+mod candid_type;
+#[cfg(feature = "ciborium")]
+pub mod cbor_tag;
+pub mod clock;
+mod convert;
+#[cfg(feature = "defmt")]
+mod defmt_format;
+#[cfg(feature = "diesel")]
+mod diesel_type;
 mod displayer;
+mod error_code;
+mod expr;
+mod flags;
+mod format;
+mod fraction;
 mod id;
+#[cfg(feature = "std")]
+mod id_map;
+mod index;
 mod instant;
+mod iter;
+pub mod layout;
+#[cfg(feature = "lerp")]
+mod lerp;
+#[cfg(feature = "equivalent")]
+pub mod lookup;
+mod matching;
+#[cfg(feature = "metrics")]
+pub mod metrics;
+#[cfg(feature = "money")]
+pub mod money;
+mod mutate;
+mod newtype;
+#[cfg(feature = "utoipa")]
+mod openapi;
+pub mod ops;
+#[cfg(feature = "rayon")]
+mod parallel;
+mod parse;
+#[cfg(feature = "bytemuck")]
+mod pod;
 pub mod prelude;
 pub mod prelude_full;
+#[cfg(feature = "primitives")]
+pub mod primitives;
+#[cfg(feature = "prost")]
+pub mod proto;
+#[cfg(feature = "rand")]
+mod random;
+#[cfg(feature = "redis")]
+mod redis_value;
+pub mod repr;
+#[cfg(feature = "schemars")]
+mod schema;
+mod sealed;
+#[cfg(feature = "serde")]
+pub mod serde_fixed;
+#[cfg(feature = "serde")]
+pub mod serde_unit;
+pub mod short_hash;
+#[cfg(feature = "unstable_simd")]
+mod simd;
+mod slice;
+mod sortable;
+#[cfg(feature = "sqlx")]
+mod sqlx_type;
+#[cfg(feature = "stats")]
+pub mod stats;
+pub mod time;
 mod to;
+mod tuple;
+mod typed_index;
+mod unit_key;
+pub mod validate;
+#[cfg(feature = "valuable")]
+mod valuable_value;
+mod variance;
+pub mod wire;
+#[cfg(feature = "zeroize")]
+mod zeroizing;
 
-#[cfg(not(feature = "unstable_generic_const_own_type"))]
-mod trait_flag;
-#[cfg(feature = "unstable_generic_const_own_type")]
 pub mod trait_flag;
 
-//#[cfg(feature = "alloc")]
-//extern crate alloc;
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
 
-pub use displayer::{DisplayProxy, DisplayerOf};
+pub use amount::{Product, Scalar, Squared, UnitPow};
+#[cfg(feature = "atomics")]
+pub use atomic::{AtomicAmount, AtomicId, AtomicInstant, AtomicRepr, IdGenerator};
+pub use backoff::Backoff;
+pub use bounded::BoundedAmount;
+#[doc(hidden)]
+pub use displayer::{
+    debug_with_name as __debug_with_name, display_with_symbol as __display_with_symbol,
+};
+pub use displayer::{
+    DebugProxy, DebugerOf, DisplayProxy, DisplayerOf, GroupedDisplay, UnitLabel, WithDisplayer,
+};
+pub use error_code::ErrorCode;
+pub use flags::{FlagNames, TypedFlags};
+pub use fraction::Fraction;
+pub use id::SubEntityOf;
+#[cfg(feature = "std")]
+pub use id_map::{IdMap, IdSet};
+pub use iter::{AmountIteratorExt, Deltas, InstantIteratorExt, Reconstruct};
+pub use matching::ComparableWithRepr;
+pub use newtype::Newtype;
+#[cfg(feature = "rayon")]
+pub use parallel::AmountParallelSliceExt;
+/// Implements [DisplayerOf] for amounts, ids and instants (with any trait flags) of a marker type,
+/// formatting their `Repr` with `#[newtype_display(format = "...")]`. Requires feature `derive`.
+///
+/// ```
+/// #![cfg_attr(
+///     feature = "unstable_generic_const_own_type",
+///     feature(generic_const_exprs)
+/// )]
+///
+/// use phantom_newtype::{Amount, Id, NewtypeDisplay};
+///
+/// #[derive(NewtypeDisplay)]
+/// #[newtype_display(format = "{} m")]
+/// enum Meters {}
+///
+/// assert_eq!(Amount::<Meters, u32>::from(5).display().to_string(), "5 m");
+/// assert_eq!(Id::<Meters, u8>::from(1).display().to_string(), "1 m");
+/// ```
+#[cfg(feature = "derive")]
+pub use phantom_newtype_derive::NewtypeDisplay;
+/// Implements [validate::ValidatorOf] for amounts, ids and instants (with any trait flags) of a
+/// marker type, with the `Repr` set by `#[newtype_validate(repr = "...")]`. They are checked
+/// against `min = ...` and `max = ...` (inclusive), and then by `with = "..."`, a function of
+/// `&Repr`. Requires feature `derive`.
+///
+/// ```
+/// #![cfg_attr(
+///     feature = "unstable_generic_const_own_type",
+///     feature(generic_const_exprs)
+/// )]
+///
+/// use phantom_newtype::validate::{StaticError, ValidationError};
+/// use phantom_newtype::{Amount, NewtypeValidate};
+///
+/// const ODD: StaticError = StaticError::new(1, "odd number of halves");
+///
+/// fn even(halves: &u8) -> Result<(), ValidationError> {
+///     if halves % 2 == 0 {
+///         Ok(())
+///     } else {
+///         Err(ODD.into())
+///     }
+/// }
+///
+/// #[derive(NewtypeValidate)]
+/// #[newtype_validate(repr = "u8", min = 2, max = 100, with = "even")]
+/// enum Halves {}
+///
+/// type Portion = Amount<Halves, u8>;
+///
+/// assert_eq!(Portion::try_new(42), Ok(Portion::new(42)));
+/// assert_eq!(Portion::try_new(0), Err(ValidationError::TooSmall));
+/// assert_eq!(Portion::try_new(102), Err(ValidationError::TooLarge));
+/// assert_eq!(Portion::try_new(41), Err(ValidationError::Custom(ODD)));
+/// ```
+#[cfg(feature = "derive")]
+pub use phantom_newtype_derive::NewtypeValidate;
+/// Implements [UnitLabel] for a unit marker, like [unit_label!]: the symbol is set by
+/// `#[unit_label(symbol = "...")]`, and the name is the marker's name, unless set by
+/// `#[unit_label(name = "...")]`. Requires feature `derive`.
+///
+/// ```
+/// #![cfg_attr(
+///     feature = "unstable_generic_const_own_type",
+///     feature(generic_const_exprs)
+/// )]
+///
+/// use phantom_newtype::{Amount, UnitLabel};
+///
+/// #[derive(UnitLabel)]
+/// #[unit_label(symbol = "ms")]
+/// enum Milliseconds {}
+///
+/// let timeout = Amount::<Milliseconds, u32>::from(42);
+/// assert_eq!(timeout.display().to_string(), "42 ms");
+/// assert_eq!(format!("{:?}", timeout.debug()), "Amount<Milliseconds>(42)");
+/// ```
+#[cfg(feature = "derive")]
+pub use phantom_newtype_derive::UnitLabel;
 
 #[cfg(feature = "unstable_generic_const_own_type")]
 pub use id::Id as IdForFlags;
 
-pub use to::{As, AsFrom, AsFromMut, AsMut, To, ToFrom, ToFromMut, ToMut};
+#[cfg(feature = "alloc")]
+pub use typed_index::TypedVec;
+pub use typed_index::{TypedIndex, TypedSlice};
+pub use unit_key::UnitKey;
+pub use variance::Invariant;
+
+pub use to::{As, AsFrom, AsFromMut, AsMut, CastUnit, To, ToFrom, ToFromMut, ToMut};
 
 // Short names. Also in mod prelude:
 #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
@@ -70,6 +258,20 @@ pub type IdIsCopyIsDefault<Unit, Repr> = Id<Unit, Repr>;
 pub type IdIsCopyNoDefault<Unit, Repr> = IdNoDefault<Unit, Repr>;
 pub type IdNoCopyIsDefault<Unit, Repr> = IdNoCopy<Unit, Repr>;
 
+/// `IdRedacted<ApiKey, String>` is `Id<ApiKey, Redacted<String>>`: its `Debug` and `Display` print
+/// `<redacted ApiKey>` rather than the secret, so it can't end up in logs by accident. See
+/// [repr::Redacted].
+pub type IdRedacted<Entity, Repr> = Id<Entity, repr::Redacted<Repr>>;
+
+/// `IdCtEq<Session, [u8; 32]>` is `Id<Session, CtEq<[u8; 32]>>`: its `==` is constant-time, for
+/// comparing tokens and credentials. See [repr::CtEq]. Requires feature `subtle`.
+#[cfg(feature = "subtle")]
+pub type IdCtEq<Entity, Repr> = Id<Entity, repr::CtEq<Repr>>;
+
+/// An [Id] whose `Repr` is the `core::num::NonZero*` counterpart of `Primitive`, for example
+/// `NonZeroId<User, u64>` is `Id<User, NonZeroU64>`. `Option` of it is as big as `Primitive`.
+pub type NonZeroId<Entity, Primitive> = Id<Entity, <Primitive as repr::HasNonZero>::NonZero>;
+
 #[cfg(feature = "unstable_generic_const_own_type")]
 pub use amount::Amount as AmountForFlags;
 
@@ -91,6 +293,10 @@ pub type AmountIsCopyIsDefault<Unit, Repr> = Amount<Unit, Repr>;
 pub type AmountIsCopyNoDefault<Unit, Repr> = AmountNoDefault<Unit, Repr>;
 pub type AmountNoCopyIsDefault<Unit, Repr> = AmountNoCopy<Unit, Repr>;
 
+/// `AmountTotalOrd<Celsius, f64>` is `Amount<Celsius, TotalOrd<f64>>`: `Eq` and `Ord`, so it can be
+/// sorted and be a `BTreeMap` key. See [repr::TotalOrd].
+pub type AmountTotalOrd<Unit, Float> = Amount<Unit, repr::TotalOrd<Float>>;
+
 #[cfg(feature = "unstable_generic_const_own_type")]
 pub use instant::Instant as InstantForFlags;
 
@@ -0,0 +1,124 @@
+// Copyright 2024 Peter Lyons Kehl
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! `equivalent::Equivalent` and `equivalent::Comparable` between [crate::Amount]s, [crate::Id]s
+//! or [crate::Instant]s and their `Repr`, in both directions. Requires feature `equivalent`.
+//!
+//! Collections built on the `equivalent` crate (like `hashbrown` and `indexmap`) can then look up
+//! typed keys by raw values, and raw keys by typed values, without converting the keys. (`std`
+//! maps use `Borrow` instead. Of the wrappers, only [crate::Id] implements `Borrow<Repr>`.)
+//!
+//! Because of coherence rules, these are implemented for primitive `Repr`s (and, with feature
+//! `alloc`, for `Id`s with `String` `Repr` looked up by `str` or `String`) rather than for any
+//! `Repr`.
+//!
+//! ```
+//! #![cfg_attr(
+//!     feature = "unstable_generic_const_own_type",
+//!     feature(generic_const_exprs)
+//! )]
+//!
+//! use equivalent::{Comparable, Equivalent};
+//! use phantom_newtype::Id;
+//! use std::cmp::Ordering;
+//!
+//! enum User {}
+//! type UserId = Id<User, u64>;
+//!
+//! assert!(5u64.equivalent(&UserId::from(5)));
+//! assert!(UserId::from(5).equivalent(&5u64));
+//! assert_eq!(4u64.compare(&UserId::from(5)), Ordering::Less);
+//! ```
+
+use crate::amount::Amount;
+use crate::id::Id;
+use crate::instant::Instant;
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+use crate::trait_flag::TraitFlags;
+use core::cmp::Ordering;
+use equivalent::{Comparable, Equivalent};
+
+// Each wrapper comes with an expression getting a reference to the `Repr`.
+macro_rules! impl_equivalent {
+    ($wrapper:ident($value:ident => $get:expr), $repr:ty, $query:ty) => {
+        #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+        impl<const TF: TraitFlags, Unit> Equivalent<$wrapper<TF, Unit, $repr>> for $query {
+            fn equivalent(&self, $value: &$wrapper<TF, Unit, $repr>) -> bool {
+                <$repr as core::borrow::Borrow<$query>>::borrow($get) == self
+            }
+        }
+
+        #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+        impl<const TF: TraitFlags, Unit> Comparable<$wrapper<TF, Unit, $repr>> for $query {
+            fn compare(&self, $value: &$wrapper<TF, Unit, $repr>) -> Ordering {
+                self.cmp(<$repr as core::borrow::Borrow<$query>>::borrow($get))
+            }
+        }
+    };
+}
+
+// Looking up raw keys by typed values.
+macro_rules! impl_wrapper_query {
+    ($wrapper:ident($value:ident => $get:expr), $($repr:ty)*) => {
+        $(
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<const TF: TraitFlags, Unit> Equivalent<$repr> for $wrapper<TF, Unit, $repr> {
+                fn equivalent(&self, key: &$repr) -> bool {
+                    let $value = self;
+                    $get == key
+                }
+            }
+
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<const TF: TraitFlags, Unit> Comparable<$repr> for $wrapper<TF, Unit, $repr> {
+                fn compare(&self, key: &$repr) -> Ordering {
+                    let $value = self;
+                    $get.cmp(key)
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_equivalent_both_ways {
+    ($wrapper:ident($value:ident => $get:expr), $($repr:ty)*) => {
+        $(
+            impl_equivalent!($wrapper($value => $get), $repr, $repr);
+            impl_wrapper_query!($wrapper($value => $get), $repr);
+        )*
+    };
+}
+
+macro_rules! impl_for_primitives {
+    ($($impl:ident!($wrapper:ident($value:ident => $get:expr));)*) => {
+        $(
+            $impl!(
+                $wrapper($value => $get),
+                u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize bool char
+            );
+        )*
+    };
+}
+// `Id` implements `Borrow<Repr>`, so looking up ids by `Repr` comes from `equivalent`'s blanket
+// impls.
+impl_for_primitives! {
+    impl_equivalent_both_ways!(Amount(value => value.get()));
+    impl_wrapper_query!(Id(value => value.get()));
+    impl_equivalent_both_ways!(Instant(value => value.get()));
+}
+
+#[cfg(feature = "alloc")]
+impl_wrapper_query!(Id(value => value.get()), alloc::string::String);
+#[cfg(feature = "alloc")]
+impl_equivalent!(Id(value => value.get()), alloc::string::String, str);
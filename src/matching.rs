@@ -0,0 +1,149 @@
+// Copyright 2024 Peter Lyons Kehl
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Comparing [Amount]s, [Id]s and [Instant]s with plain `Repr` values, for match guards (the
+//! wrappers' constructors can't be patterns) and, opted into by [ComparableWithRepr], by operators.
+//! And [Amount::ZERO] and [Instant::ZERO] constants.
+//!
+//! ```
+//! #![cfg_attr(
+//!     feature = "unstable_generic_const_own_type",
+//!     feature(generic_const_exprs)
+//! )]
+//!
+//! use core::cmp::Ordering;
+//! use phantom_newtype::{Amount, Id};
+//!
+//! enum User {}
+//! enum Cents {}
+//! type UserId = Id<User, u64>;
+//! type Balance = Amount<Cents, i64>;
+//!
+//! fn describe(user: UserId, balance: Balance) -> &'static str {
+//!     match (user, balance) {
+//!         (user, _) if user.is(&0) => "root",
+//!         (_, balance) if balance == Balance::ZERO => "empty",
+//!         (_, balance) if balance.cmp_repr(&10_000) == Ordering::Greater => "rich",
+//!         (_, balance) if balance.eq_repr(&-1) => "overdrawn by a cent",
+//!         _ => "regular",
+//!     }
+//! }
+//!
+//! assert_eq!(describe(UserId::from(0), Balance::from(5)), "root");
+//! assert_eq!(describe(UserId::from(7), Balance::from(0)), "empty");
+//! assert_eq!(describe(UserId::from(7), Balance::from(20_000)), "rich");
+//! assert_eq!(describe(UserId::from(7), Balance::from(-1)), "overdrawn by a cent");
+//! assert_eq!(describe(UserId::from(7), Balance::from(5)), "regular");
+//! ```
+
+use crate::amount::Amount;
+use crate::id::Id;
+use crate::instant::Instant;
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+use crate::trait_flag::TraitFlags;
+use core::cmp::Ordering;
+
+macro_rules! impl_repr_cmp {
+    ($($wrapper:ident)*) => {
+        $(
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<const TF: TraitFlags, Unit, Repr> $wrapper<TF, Unit, Repr> {
+                /// Whether the wrapped value equals `repr`.
+                pub fn is(&self, repr: &Repr) -> bool
+                where
+                    Repr: PartialEq,
+                {
+                    self.0 == *repr
+                }
+
+                /// Like [Self::is], but `other` may be of any type that `Repr` compares with (like
+                /// `str` for a `String` `Repr`).
+                pub fn eq_repr<Other: ?Sized>(&self, other: &Other) -> bool
+                where
+                    Repr: PartialEq<Other>,
+                {
+                    self.0 == *other
+                }
+
+                /// Order of the wrapped value relative to `repr`.
+                pub fn cmp_repr(&self, repr: &Repr) -> Ordering
+                where
+                    Repr: Ord,
+                {
+                    self.0.cmp(repr)
+                }
+            }
+        )*
+    };
+}
+impl_repr_cmp!(Amount Id Instant);
+
+/// Implement for a unit (or entity) marker type to let its [Amount]s, [Id]s and [Instant]s be
+/// compared with a bare `Repr` (with `==`, `<` and so on), like `assert_eq!(height, 42)`.
+///
+/// Opt-in, because comparing only typed values is often the point. Only the wrapper can be on
+/// the left: `42 == height` doesn't compile.
+///
+/// ```
+/// #![cfg_attr(
+///     feature = "unstable_generic_const_own_type",
+///     feature(generic_const_exprs)
+/// )]
+///
+/// use phantom_newtype::{Amount, ComparableWithRepr};
+///
+/// enum Meters {}
+/// impl ComparableWithRepr for Meters {}
+///
+/// let height = Amount::<Meters, u32>::from(42);
+/// assert_eq!(height, 42);
+/// assert!(height < 100);
+/// ```
+///
+/// Units that don't opt in compare only with typed values:
+///
+/// ```compile_fail
+/// use phantom_newtype::Amount;
+///
+/// enum Meters {}
+///
+/// let height = Amount::<Meters, u32>::from(42);
+/// assert_eq!(height, 42);
+/// ```
+pub trait ComparableWithRepr {}
+
+macro_rules! impl_repr_cmp_ops {
+    ($($wrapper:ident)*) => {
+        $(
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<const TF: TraitFlags, Unit: ComparableWithRepr, Repr: PartialEq> PartialEq<Repr>
+                for $wrapper<TF, Unit, Repr>
+            {
+                fn eq(&self, rhs: &Repr) -> bool {
+                    self.0.eq(rhs)
+                }
+            }
+
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<const TF: TraitFlags, Unit: ComparableWithRepr, Repr: PartialOrd> PartialOrd<Repr>
+                for $wrapper<TF, Unit, Repr>
+            {
+                fn partial_cmp(&self, rhs: &Repr) -> Option<Ordering> {
+                    self.0.partial_cmp(rhs)
+                }
+            }
+        )*
+    };
+}
+impl_repr_cmp_ops!(Amount Id Instant);
@@ -0,0 +1,161 @@
+// Copyright 2024 Peter Lyons Kehl
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Recording [Amount]s into `metrics` counters, gauges and histograms without unwrapping them.
+//! Requires feature `metrics`.
+//!
+//! Amounts of primitive numeric `Repr` are `metrics::IntoF64`, so `Gauge::set`, `Gauge::increment`
+//! and `Histogram::record` take them directly. Counters take `u64`, hence
+//! [AmountCounterExt::increment_amount] and [AmountCounterExt::absolute_amount].
+//!
+//! ```
+//! #![cfg_attr(
+//!     feature = "unstable_generic_const_own_type",
+//!     feature(generic_const_exprs)
+//! )]
+//!
+//! use metrics::{Counter, Gauge};
+//! use phantom_newtype::metrics::AmountCounterExt;
+//! use phantom_newtype::Amount;
+//! use std::sync::atomic::{AtomicU64, Ordering};
+//! use std::sync::Arc;
+//!
+//! enum Bytes {}
+//! enum Celsius {}
+//!
+//! let sent = Arc::new(AtomicU64::new(0));
+//! let counter = Counter::from_arc(sent.clone());
+//! counter.increment_amount(Amount::<Bytes, u64>::new(1500));
+//! counter.increment_amount(Amount::<Bytes, u64>::new(500));
+//! assert_eq!(sent.load(Ordering::Relaxed), 2000);
+//!
+//! let temperature = Arc::new(AtomicU64::new(0));
+//! let gauge = Gauge::from_arc(temperature.clone());
+//! gauge.set(Amount::<Celsius, f64>::new(21.5));
+//! assert_eq!(f64::from_bits(temperature.load(Ordering::Relaxed)), 21.5);
+//!
+//! // With no recorder installed, this records nothing - but it compiles.
+//! metrics::histogram!("payload_size").record(Amount::<Bytes, u32>::new(64));
+//! ```
+
+use crate::amount::Amount;
+use crate::displayer::UnitLabel;
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+use crate::trait_flag::TraitFlags;
+use ::metrics::{Counter, IntoF64};
+
+macro_rules! impl_into_f64 {
+    ($($t:ty)*) => {
+        $(
+            /// Like `Repr as f64`: 64-bit integers beyond 2^53 lose precision.
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<const TF: TraitFlags, Unit> IntoF64 for Amount<TF, Unit, $t> {
+                fn into_f64(self) -> f64 {
+                    self.0 as f64
+                }
+            }
+        )*
+    };
+}
+impl_into_f64!(u8 u16 u32 u64 usize i8 i16 i32 i64 isize f32 f64);
+
+/// Recording `u64` [Amount]s into a `metrics::Counter`.
+pub trait AmountCounterExt {
+    /// Increment the counter by `amount`.
+    #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+    fn increment_amount<const TF: TraitFlags, Unit>(&self, amount: Amount<TF, Unit, u64>);
+
+    /// Set the counter to `amount`, unless it's already higher.
+    #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+    fn absolute_amount<const TF: TraitFlags, Unit>(&self, amount: Amount<TF, Unit, u64>);
+}
+
+impl AmountCounterExt for Counter {
+    #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+    fn increment_amount<const TF: TraitFlags, Unit>(&self, amount: Amount<TF, Unit, u64>) {
+        self.increment(amount.0)
+    }
+
+    #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+    fn absolute_amount<const TF: TraitFlags, Unit>(&self, amount: Amount<TF, Unit, u64>) {
+        self.absolute(amount.0)
+    }
+}
+
+/// The `metrics::Unit` of `Unit`, by its [UnitLabel]: its `SYMBOL` matching a unit's canonical
+/// label (like `ms` or `B`), or else its `NAME` matching a unit's name, ignoring case and
+/// underscores (like `Milliseconds` or `BitsPerSecond`). `None` if `metrics` has no such unit.
+///
+/// ```
+/// #![cfg_attr(
+///     feature = "unstable_generic_const_own_type",
+///     feature(generic_const_exprs)
+/// )]
+///
+/// use phantom_newtype::metrics::metric_unit;
+///
+/// enum Millis {}
+/// enum BitsPerSecond {}
+/// enum Widgets {}
+/// phantom_newtype::unit_label! {
+///     Millis { symbol: "ms", name: "Millis" }
+///     BitsPerSecond { symbol: "b/s", name: "BitsPerSecond" }
+///     Widgets { symbol: "w", name: "Widgets" }
+/// }
+///
+/// assert_eq!(metric_unit::<Millis>(), Some(metrics::Unit::Milliseconds));
+/// let unit = metric_unit::<BitsPerSecond>();
+/// assert_eq!(unit, Some(metrics::Unit::BitsPerSecond));
+/// assert_eq!(metric_unit::<Widgets>(), None);
+///
+/// metrics::describe_gauge!("throughput", unit.unwrap(), "Current throughput");
+/// ```
+pub fn metric_unit<Unit: UnitLabel>() -> Option<::metrics::Unit> {
+    use ::metrics::Unit::*;
+    const UNITS: [::metrics::Unit; 17] = [
+        Count,
+        Percent,
+        Seconds,
+        Milliseconds,
+        Microseconds,
+        Nanoseconds,
+        Tebibytes,
+        Gibibytes,
+        Mebibytes,
+        Kibibytes,
+        Bytes,
+        TerabitsPerSecond,
+        GigabitsPerSecond,
+        MegabitsPerSecond,
+        KilobitsPerSecond,
+        BitsPerSecond,
+        CountPerSecond,
+    ];
+    // `Count` has an empty label.
+    let by_symbol = UNITS
+        .iter()
+        .find(|unit| !Unit::SYMBOL.is_empty() && unit.as_canonical_label() == Unit::SYMBOL);
+    let letters = |name: &'static str| {
+        name.chars()
+            .filter(|&c| c != '_')
+            .flat_map(char::to_lowercase)
+    };
+    by_symbol
+        .or_else(|| {
+            UNITS
+                .iter()
+                .find(|unit| letters(unit.as_str()).eq(letters(Unit::NAME)))
+        })
+        .copied()
+}
@@ -0,0 +1,344 @@
+// Copyright 2024 Peter Lyons Kehl
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Currency-tagged amounts. Requires feature `money`.
+//!
+//! [Money] is an [crate::Amount] of a [Currency], kept in the currency's minor units (cents for
+//! [Usd]). All rounding in this module is "banker's rounding" (half to even, see
+//! [round_half_even]), so that every user of this module rounds the same way.
+//!
+//! ```
+//! #![cfg_attr(
+//!     feature = "unstable_generic_const_own_type",
+//!     feature(generic_const_exprs)
+//! )]
+//!
+//! use phantom_newtype::money::{Money, Usd};
+//!
+//! let price = Money::<Usd>::from_major(10).unwrap() + Money::<Usd>::from(5);
+//! assert_eq!(price.get_copy(), 1005);
+//! assert_eq!(format!("{}", price.display()), "$10.05");
+//!
+//! // 10.05 * 7.5% = 0.75375, rounded to 0.75.
+//! let tax = price.mul_ratio(75, 1000).unwrap();
+//! assert_eq!(format!("{}", tax.display()), "$0.75");
+//! ```
+
+use crate::amount::Amount;
+use crate::displayer::DisplayerOf;
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+use crate::trait_flag::TraitFlags;
+use core::convert::TryFrom;
+use core::fmt;
+
+/// A unit marker for money. Implement it for your own marker types to get the functionality of
+/// this module for other currencies.
+///
+/// ```compile_fail
+/// #![cfg_attr(
+///     feature = "unstable_generic_const_own_type",
+///     feature(generic_const_exprs)
+/// )]
+///
+/// use phantom_newtype::money::{Currency, Money};
+///
+/// enum TooFine {}
+/// impl Currency for TooFine {
+///     const CODE: &'static str = "XTF";
+///     const SYMBOL: &'static str = "T";
+///     // 10^19 minor units don't fit in `i64`.
+///     const MINOR_UNITS: u32 = 19;
+/// }
+///
+/// let _ = Money::<TooFine>::from_major(1);
+/// ```
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not a currency",
+    label = "no `Currency` for `{Self}`",
+    note = "money functionality needs `impl Currency for {Self}`"
+)]
+pub trait Currency {
+    /// ISO 4217 code, like `"USD"`.
+    const CODE: &'static str;
+    /// Symbol used by [DisplayerOf] (for [crate::Amount::display]), like `"$"`.
+    const SYMBOL: &'static str;
+    /// Number of decimal digits of the minor unit: `2` for cents, `0` for currencies without a
+    /// minor unit. At most `18`, so that a major unit fits in `i64` minor units (checked at compile
+    /// time, where the functionality of this module is used).
+    const MINOR_UNITS: u32;
+}
+
+/// United States dollar.
+pub enum Usd {}
+impl Currency for Usd {
+    const CODE: &'static str = "USD";
+    const SYMBOL: &'static str = "$";
+    const MINOR_UNITS: u32 = 2;
+}
+
+/// Euro.
+pub enum Eur {}
+impl Currency for Eur {
+    const CODE: &'static str = "EUR";
+    const SYMBOL: &'static str = "€";
+    const MINOR_UNITS: u32 = 2;
+}
+
+/// Pound sterling.
+pub enum Gbp {}
+impl Currency for Gbp {
+    const CODE: &'static str = "GBP";
+    const SYMBOL: &'static str = "£";
+    const MINOR_UNITS: u32 = 2;
+}
+
+/// Japanese yen. It has no minor unit.
+pub enum Jpy {}
+impl Currency for Jpy {
+    const CODE: &'static str = "JPY";
+    const SYMBOL: &'static str = "¥";
+    const MINOR_UNITS: u32 = 0;
+}
+
+/// An amount of currency `C`, in minor units of `C`.
+pub type Money<C> = crate::Amount<C, i64>;
+
+/// Divide `numerator` by `denominator`, rounding half to even ("banker's rounding").
+///
+/// # Panics
+///
+/// If `denominator` is zero, or if the quotient overflows (`i128::MIN / -1`), the same as `/`.
+///
+/// ```
+/// use phantom_newtype::money::round_half_even;
+///
+/// assert_eq!(round_half_even(5, 2), 2);
+/// assert_eq!(round_half_even(7, 2), 4);
+/// assert_eq!(round_half_even(-5, 2), -2);
+/// assert_eq!(round_half_even(-7, 2), -4);
+/// assert_eq!(round_half_even(8, 3), 3);
+/// assert_eq!(round_half_even(7, -3), -2);
+/// ```
+pub const fn round_half_even(numerator: i128, denominator: i128) -> i128 {
+    let quotient = numerator / denominator;
+    let remainder = numerator % denominator;
+    if remainder == 0 {
+        return quotient;
+    }
+    let away_from_zero = if (numerator < 0) == (denominator < 0) {
+        quotient + 1
+    } else {
+        quotient - 1
+    };
+    let twice_remainder = remainder.unsigned_abs() * 2;
+    let denominator = denominator.unsigned_abs();
+    if twice_remainder > denominator || (twice_remainder == denominator && quotient % 2 != 0) {
+        away_from_zero
+    } else {
+        quotient
+    }
+}
+
+struct MinorPerMajor<C>(C);
+
+impl<C: Currency> MinorPerMajor<C> {
+    // Evaluated at compile time, so an out-of-range `MINOR_UNITS` fails to compile rather than
+    // overflowing `pow`.
+    const VALUE: i64 = {
+        assert!(
+            C::MINOR_UNITS <= 18,
+            "Currency::MINOR_UNITS must be at most 18"
+        );
+        10i64.pow(C::MINOR_UNITS)
+    };
+}
+
+fn minor_per_major<C: Currency>() -> i64 {
+    MinorPerMajor::<C>::VALUE
+}
+
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+impl<const TF: TraitFlags, C: Currency> Amount<TF, C, i64> {
+    /// An amount of whole major units (dollars for [Usd]), or `None` on overflow.
+    pub fn from_major(major: i64) -> Option<Self> {
+        major.checked_mul(minor_per_major::<C>()).map(Self::new)
+    }
+
+    /// Convert a decimal number `value * 10^-decimals` (in major units) to minor units, rounding
+    /// half to even. Return `None` on overflow. Any number of `decimals` is accepted: beyond the
+    /// precision of `i128`, the result is zero.
+    ///
+    /// ```
+    /// #![cfg_attr(
+    ///     feature = "unstable_generic_const_own_type",
+    ///     feature(generic_const_exprs)
+    /// )]
+    ///
+    /// use phantom_newtype::money::{Money, Usd};
+    ///
+    /// // $10.005 -> $10.00, but $10.015 -> $10.02.
+    /// assert_eq!(Money::<Usd>::from_decimal(10_005, 3).unwrap().get_copy(), 1000);
+    /// assert_eq!(Money::<Usd>::from_decimal(10_015, 3).unwrap().get_copy(), 1002);
+    /// // $12.5 -> 1250 cents.
+    /// assert_eq!(Money::<Usd>::from_decimal(125, 1).unwrap().get_copy(), 1250);
+    /// ```
+    pub fn from_decimal(value: i128, decimals: u32) -> Option<Self> {
+        let minor = if decimals >= C::MINOR_UNITS {
+            match 10i128.checked_pow(decimals - C::MINOR_UNITS) {
+                Some(divisor) => round_half_even(value, divisor),
+                // The divisor is beyond `i128`, so `value` is less than half of it.
+                None => 0,
+            }
+        } else {
+            value.checked_mul(10i128.checked_pow(C::MINOR_UNITS - decimals)?)?
+        };
+        i64::try_from(minor).ok().map(Self::new)
+    }
+
+    /// Whole major units, truncated toward zero.
+    pub fn major(&self) -> i64 {
+        self.get_copy() / minor_per_major::<C>()
+    }
+
+    /// The minor units left over after [Amount::major]. Negative for negative amounts.
+    pub fn minor_remainder(&self) -> i64 {
+        self.get_copy() % minor_per_major::<C>()
+    }
+
+    /// Multiply by `numerator / denominator`, rounding half to even. Return `None` if
+    /// `denominator` is zero or the result overflows.
+    ///
+    /// ```
+    /// #![cfg_attr(
+    ///     feature = "unstable_generic_const_own_type",
+    ///     feature(generic_const_exprs)
+    /// )]
+    ///
+    /// use phantom_newtype::money::{Money, Eur};
+    ///
+    /// let bill = Money::<Eur>::from(1001);
+    /// // One third of €10.01 is €3.336..., rounded to €3.34.
+    /// assert_eq!(bill.mul_ratio(1, 3).unwrap().get_copy(), 334);
+    /// assert!(bill.mul_ratio(1, 0).is_none());
+    /// ```
+    pub fn mul_ratio(&self, numerator: i64, denominator: i64) -> Option<Self> {
+        if denominator == 0 {
+            return None;
+        }
+        let product = i128::from(self.get_copy()) * i128::from(numerator);
+        let rounded = round_half_even(product, i128::from(denominator));
+        i64::try_from(rounded).ok().map(Self::new)
+    }
+}
+
+/// Display money with the currency symbol and the minor units as decimals, like `-$10.05` or
+/// `¥500`.
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+impl<const TF: TraitFlags, C: Currency> DisplayerOf<Amount<TF, C, i64>> for C {
+    fn display(amount: &Amount<TF, C, i64>, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = amount.get_copy();
+        let sign = if value < 0 { "-" } else { "" };
+        let value = value.unsigned_abs();
+        let minor_per_major = minor_per_major::<C>().unsigned_abs();
+        write!(f, "{}{}{}", sign, C::SYMBOL, value / minor_per_major)?;
+        if C::MINOR_UNITS > 0 {
+            write!(
+                f,
+                ".{:0width$}",
+                value % minor_per_major,
+                width = C::MINOR_UNITS as usize
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    extern crate std;
+    use std::format;
+
+    #[test]
+    fn display() {
+        assert_eq!(format!("{}", Money::<Usd>::from(0).display()), "$0.00");
+        assert_eq!(format!("{}", Money::<Usd>::from(7).display()), "$0.07");
+        assert_eq!(
+            format!("{}", Money::<Usd>::from(-1005).display()),
+            "-$10.05"
+        );
+        assert_eq!(format!("{}", Money::<Jpy>::from(500).display()), "¥500");
+        assert_eq!(
+            format!("{}", Money::<Gbp>::from(i64::MIN).display()),
+            "-£92233720368547758.08"
+        );
+    }
+
+    #[test]
+    fn major_and_minor() {
+        let m = Money::<Eur>::from(-1005);
+        assert_eq!((m.major(), m.minor_remainder()), (-10, -5));
+        assert_eq!(Money::<Jpy>::from_major(3).unwrap().get_copy(), 3);
+        assert!(Money::<Usd>::from_major(i64::MAX).is_none());
+    }
+
+    #[test]
+    fn from_decimal_rounds_half_to_even() {
+        assert_eq!(
+            Money::<Usd>::from_decimal(-10_005, 3).unwrap().get_copy(),
+            -1000
+        );
+        assert_eq!(
+            Money::<Usd>::from_decimal(-10_015, 3).unwrap().get_copy(),
+            -1002
+        );
+        assert_eq!(Money::<Jpy>::from_decimal(25, 1).unwrap().get_copy(), 2);
+        assert_eq!(Money::<Jpy>::from_decimal(35, 1).unwrap().get_copy(), 4);
+        assert!(Money::<Usd>::from_decimal(i128::MAX, 0).is_none());
+    }
+
+    #[test]
+    fn from_decimal_beyond_i128_scale() {
+        let from_decimal = |value, decimals| {
+            Money::<Usd>::from_decimal(value, decimals)
+                .unwrap()
+                .get_copy()
+        };
+        assert_eq!(from_decimal(i128::MAX, 40), 2);
+        assert_eq!(from_decimal(i128::MAX, 41), 0);
+        assert_eq!(from_decimal(i128::MIN, 41), 0);
+        assert_eq!(from_decimal(5, u32::MAX), 0);
+    }
+
+    #[test]
+    fn finest_minor_units() {
+        enum Finest {}
+        impl Currency for Finest {
+            const CODE: &'static str = "XFN";
+            const SYMBOL: &'static str = "F";
+            const MINOR_UNITS: u32 = 18;
+        }
+        assert_eq!(
+            Money::<Finest>::from_major(9).unwrap().get_copy(),
+            9 * 10i64.pow(18)
+        );
+        assert!(Money::<Finest>::from_major(10).is_none());
+    }
+
+    #[test]
+    #[should_panic]
+    fn round_half_even_overflow() {
+        round_half_even(i128::MIN, -1);
+    }
+}
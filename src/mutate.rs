@@ -0,0 +1,86 @@
+// Copyright 2024 Peter Lyons Kehl
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! In-place mutation of the `Repr` of [Amount]s, [Id]s and [Instant]s, with the vocabulary of
+//! `core::cell::Cell` and `core::mem`.
+//!
+//! ```
+//! #![cfg_attr(
+//!     feature = "unstable_generic_const_own_type",
+//!     feature(generic_const_exprs)
+//! )]
+//!
+//! use phantom_newtype::{Amount, Id};
+//!
+//! enum Request {}
+//! enum Bytes {}
+//!
+//! struct Connection {
+//!     in_flight: Id<Request, u32>,
+//!     buffered: Amount<Bytes, usize>,
+//! }
+//!
+//! let mut connection = Connection {
+//!     in_flight: Id::from(7),
+//!     buffered: Amount::from(512),
+//! };
+//!
+//! assert_eq!(connection.in_flight.replace(8), 7);
+//! assert_eq!(connection.buffered.take(), 512);
+//! assert_eq!(connection.buffered, Amount::from(0));
+//! connection.buffered.set(64);
+//! assert_eq!(connection.buffered, Amount::from(64));
+//! *connection.buffered.get_mut() += 1;
+//! assert_eq!(connection.buffered, Amount::from(65));
+//! assert_eq!(connection.in_flight, Id::from(8));
+//! ```
+
+use crate::amount::Amount;
+use crate::id::Id;
+use crate::instant::Instant;
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+use crate::trait_flag::TraitFlags;
+
+macro_rules! impl_mutate {
+    ($($wrapper:ident)*) => {
+        $(
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<const TF: TraitFlags, Unit, Repr> $wrapper<TF, Unit, Repr> {
+                /// A mutable reference to the wrapped value.
+                pub fn get_mut(&mut self) -> &mut Repr {
+                    &mut self.0
+                }
+
+                /// Replace the wrapped value with `repr`, dropping the old one.
+                pub fn set(&mut self, repr: Repr) {
+                    self.0 = repr;
+                }
+
+                /// Replace the wrapped value with `repr`, and return the old one.
+                pub fn replace(&mut self, repr: Repr) -> Repr {
+                    core::mem::replace(&mut self.0, repr)
+                }
+
+                /// Replace the wrapped value with its default, and return the old one.
+                pub fn take(&mut self) -> Repr
+                where
+                    Repr: Default,
+                {
+                    core::mem::take(&mut self.0)
+                }
+            }
+        )*
+    };
+}
+impl_mutate!(Amount Id Instant);
@@ -0,0 +1,94 @@
+// Copyright 2024 Peter Lyons Kehl
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! [Newtype]: one trait over [Amount], [Id] and [Instant], for generic code.
+
+use crate::amount::Amount;
+use crate::id::Id;
+use crate::instant::Instant;
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+use crate::trait_flag::TraitFlags;
+
+/// Any of [Amount], [Id] or [Instant] (with any trait flags), so that a function can accept
+/// "any phantom newtype" without a copy per wrapper.
+///
+/// ```
+/// #![cfg_attr(
+///     feature = "unstable_generic_const_own_type",
+///     feature(generic_const_exprs)
+/// )]
+///
+/// use phantom_newtype::{Amount, Id, Instant, Newtype};
+///
+/// struct Gauge {
+///     last: f64,
+/// }
+///
+/// impl Gauge {
+///     fn record<T: Newtype>(&mut self, value: &T)
+///     where
+///         T::Repr: Copy + Into<f64>,
+///     {
+///         self.last = (*value.get()).into();
+///     }
+/// }
+///
+/// enum Bytes {}
+/// enum Seconds {}
+/// enum Worker {}
+///
+/// let mut gauge = Gauge { last: 0.0 };
+/// gauge.record(&Amount::<Bytes, u32>::from(512));
+/// assert_eq!(gauge.last, 512.0);
+/// gauge.record(&Instant::<Seconds, f32>::from(1.5));
+/// assert_eq!(gauge.last, 1.5);
+/// gauge.record(&Id::<Worker, u8>::from(3));
+/// assert_eq!(gauge.last, 3.0);
+///
+/// fn doubled<T: Newtype<Repr = u32>>(value: &T) -> T {
+///     T::from_repr(value.get() * 2)
+/// }
+/// assert_eq!(doubled(&Amount::<Bytes, u32>::from(512)).get_copy(), 1024);
+/// ```
+pub trait Newtype: Sized {
+    /// The marker type: `Unit` of an [Amount] or [Instant], `Entity` of an [Id].
+    type Tag;
+    /// The wrapped type.
+    type Repr;
+
+    fn get(&self) -> &Self::Repr;
+
+    fn from_repr(repr: Self::Repr) -> Self;
+}
+
+macro_rules! impl_newtype {
+    ($($wrapper:ident)*) => {
+        $(
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<const TF: TraitFlags, Unit, Repr> Newtype for $wrapper<TF, Unit, Repr> {
+                type Tag = Unit;
+                type Repr = Repr;
+
+                fn get(&self) -> &Repr {
+                    $wrapper::get(self)
+                }
+
+                fn from_repr(repr: Repr) -> Self {
+                    Self::new(repr)
+                }
+            }
+        )*
+    };
+}
+impl_newtype!(Amount Id Instant);
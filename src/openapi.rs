@@ -0,0 +1,127 @@
+// Copyright 2024 Peter Lyons Kehl
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! `utoipa::ToSchema` (and `PartialSchema`) of [Amount]s, [Id]s and [Instant]s: the schema of their
+//! `Repr` (as they serialize as `Repr`), titled and named after the unit, like `UserId` for
+//! `Id<User, u64>`. Requires feature `utoipa`.
+//!
+//! In fields of `#[derive(ToSchema)]` types, refer to them through type aliases (like `UserId`
+//! below). The derive treats generic arguments of a field's type as schemas of their own, but unit
+//! markers have none.
+//!
+//! ```
+//! #![cfg_attr(
+//!     feature = "unstable_generic_const_own_type",
+//!     feature(generic_const_exprs)
+//! )]
+//!
+//! use phantom_newtype::{Amount, Id};
+//! use serde_json::json;
+//! use utoipa::{OpenApi, PartialSchema, ToSchema};
+//!
+//! enum User {}
+//! enum Cents {}
+//! type UserId = Id<User, u64>;
+//! type Total = Amount<Cents, u32>;
+//!
+//! #[derive(ToSchema)]
+//! struct Payment {
+//!     payer: UserId,
+//!     total: Total,
+//! }
+//!
+//! assert_eq!(UserId::name(), "UserId");
+//! assert_eq!(
+//!     serde_json::to_value(UserId::schema()).unwrap(),
+//!     json!({"type": "integer", "format": "int64", "minimum": 0, "title": "UserId"})
+//! );
+//!
+//! #[derive(OpenApi)]
+//! #[openapi(components(schemas(Payment)))]
+//! struct ApiDoc;
+//!
+//! let schemas = &serde_json::to_value(ApiDoc::openapi()).unwrap()["components"]["schemas"];
+//! assert_eq!(schemas["Payment"]["properties"]["payer"], json!({"$ref": "#/components/schemas/UserId"}));
+//! assert_eq!(schemas["UserId"]["title"], "UserId");
+//! assert_eq!(schemas["CentsAmount"]["title"], "CentsAmount");
+//! ```
+
+use crate::amount::Amount;
+use crate::displayer::unit_name;
+use crate::id::Id;
+use crate::instant::Instant;
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+use crate::trait_flag::TraitFlags;
+use alloc::borrow::Cow;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use utoipa::openapi::schema::{AllOfBuilder, Schema};
+use utoipa::openapi::RefOr;
+use utoipa::{PartialSchema, ToSchema};
+
+/// The schema of `Repr`, with `title` set. A reference can't be titled, so it's wrapped in an
+/// `allOf` (of one).
+fn titled(repr: RefOr<Schema>, title: String) -> RefOr<Schema> {
+    match repr {
+        RefOr::T(mut schema) => {
+            match &mut schema {
+                Schema::Object(object) => object.title = Some(title),
+                Schema::Array(array) => array.title = Some(title),
+                Schema::OneOf(one_of) => one_of.title = Some(title),
+                Schema::AllOf(all_of) => all_of.title = Some(title),
+                _ => {}
+            }
+            RefOr::T(schema)
+        }
+        reference => AllOfBuilder::new()
+            .item(reference)
+            .title(Some(title))
+            .into(),
+    }
+}
+
+macro_rules! impl_to_schema {
+    ($($wrapper:ident)*) => {
+        $(
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<const TF: TraitFlags, Unit, Repr> PartialSchema for $wrapper<TF, Unit, Repr>
+            where
+                Repr: PartialSchema,
+            {
+                fn schema() -> RefOr<Schema> {
+                    titled(
+                        Repr::schema(),
+                        format!("{}{}", unit_name::<Unit>(), stringify!($wrapper)),
+                    )
+                }
+            }
+
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<const TF: TraitFlags, Unit, Repr> ToSchema for $wrapper<TF, Unit, Repr>
+            where
+                Repr: ToSchema,
+            {
+                fn name() -> Cow<'static, str> {
+                    format!("{}{}", unit_name::<Unit>(), stringify!($wrapper)).into()
+                }
+
+                fn schemas(schemas: &mut Vec<(String, RefOr<Schema>)>) {
+                    Repr::schemas(schemas);
+                }
+            }
+        )*
+    };
+}
+impl_to_schema!(Amount Id Instant);
@@ -0,0 +1,108 @@
+// Copyright 2024 Peter Lyons Kehl
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Arithmetic over slices of [Amount]s, keeping the units, so that numeric code doesn't have to
+//! strip them. The loops are plain, so that the compiler can vectorize them.
+//!
+//! Also [AddTo] and [SubFrom], through which `+` and `-` of amounts and instants are implemented,
+//! so that mixing units reports that in plain language.
+//!
+//! ```
+//! #![cfg_attr(
+//!     feature = "unstable_generic_const_own_type",
+//!     feature(generic_const_exprs)
+//! )]
+//!
+//! use phantom_newtype::{ops, Amount, Product};
+//!
+//! enum Cycles {}
+//! enum Calls {}
+//! type CyclesPerCall = Amount<Cycles, u64>;
+//! type CallCount = Amount<Calls, u64>;
+//!
+//! let mut costs = [10, 20, 30].map(CyclesPerCall::from);
+//! let counts = [4, 2, 1].map(CallCount::from);
+//! let total: Amount<Product<Cycles, Calls>, u64> = ops::dot(&costs, &counts);
+//! assert_eq!(total.get_copy(), 110);
+//!
+//! ops::scale_slice(&mut costs, 3);
+//! assert_eq!(costs, [30, 60, 90].map(CyclesPerCall::from));
+//! ```
+
+use crate::amount::{Amount, Product};
+#[cfg(doc)]
+use crate::instant::Instant;
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+use crate::trait_flag::TraitFlags;
+use core::ops::{Add, Mul, MulAssign};
+
+/// The right-hand side of `+` on `Lhs`, an [Amount] or an [Instant]: an amount of the same unit
+/// (and trait flags), or, on an amount, an instant of the same unit. `Lhs + Self` is
+/// `self.add_to(lhs)`.
+#[diagnostic::on_unimplemented(
+    message = "cannot add `{Self}` to `{Lhs}`",
+    label = "not an amount of the same unit as `{Lhs}`",
+    note = "amounts and instants add up only with amounts of the same unit (and trait flags); convert one of them (see `To`) to mix units"
+)]
+pub trait AddTo<Lhs> {
+    type Output;
+    fn add_to(self, lhs: Lhs) -> Self::Output;
+}
+
+/// The right-hand side of `-` on `Lhs`, an [Amount] or an [Instant]: an amount of the same unit
+/// (and trait flags), or, on an instant, an instant of the same unit. `Lhs - Self` is
+/// `self.sub_from(lhs)`.
+#[diagnostic::on_unimplemented(
+    message = "cannot subtract `{Self}` from `{Lhs}`",
+    label = "not an amount (or instant) of the same unit as `{Lhs}`",
+    note = "amounts subtract only amounts, and instants subtract amounts or instants, of the same unit (and trait flags); convert one of them (see `To`) to mix units"
+)]
+pub trait SubFrom<Lhs> {
+    type Output;
+    fn sub_from(self, lhs: Lhs) -> Self::Output;
+}
+
+/// Sum of products of the respective amounts of `a` and `b` (zero, that is `Repr::default()`, if
+/// they are empty).
+///
+/// # Panics
+/// If `a` and `b` have different lengths.
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+pub fn dot<const TF: TraitFlags, U, V, Repr>(
+    a: &[Amount<TF, U, Repr>],
+    b: &[Amount<TF, V, Repr>],
+) -> Amount<TF, Product<U, V>, Repr>
+where
+    Repr: Add<Output = Repr> + Mul<Output = Repr> + Default + Copy,
+{
+    assert_eq!(a.len(), b.len(), "dot of slices of different lengths");
+    let sum = a
+        .iter()
+        .zip(b)
+        .fold(Repr::default(), |sum, (a, b)| sum + a.0 * b.0);
+    Amount::new(sum)
+}
+
+/// Multiplies each amount by `factor`, in place.
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+pub fn scale_slice<const TF: TraitFlags, Unit, Repr>(
+    amounts: &mut [Amount<TF, Unit, Repr>],
+    factor: Repr,
+) where
+    Repr: MulAssign + Copy,
+{
+    for amount in amounts {
+        amount.0 *= factor;
+    }
+}
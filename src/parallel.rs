@@ -0,0 +1,69 @@
+// Copyright 2024 Peter Lyons Kehl
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Parallel aggregation of [Amount]s with `rayon`. Requires feature `rayon`.
+//!
+//! The wrappers are `Send` and `Sync` whenever their `Repr` is, so `rayon`'s own
+//! `IntoParallelIterator`, `FromParallelIterator` and `ParallelExtend` for slices and collections
+//! work with them as they are. [AmountParallelSliceExt::par_sum] adds the missing sum.
+//!
+//! ```
+//! #![cfg_attr(
+//!     feature = "unstable_generic_const_own_type",
+//!     feature(generic_const_exprs)
+//! )]
+//!
+//! use phantom_newtype::{Amount, AmountParallelSliceExt};
+//! use rayon::prelude::*;
+//!
+//! enum Cents {}
+//! type Payment = Amount<Cents, u64>;
+//!
+//! let payments: Vec<Payment> = (1..=1_000u64).into_par_iter().map(Payment::from).collect();
+//! assert_eq!(payments.par_sum(), Payment::from(500_500));
+//!
+//! let mut refunds: Vec<Payment> = Vec::new();
+//! refunds.par_extend(payments.par_iter().filter(|p| p.get_copy() % 100 == 0).copied());
+//! assert_eq!(refunds.par_sum(), Payment::from(5_500));
+//! ```
+
+use crate::amount::Amount;
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+use crate::trait_flag::TraitFlags;
+use core::ops::Add;
+use rayon::prelude::*;
+
+/// Parallel aggregation of slices of [Amount]s.
+pub trait AmountParallelSliceExt {
+    type Amount;
+
+    /// Sum of the amounts, computed in parallel (zero, that is `Repr::default()`, if empty).
+    fn par_sum(&self) -> Self::Amount;
+}
+
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+impl<const TF: TraitFlags, Unit, Repr> AmountParallelSliceExt for [Amount<TF, Unit, Repr>]
+where
+    Repr: Add<Output = Repr> + Default + Copy + Send + Sync,
+{
+    type Amount = Amount<TF, Unit, Repr>;
+
+    fn par_sum(&self) -> Amount<TF, Unit, Repr> {
+        Amount::new(
+            self.par_iter()
+                .map(|amount| amount.0)
+                .reduce(Repr::default, Add::add),
+        )
+    }
+}
@@ -0,0 +1,76 @@
+// Copyright 2024 Peter Lyons Kehl
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Parsing [Amount]s, [Id]s and [Instant]s of primitive integers from strings in a radix (like
+//! hexadecimal block heights or binary flag fields), with `core`'s own [ParseIntError].
+//!
+//! ```
+//! #![cfg_attr(
+//!     feature = "unstable_generic_const_own_type",
+//!     feature(generic_const_exprs)
+//! )]
+//!
+//! use phantom_newtype::{Amount, Id, Instant};
+//!
+//! enum Block {}
+//! enum Flags {}
+//! enum Wei {}
+//!
+//! let height = Instant::<Block, u64>::from_hex_str("1f4").unwrap();
+//! assert_eq!(height.get_copy(), 500);
+//! let flags = Id::<Flags, u8>::from_bin_str("1010").unwrap();
+//! assert_eq!(flags.get_copy(), 0b1010);
+//! let balance = Amount::<Wei, i32>::from_str_radix("-z", 36).unwrap();
+//! assert_eq!(balance.get_copy(), -35);
+//!
+//! let error = Id::<Flags, u8>::from_bin_str("102").unwrap_err();
+//! assert_eq!(error, u8::from_str_radix("102", 2).unwrap_err());
+//! ```
+
+use crate::amount::Amount;
+use crate::id::Id;
+use crate::instant::Instant;
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+use crate::trait_flag::TraitFlags;
+use core::num::ParseIntError;
+
+macro_rules! impl_from_str_radix {
+    ($($wrapper:ident)*; $ints:tt) => {
+        $(
+            impl_from_str_radix!(@ints $wrapper $ints);
+        )*
+    };
+    (@ints $wrapper:ident ($($int:ident)*)) => {
+        $(
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<const TF: TraitFlags, Unit> $wrapper<TF, Unit, $int> {
+                #[doc = concat!("Parse `src` in `radix`, as per [", stringify!($int), "::from_str_radix].")]
+                pub fn from_str_radix(src: &str, radix: u32) -> Result<Self, ParseIntError> {
+                    <$int>::from_str_radix(src, radix).map(Self::new)
+                }
+
+                /// Parse hexadecimal `src` (without any `0x` prefix).
+                pub fn from_hex_str(src: &str) -> Result<Self, ParseIntError> {
+                    Self::from_str_radix(src, 16)
+                }
+
+                /// Parse binary `src` (without any `0b` prefix).
+                pub fn from_bin_str(src: &str) -> Result<Self, ParseIntError> {
+                    Self::from_str_radix(src, 2)
+                }
+            }
+        )*
+    };
+}
+impl_from_str_radix!(Amount Id Instant; (u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize));
@@ -0,0 +1,57 @@
+// Copyright 2024 Peter Lyons Kehl
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! `bytemuck::Zeroable`, `bytemuck::Pod` and `bytemuck::TransparentWrapper` of [Amount]s, [Id]s
+//! and [Instant]s, for casting them (and slices of them) to and from bytes or their `Repr`. `Pod`
+//! only for the `Copy` trait flags, as `Pod` requires `Copy`. Requires feature `bytemuck`.
+//!
+//! SAFETY: each wrapper is `#[repr(transparent)]` over `Repr`, and its only other field is a
+//! zero-sized `PhantomData`.
+
+use crate::amount::Amount;
+use crate::id::Id;
+use crate::instant::Instant;
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+use crate::trait_flag::{self, TraitFlags};
+use bytemuck::{Pod, TransparentWrapper, Zeroable};
+
+macro_rules! impl_bytemuck {
+    ($($wrapper:ident)*) => {
+        $(
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            unsafe impl<const TF: TraitFlags, Unit, Repr: Zeroable> Zeroable
+                for $wrapper<TF, Unit, Repr>
+            {
+            }
+
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            unsafe impl<Unit: 'static, Repr: Pod> Pod
+                for $wrapper<{ trait_flag::TRAIT_FLAGS_IS_COPY_IS_DEFAULT }, Unit, Repr>
+            {
+            }
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            unsafe impl<Unit: 'static, Repr: Pod> Pod
+                for $wrapper<{ trait_flag::TRAIT_FLAGS_IS_COPY_NO_DEFAULT }, Unit, Repr>
+            {
+            }
+
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            unsafe impl<const TF: TraitFlags, Unit, Repr> TransparentWrapper<Repr>
+                for $wrapper<TF, Unit, Repr>
+            {
+            }
+        )*
+    };
+}
+impl_bytemuck!(Amount Id Instant);
@@ -2,6 +2,30 @@
 //! omitted. Indicate significant (non-standard) differences only.
 //!
 //! For all aliases, include [crate::prelude_full].
+//!
+//! # Reading compiler errors
+//!
+//! These are type aliases, so compiler errors show the types they stand for, with the trait flags
+//! as the first generic argument: `Id<3, User, u64>` is `Id<User, u64>`. On stable Rust, the flags
+//! are a number, whose bits tell which optional traits the type has:
+//!
+//! | In errors        | Alias                      |
+//! |------------------|----------------------------|
+//! | `Id<3, E, R>`    | `Id<E, R>`                 |
+//! | `Id<2, E, R>`    | `IdNoCopy<E, R>`           |
+//! | `Id<1, E, R>`    | `IdNoDefault<E, R>`        |
+//! | `Id<0, E, R>`    | `IdNoCopyNoDefault<E, R>`  |
+//!
+//! And the same for `Amount` and `Instant`. If you only use the aliases here, the flags are
+//! always `3`, and the types to look at are the other two arguments. (These numbers are subject
+//! to change, like the trait flags themselves. With feature `unstable_generic_const_own_type`,
+//! errors show the flags as `TraitFlagsValues` variants instead.)
+//!
+//! To not see the flags at all, use the structs in `plain` (feature `plain`) instead: they
+//! delegate to these aliases, and errors name them `Id<User, u64>` and so on.
+#[cfg(feature = "plain")]
+pub mod plain;
+
 pub use crate::Id;
 pub use crate::IdNoCopy;
 pub use crate::IdNoCopyNoDefault;
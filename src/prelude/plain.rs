@@ -0,0 +1,305 @@
+// Copyright 2024 Peter Lyons Kehl
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! [Id], [Amount] and [Instant] as structs of their own, rather than aliases, so that compiler
+//! errors name them as written: `Id<User, u64>`, not `Id<3, User, u64>`. Each wraps the
+//! same-named alias from [crate::prelude] (with the default trait flags), and dereferences to it
+//! for everything that isn't delegated here.
+//!
+//! ```
+//! #![cfg_attr(
+//!     feature = "unstable_generic_const_own_type",
+//!     feature(generic_const_exprs)
+//! )]
+//!
+//! use phantom_newtype::prelude::plain::{Amount, Id, Instant};
+//!
+//! enum User {}
+//! enum Seconds {}
+//!
+//! let user = Id::<User, u64>::new(7);
+//! assert_eq!(*user.get(), 7);
+//!
+//! let start = Instant::<Seconds, u64>::new(10);
+//! let end = start + Amount::new(5);
+//! assert_eq!(end - start, Amount::<Seconds, u64>::new(5));
+//! ```
+//!
+//! And `Id::<User, u64>::new(7) == Id::<Order, u64>::new(7)` fails to compile with "expected
+//! `Id<User, u64>`, found `Id<Order, u64>`".
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::ops::{Add, AddAssign, Deref, DerefMut, Div, DivAssign, Mul, MulAssign, Sub, SubAssign};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+macro_rules! plain_wrapper {
+    (@fmt $wrapper:ident: $($trait:ident)*) => {
+        $(
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<Unit, Repr> fmt::$trait for $wrapper<Unit, Repr>
+            where
+                crate::$wrapper<Unit, Repr>: fmt::$trait,
+            {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    fmt::$trait::fmt(&self.0, f)
+                }
+            }
+        )*
+    };
+    ($($wrapper:ident)*) => {
+        $(
+            #[doc = concat!("[crate::prelude::", stringify!($wrapper), "], without trait flags in compiler errors.")]
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            #[repr(transparent)]
+            pub struct $wrapper<Unit, Repr>(pub crate::$wrapper<Unit, Repr>);
+
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<Unit, Repr> $wrapper<Unit, Repr> {
+                pub const fn new(repr: Repr) -> Self {
+                    Self(crate::$wrapper::new(repr))
+                }
+
+                pub const fn get(&self) -> &Repr {
+                    self.0.get()
+                }
+            }
+
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<Unit, Repr> Deref for $wrapper<Unit, Repr> {
+                type Target = crate::$wrapper<Unit, Repr>;
+                fn deref(&self) -> &Self::Target {
+                    &self.0
+                }
+            }
+
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<Unit, Repr> DerefMut for $wrapper<Unit, Repr> {
+                fn deref_mut(&mut self) -> &mut Self::Target {
+                    &mut self.0
+                }
+            }
+
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<Unit, Repr> From<Repr> for $wrapper<Unit, Repr> {
+                fn from(repr: Repr) -> Self {
+                    Self::new(repr)
+                }
+            }
+
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<Unit, Repr> From<crate::$wrapper<Unit, Repr>> for $wrapper<Unit, Repr> {
+                fn from(flagged: crate::$wrapper<Unit, Repr>) -> Self {
+                    Self(flagged)
+                }
+            }
+
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<Unit, Repr> From<$wrapper<Unit, Repr>> for crate::$wrapper<Unit, Repr> {
+                fn from(plain: $wrapper<Unit, Repr>) -> Self {
+                    plain.0
+                }
+            }
+
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<Unit, Repr: Clone> Clone for $wrapper<Unit, Repr> {
+                fn clone(&self) -> Self {
+                    Self(self.0.clone())
+                }
+            }
+
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<Unit, Repr: Copy> Copy for $wrapper<Unit, Repr> {}
+
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<Unit, Repr: Default> Default for $wrapper<Unit, Repr> {
+                fn default() -> Self {
+                    Self(crate::$wrapper::default())
+                }
+            }
+
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<Unit, Repr: PartialEq> PartialEq for $wrapper<Unit, Repr> {
+                fn eq(&self, rhs: &Self) -> bool {
+                    self.0 == rhs.0
+                }
+            }
+
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<Unit, Repr: Eq> Eq for $wrapper<Unit, Repr> {}
+
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<Unit, Repr: PartialOrd> PartialOrd for $wrapper<Unit, Repr> {
+                fn partial_cmp(&self, rhs: &Self) -> Option<Ordering> {
+                    self.0.partial_cmp(&rhs.0)
+                }
+            }
+
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<Unit, Repr: Ord> Ord for $wrapper<Unit, Repr> {
+                fn cmp(&self, rhs: &Self) -> Ordering {
+                    self.0.cmp(&rhs.0)
+                }
+            }
+
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<Unit, Repr: Hash> Hash for $wrapper<Unit, Repr> {
+                fn hash<H: Hasher>(&self, state: &mut H) {
+                    self.0.hash(state)
+                }
+            }
+
+            plain_wrapper!(@fmt $wrapper: Debug Display LowerHex UpperHex Octal Binary LowerExp UpperExp);
+
+            #[cfg(feature = "serde")]
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<Unit, Repr> Serialize for $wrapper<Unit, Repr>
+            where
+                crate::$wrapper<Unit, Repr>: Serialize,
+            {
+                fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    self.0.serialize(serializer)
+                }
+            }
+
+            #[cfg(feature = "serde")]
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<'de, Unit, Repr> Deserialize<'de> for $wrapper<Unit, Repr>
+            where
+                crate::$wrapper<Unit, Repr>: Deserialize<'de>,
+            {
+                fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    crate::$wrapper::deserialize(deserializer).map(Self)
+                }
+            }
+        )*
+    };
+}
+
+plain_wrapper!(Amount Id Instant);
+
+// Arithmetic keeps one `Repr` throughout, unlike that of the flagged types, so that a mismatch is
+// reported as one, rather than as a missing `AddTo` or `SubFrom`.
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+impl<Unit, Repr: Add<Output = Repr>> Add for Amount<Unit, Repr> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.0 .0 + rhs.0 .0)
+    }
+}
+
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+impl<Unit, Repr: Sub<Output = Repr>> Sub for Amount<Unit, Repr> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.0 .0 - rhs.0 .0)
+    }
+}
+
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+impl<Unit, Repr: AddAssign> AddAssign for Amount<Unit, Repr> {
+    fn add_assign(&mut self, rhs: Self) {
+        self.0 .0 += rhs.0 .0
+    }
+}
+
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+impl<Unit, Repr: SubAssign> SubAssign for Amount<Unit, Repr> {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.0 .0 -= rhs.0 .0
+    }
+}
+
+// Scaling goes through the flagged `Amount`, so that it takes whatever that does: a `Repr`, or a
+// `Scalar` (see `repr::Scale`).
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+impl<Unit, Repr, Rhs> Mul<Rhs> for Amount<Unit, Repr>
+where
+    crate::Amount<Unit, Repr>: Mul<Rhs, Output = crate::Amount<Unit, Repr>>,
+{
+    type Output = Self;
+    fn mul(self, rhs: Rhs) -> Self {
+        Self(self.0 * rhs)
+    }
+}
+
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+impl<Unit, Repr, Rhs> Div<Rhs> for Amount<Unit, Repr>
+where
+    crate::Amount<Unit, Repr>: Div<Rhs, Output = crate::Amount<Unit, Repr>>,
+{
+    type Output = Self;
+    fn div(self, rhs: Rhs) -> Self {
+        Self(self.0 / rhs)
+    }
+}
+
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+impl<Unit, Repr, Rhs> MulAssign<Rhs> for Amount<Unit, Repr>
+where
+    crate::Amount<Unit, Repr>: MulAssign<Rhs>,
+{
+    fn mul_assign(&mut self, rhs: Rhs) {
+        self.0 *= rhs
+    }
+}
+
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+impl<Unit, Repr, Rhs> DivAssign<Rhs> for Amount<Unit, Repr>
+where
+    crate::Amount<Unit, Repr>: DivAssign<Rhs>,
+{
+    fn div_assign(&mut self, rhs: Rhs) {
+        self.0 /= rhs
+    }
+}
+
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+impl<Unit, Repr: Add<Output = Repr>> Add<Amount<Unit, Repr>> for Instant<Unit, Repr> {
+    type Output = Self;
+    fn add(self, rhs: Amount<Unit, Repr>) -> Self {
+        Self::new(self.0 .0 + rhs.0 .0)
+    }
+}
+
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+impl<Unit, Repr: Sub<Output = Repr>> Sub<Amount<Unit, Repr>> for Instant<Unit, Repr> {
+    type Output = Self;
+    fn sub(self, rhs: Amount<Unit, Repr>) -> Self {
+        Self::new(self.0 .0 - rhs.0 .0)
+    }
+}
+
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+impl<Unit, Repr: Sub<Output = Repr>> Sub for Instant<Unit, Repr> {
+    type Output = Amount<Unit, Repr>;
+    fn sub(self, rhs: Self) -> Amount<Unit, Repr> {
+        Amount::new(self.0 .0 - rhs.0 .0)
+    }
+}
+
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+impl<Unit, Repr: AddAssign> AddAssign<Amount<Unit, Repr>> for Instant<Unit, Repr> {
+    fn add_assign(&mut self, rhs: Amount<Unit, Repr>) {
+        self.0 .0 += rhs.0 .0
+    }
+}
+
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+impl<Unit, Repr: SubAssign> SubAssign<Amount<Unit, Repr>> for Instant<Unit, Repr> {
+    fn sub_assign(&mut self, rhs: Amount<Unit, Repr>) {
+        self.0 .0 -= rhs.0 .0
+    }
+}
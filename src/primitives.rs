@@ -0,0 +1,137 @@
+// Copyright 2024 Peter Lyons Kehl
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Small building blocks made of amounts and instants. Requires feature `primitives`.
+
+use crate::{Amount, Instant, Scalar};
+
+/// A token bucket rate limiter: up to `capacity` tokens (of `Unit`), refilled by `refill` tokens
+/// every `period` (of `TimeUnit`). The caller passes the current [Instant] in, so that it works
+/// with any clock (see [crate::clock]) and in tests.
+///
+/// The bucket starts full. Time going backwards (an earlier `now` than before) refills nothing.
+///
+/// ```
+/// #![cfg_attr(
+///     feature = "unstable_generic_const_own_type",
+///     feature(generic_const_exprs)
+/// )]
+///
+/// use phantom_newtype::primitives::TokenBucket;
+/// use phantom_newtype::{Amount, Instant};
+///
+/// enum Requests {}
+/// enum Millis {}
+/// type Count = Amount<Requests, u64>;
+/// type Duration = Amount<Millis, u64>;
+/// type Timestamp = Instant<Millis, u64>;
+///
+/// // 10 requests a second, in bursts of up to 5.
+/// let mut bucket = TokenBucket::new(Count::from(5), Count::from(1), Duration::from(100), Timestamp::from(0));
+/// assert!(bucket.try_take(Count::from(5), Timestamp::from(0)));
+/// assert!(!bucket.try_take(Count::from(1), Timestamp::from(50)));
+/// assert_eq!(bucket.time_until(Count::from(1), Timestamp::from(50)), Some(Duration::from(50)));
+///
+/// assert!(bucket.try_take(Count::from(1), Timestamp::from(100)));
+/// assert_eq!(bucket.available(Timestamp::from(1_000)), Count::from(5));
+/// assert_eq!(bucket.time_until(Count::from(6), Timestamp::from(1_000)), None);
+/// ```
+pub struct TokenBucket<Unit, TimeUnit> {
+    capacity: Amount<Unit, u64>,
+    refill: Amount<Unit, u64>,
+    period: Amount<TimeUnit, u64>,
+    tokens: Amount<Unit, u64>,
+    last_refill: Instant<TimeUnit, u64>,
+}
+
+impl<Unit, TimeUnit> TokenBucket<Unit, TimeUnit> {
+    /// A full bucket.
+    ///
+    /// # Panics
+    ///
+    /// If `period` is zero.
+    pub fn new(
+        capacity: Amount<Unit, u64>,
+        refill: Amount<Unit, u64>,
+        period: Amount<TimeUnit, u64>,
+        now: Instant<TimeUnit, u64>,
+    ) -> Self {
+        assert!(period.get_copy() != 0, "the refill period must not be zero");
+        Self {
+            capacity,
+            refill,
+            period,
+            tokens: capacity,
+            last_refill: now,
+        }
+    }
+
+    pub fn capacity(&self) -> Amount<Unit, u64> {
+        self.capacity
+    }
+
+    /// Tokens available at `now`.
+    pub fn available(&mut self, now: Instant<TimeUnit, u64>) -> Amount<Unit, u64> {
+        self.refill_until(now);
+        self.tokens
+    }
+
+    /// Take `tokens`, if available at `now`. Otherwise take none, and return `false`.
+    pub fn try_take(&mut self, tokens: Amount<Unit, u64>, now: Instant<TimeUnit, u64>) -> bool {
+        self.refill_until(now);
+        if tokens <= self.tokens {
+            self.tokens -= tokens;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// How long after `now` `tokens` will be available (zero if they are already). `None` if
+    /// `tokens` exceed the capacity (or if nothing is ever refilled), so they never will be.
+    pub fn time_until(
+        &mut self,
+        tokens: Amount<Unit, u64>,
+        now: Instant<TimeUnit, u64>,
+    ) -> Option<Amount<TimeUnit, u64>> {
+        self.refill_until(now);
+        if tokens <= self.tokens {
+            return Some(Amount::new(0));
+        }
+        if tokens > self.capacity || self.refill.get_copy() == 0 {
+            return None;
+        }
+        let missing = tokens - self.tokens;
+        let periods = missing.get_copy().div_ceil(self.refill.get_copy());
+        // The current period is partly over already.
+        let next_refill = self.last_refill + self.period * Scalar(periods);
+        Some(next_refill - now)
+    }
+
+    fn refill_until(&mut self, now: Instant<TimeUnit, u64>) {
+        if now <= self.last_refill {
+            return;
+        }
+        let periods: u64 = (now - self.last_refill) / self.period;
+        let refilled = self.refill.get_copy().saturating_mul(periods);
+        self.tokens =
+            Amount::new(self.tokens.get_copy().saturating_add(refilled)).min(self.capacity);
+        self.last_refill = if self.tokens == self.capacity {
+            // A full bucket doesn't accumulate the time towards its next refill.
+            now
+        } else {
+            self.last_refill + self.period * Scalar(periods)
+        };
+    }
+}
@@ -0,0 +1,240 @@
+// Copyright 2024 Peter Lyons Kehl
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Typed mirrors of `prost`-generated messages. Requires feature `prost`.
+//!
+//! Generated messages have plain fields (like `u64`), and their encoding can't be changed to take
+//! [crate::Amount]s, [crate::Id]s or [crate::Instant]s. Instead, declare a mirror of the message
+//! once, with [crate::proto_message], and convert with `into()` at the message boundary.
+//!
+//! ```
+//! #![cfg_attr(
+//!     feature = "unstable_generic_const_own_type",
+//!     feature(generic_const_exprs)
+//! )]
+//!
+//! use phantom_newtype::{Amount, Id};
+//!
+//! mod pb {
+//!     // As generated by `prost-build`.
+//!     #[derive(Clone, PartialEq, prost::Message)]
+//!     pub struct Transfer {
+//!         #[prost(uint64, tag = "1")]
+//!         pub from: u64,
+//!         #[prost(uint64, tag = "2")]
+//!         pub to: u64,
+//!         #[prost(int64, tag = "3")]
+//!         pub amount: i64,
+//!         #[prost(uint64, repeated, tag = "4")]
+//!         pub approvers: Vec<u64>,
+//!         #[prost(string, tag = "5")]
+//!         pub memo: String,
+//!     }
+//! }
+//!
+//! enum Account {}
+//! enum Cents {}
+//!
+//! phantom_newtype::proto_message! {
+//!     #[derive(Debug, PartialEq)]
+//!     pub struct Transfer for pb::Transfer {
+//!         pub from: Id<Account, u64>,
+//!         pub to: Id<Account, u64>,
+//!         pub amount: Amount<Cents, i64>,
+//!         pub approvers: Vec<Id<Account, u64>>,
+//!         pub memo: String,
+//!     }
+//! }
+//!
+//! let transfer = Transfer {
+//!     from: Id::from(1),
+//!     to: Id::from(2),
+//!     amount: Amount::new(250),
+//!     approvers: vec![Id::from(3)],
+//!     memo: "rent".to_owned(),
+//! };
+//! let message: pb::Transfer = transfer.into();
+//! let bytes = prost::Message::encode_to_vec(&message);
+//!
+//! let decoded: Transfer = <pb::Transfer as prost::Message>::decode(&bytes[..]).unwrap().into();
+//! assert_eq!(decoded.amount, Amount::new(250));
+//! assert_eq!(decoded.approvers, [Id::from(3)]);
+//! ```
+//!
+//! Fields of a mirror are [Proto] types: amounts, ids and instants (of [Proto] `Repr`s), scalars as
+//! generated (`bool`, `i32`, `i64`, `u32`, `u64`, `f32`, `f64`, `String`, `Vec<u8>`, `Bytes`), other
+//! mirrors (for nested messages), and `Option`s, `Vec`s and `Box`es of these. Fields of the message
+//! that the mirror leaves out get their defaults when converting to the message.
+
+use crate::amount::Amount;
+use crate::id::Id;
+use crate::instant::Instant;
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+use crate::trait_flag::TraitFlags;
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// A field type of a [crate::proto_message] mirror: it converts (losslessly) from and to the type of
+/// the generated message's field.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` can't be a field of a `proto_message!` mirror",
+    label = "no `proto::Proto` for `{Self}`",
+    note = "use an amount, id or instant of a protobuf scalar, a scalar, another mirror, or an `Option`, `Vec` or `Box` of these"
+)]
+pub trait Proto: Sized {
+    /// The type of the field in the generated message.
+    type Proto;
+
+    fn from_proto(proto: Self::Proto) -> Self;
+    fn into_proto(self) -> Self::Proto;
+}
+
+macro_rules! impl_proto_as_is {
+    ($($t:ty)*) => {
+        $(
+            impl Proto for $t {
+                type Proto = Self;
+
+                fn from_proto(proto: Self) -> Self {
+                    proto
+                }
+
+                fn into_proto(self) -> Self {
+                    self
+                }
+            }
+        )*
+    };
+}
+impl_proto_as_is!(bool i32 i64 u32 u64 f32 f64 String Vec<u8> prost::bytes::Bytes);
+
+impl<T: Proto> Proto for Option<T> {
+    type Proto = Option<T::Proto>;
+
+    fn from_proto(proto: Self::Proto) -> Self {
+        proto.map(T::from_proto)
+    }
+
+    fn into_proto(self) -> Self::Proto {
+        self.map(T::into_proto)
+    }
+}
+
+impl<T: Proto> Proto for Vec<T> {
+    type Proto = Vec<T::Proto>;
+
+    fn from_proto(proto: Self::Proto) -> Self {
+        proto.into_iter().map(T::from_proto).collect()
+    }
+
+    fn into_proto(self) -> Self::Proto {
+        self.into_iter().map(T::into_proto).collect()
+    }
+}
+
+impl<T: Proto> Proto for Box<T> {
+    type Proto = Box<T::Proto>;
+
+    fn from_proto(proto: Self::Proto) -> Self {
+        Box::new(T::from_proto(*proto))
+    }
+
+    fn into_proto(self) -> Self::Proto {
+        Box::new((*self).into_proto())
+    }
+}
+
+macro_rules! impl_proto {
+    ($($wrapper:ident)*) => {
+        $(
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<const TF: TraitFlags, Unit, Repr: Proto> Proto for $wrapper<TF, Unit, Repr> {
+                type Proto = Repr::Proto;
+
+                fn from_proto(proto: Self::Proto) -> Self {
+                    Self::new(Repr::from_proto(proto))
+                }
+
+                fn into_proto(self) -> Self::Proto {
+                    self.0.into_proto()
+                }
+            }
+        )*
+    };
+}
+impl_proto!(Amount Id Instant);
+
+#[doc(hidden)]
+pub const fn assert_message<M: prost::Message + Default>() {}
+
+/// Declares a typed mirror of a `prost`-generated message, with `From` conversions both ways, and
+/// [proto::Proto](crate::proto::Proto) (so that it can be a field of other mirrors). See
+/// [proto](crate::proto). Requires feature `prost`.
+#[macro_export]
+macro_rules! proto_message {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident for $proto:path {
+            $(
+                $(#[$field_meta:meta])*
+                $field_vis:vis $field:ident: $field_type:ty
+            ),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        $vis struct $name {
+            $(
+                $(#[$field_meta])*
+                $field_vis $field: $field_type,
+            )*
+        }
+
+        const _: () = $crate::proto::assert_message::<$proto>();
+
+        impl ::core::convert::From<$proto> for $name {
+            fn from(message: $proto) -> Self {
+                Self {
+                    $(
+                        $field: <$field_type as $crate::proto::Proto>::from_proto(message.$field),
+                    )*
+                }
+            }
+        }
+
+        impl ::core::convert::From<$name> for $proto {
+            #[allow(clippy::needless_update)]
+            fn from(mirror: $name) -> Self {
+                Self {
+                    $(
+                        $field: <$field_type as $crate::proto::Proto>::into_proto(mirror.$field),
+                    )*
+                    ..::core::default::Default::default()
+                }
+            }
+        }
+
+        impl $crate::proto::Proto for $name {
+            type Proto = $proto;
+
+            fn from_proto(proto: $proto) -> Self {
+                ::core::convert::From::from(proto)
+            }
+
+            fn into_proto(self) -> $proto {
+                ::core::convert::From::from(self)
+            }
+        }
+    };
+}
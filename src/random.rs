@@ -0,0 +1,74 @@
+// Copyright 2024 Peter Lyons Kehl
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Random [Amount]s, [Id]s and [Instant]s, sampled like their `Repr`. Requires feature `rand`.
+//!
+//! ```
+//! #![cfg_attr(
+//!     feature = "unstable_generic_const_own_type",
+//!     feature(generic_const_exprs)
+//! )]
+//!
+//! use phantom_newtype::{Amount, Id};
+//! use rand::rngs::SmallRng;
+//! use rand::{Rng, SeedableRng};
+//!
+//! enum User {}
+//! enum Cents {}
+//! type UserId = Id<User, u64>;
+//!
+//! let mut rng = SmallRng::seed_from_u64(7);
+//! let users: Vec<UserId> = (0..1000).map(|_| UserId::random(&mut rng)).collect();
+//! assert_eq!(users.len(), 1000);
+//!
+//! let balance: Amount<Cents, u32> = rng.gen();
+//! let again: Amount<Cents, u32> = SmallRng::seed_from_u64(7).gen();
+//! assert_ne!(balance, again); // `rng` was advanced by the ids.
+//! ```
+
+use crate::amount::Amount;
+use crate::id::Id;
+use crate::instant::Instant;
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+use crate::trait_flag::TraitFlags;
+use rand::distributions::{Distribution, Standard};
+use rand::Rng;
+
+macro_rules! impl_random {
+    ($($wrapper:ident)*) => {
+        $(
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<const TF: TraitFlags, Unit, Repr> Distribution<$wrapper<TF, Unit, Repr>> for Standard
+            where
+                Standard: Distribution<Repr>,
+            {
+                fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> $wrapper<TF, Unit, Repr> {
+                    $wrapper::new(Distribution::<Repr>::sample(self, rng))
+                }
+            }
+
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<const TF: TraitFlags, Unit, Repr> $wrapper<TF, Unit, Repr>
+            where
+                Standard: Distribution<Repr>,
+            {
+                /// Sample a random value, as per the `Standard` distribution of `Repr`.
+                pub fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+                    Distribution::<Self>::sample(&Standard, rng)
+                }
+            }
+        )*
+    };
+}
+impl_random!(Amount Id Instant);
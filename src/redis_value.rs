@@ -0,0 +1,70 @@
+// Copyright 2024 Peter Lyons Kehl
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! `redis` `ToRedisArgs`, `ToSingleRedisArg` and `FromRedisValue` of [Amount]s, [Id]s and
+//! [Instant]s: they delegate to `Repr`, so that a wrapper is a key, argument or reply like its
+//! `Repr`. Requires feature `redis`.
+
+use crate::amount::Amount;
+use crate::id::Id;
+use crate::instant::Instant;
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+use crate::trait_flag::TraitFlags;
+use redis::{
+    FromRedisValue, NumericBehavior, ParsingError, RedisWrite, ToRedisArgs, ToSingleRedisArg, Value,
+};
+
+macro_rules! impl_redis {
+    ($($wrapper:ident)*) => {
+        $(
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<const TF: TraitFlags, Unit, Repr: ToRedisArgs> ToRedisArgs for $wrapper<TF, Unit, Repr> {
+                fn write_redis_args<W>(&self, out: &mut W)
+                where
+                    W: ?Sized + RedisWrite,
+                {
+                    self.0.write_redis_args(out)
+                }
+
+                fn describe_numeric_behavior(&self) -> NumericBehavior {
+                    self.0.describe_numeric_behavior()
+                }
+
+                fn num_of_args(&self) -> usize {
+                    self.0.num_of_args()
+                }
+            }
+
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<const TF: TraitFlags, Unit, Repr: ToSingleRedisArg> ToSingleRedisArg
+                for $wrapper<TF, Unit, Repr>
+            {
+            }
+
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<const TF: TraitFlags, Unit, Repr: FromRedisValue> FromRedisValue
+                for $wrapper<TF, Unit, Repr>
+            {
+                fn from_redis_value_ref(v: &Value) -> Result<Self, ParsingError> {
+                    Repr::from_redis_value_ref(v).map($wrapper::new)
+                }
+
+                fn from_redis_value(v: Value) -> Result<Self, ParsingError> {
+                    Repr::from_redis_value(v).map($wrapper::new)
+                }
+            }
+        )*
+    };
+}
+impl_redis!(Amount Id Instant);
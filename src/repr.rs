@@ -0,0 +1,911 @@
+// Copyright 2024 Peter Lyons Kehl
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Traits describing what a `Repr` can do, beyond what `core` traits express.
+//!
+//! They are implemented for primitive integers. Implement them for your own `Repr` types if you
+//! need the functionality that depends on them.
+
+use core::convert::TryFrom;
+use core::fmt;
+
+/// Addition that reports overflow instead of panicking or wrapping.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` has no checked addition",
+    label = "no `repr::CheckedAdd` for `{Self}`",
+    note = "`repr::CheckedAdd` is implemented for primitive integers; implement it for your own `Repr`"
+)]
+pub trait CheckedAdd: Sized {
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+}
+
+/// Subtraction that reports overflow instead of panicking or wrapping.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` has no checked subtraction",
+    label = "no `repr::CheckedSub` for `{Self}`",
+    note = "`repr::CheckedSub` is implemented for primitive integers; implement it for your own `Repr`"
+)]
+pub trait CheckedSub: Sized {
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+}
+
+/// Multiplication that reports overflow instead of panicking or wrapping.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` has no checked multiplication",
+    label = "no `repr::CheckedMul` for `{Self}`",
+    note = "`repr::CheckedMul` is implemented for primitive integers; implement it for your own `Repr`"
+)]
+pub trait CheckedMul: Sized {
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+}
+
+macro_rules! impl_checked {
+    ($($t:ty)*) => {
+        $(
+            impl CheckedAdd for $t {
+                fn checked_add(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_add(self, rhs)
+                }
+            }
+            impl CheckedSub for $t {
+                fn checked_sub(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_sub(self, rhs)
+                }
+            }
+            impl CheckedMul for $t {
+                fn checked_mul(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_mul(self, rhs)
+                }
+            }
+        )*
+    };
+}
+impl_checked!(u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize);
+
+/// Error returned by checked operations when the result doesn't fit `Repr`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ArithmeticOverflow;
+
+impl fmt::Display for ArithmeticOverflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("arithmetic overflow")
+    }
+}
+
+impl core::error::Error for ArithmeticOverflow {}
+
+/// A `core::num::NonZero*` integer, usable as a `Repr` that keeps the niche optimization (so that
+/// `Option<Id<Entity, NonZeroU64>>` is as big as `u64`).
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not a non-zero integer",
+    label = "no `repr::NonZeroRepr` for `{Self}`",
+    note = "use a `core::num::NonZero*` `Repr`, for example through `NonZeroId`"
+)]
+pub trait NonZeroRepr: Copy {
+    /// The primitive integer that this is a non-zero variant of.
+    type Primitive: Copy;
+
+    /// `None` if `value` is zero.
+    fn new_checked(value: Self::Primitive) -> Option<Self>;
+    fn get_primitive(self) -> Self::Primitive;
+}
+
+/// A primitive integer that has a [NonZeroRepr] counterpart. Used by [crate::NonZeroId].
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` has no non-zero counterpart",
+    label = "no `repr::HasNonZero` for `{Self}`",
+    note = "`NonZeroId<Entity, Primitive>` needs a primitive integer `Primitive`"
+)]
+pub trait HasNonZero: Copy {
+    type NonZero: NonZeroRepr<Primitive = Self>;
+}
+
+macro_rules! impl_non_zero {
+    ($($t:ty => $non_zero:ident)*) => {
+        $(
+            impl NonZeroRepr for core::num::$non_zero {
+                type Primitive = $t;
+
+                fn new_checked(value: $t) -> Option<Self> {
+                    Self::new(value)
+                }
+                fn get_primitive(self) -> $t {
+                    self.get()
+                }
+            }
+            impl HasNonZero for $t {
+                type NonZero = core::num::$non_zero;
+            }
+        )*
+    };
+}
+impl_non_zero! {
+    u8 => NonZeroU8
+    u16 => NonZeroU16
+    u32 => NonZeroU32
+    u64 => NonZeroU64
+    u128 => NonZeroU128
+    usize => NonZeroUsize
+    i8 => NonZeroI8
+    i16 => NonZeroI16
+    i32 => NonZeroI32
+    i64 => NonZeroI64
+    i128 => NonZeroI128
+    isize => NonZeroIsize
+}
+
+/// Order-preserving fixed-size byte encoding: for any `a` and `b`, `a.cmp(&b)` equals the
+/// lexicographic comparison of their encodings. That lets typed values be keys in ordered
+/// key-value stores.
+///
+/// Integers are encoded big-endian, with the sign bit flipped for signed integers.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` has no order-preserving byte encoding",
+    label = "no `repr::SortableBytes` for `{Self}`",
+    note = "`repr::SortableBytes` is implemented for primitive integers; implement it for your own `Repr`"
+)]
+pub trait SortableBytes: Sized {
+    /// `[u8; N]` where `N` is the size of the integer.
+    type Bytes: Copy + AsRef<[u8]> + for<'a> TryFrom<&'a [u8]>;
+
+    fn to_sortable_bytes(self) -> Self::Bytes;
+    fn from_sortable_bytes(bytes: Self::Bytes) -> Self;
+}
+
+macro_rules! impl_sortable_bytes {
+    ($($t:ty => $sign_bias:expr;)*) => {
+        $(
+            impl SortableBytes for $t {
+                type Bytes = [u8; core::mem::size_of::<$t>()];
+
+                fn to_sortable_bytes(self) -> Self::Bytes {
+                    (self ^ $sign_bias).to_be_bytes()
+                }
+                fn from_sortable_bytes(bytes: Self::Bytes) -> Self {
+                    <$t>::from_be_bytes(bytes) ^ $sign_bias
+                }
+            }
+        )*
+    };
+}
+impl_sortable_bytes! {
+    u8 => 0;
+    u16 => 0;
+    u32 => 0;
+    u64 => 0;
+    u128 => 0;
+    usize => 0;
+    i8 => i8::MIN;
+    i16 => i16::MIN;
+    i32 => i32::MIN;
+    i64 => i64::MIN;
+    i128 => i128::MIN;
+    isize => isize::MIN;
+}
+
+/// An unsigned integer used as a set of bits, as in [crate::TypedFlags].
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` can't hold `TypedFlags`",
+    label = "no `repr::FlagsRepr` for `{Self}`",
+    note = "use an unsigned primitive integer `Repr` for `TypedFlags`"
+)]
+pub trait FlagsRepr: Copy + Eq {
+    /// Number of bits.
+    const BITS: u32;
+    /// No bits set.
+    const EMPTY: Self;
+
+    /// Only bit number `index` set. `index` must be less than [FlagsRepr::BITS].
+    fn bit(index: u32) -> Self;
+    fn union(self, other: Self) -> Self;
+    fn intersection(self, other: Self) -> Self;
+    fn complement(self) -> Self;
+}
+
+macro_rules! impl_flags_repr {
+    ($($t:ty)*) => {
+        $(
+            impl FlagsRepr for $t {
+                const BITS: u32 = <$t>::BITS;
+                const EMPTY: Self = 0;
+
+                fn bit(index: u32) -> Self {
+                    1 << index
+                }
+                fn union(self, other: Self) -> Self {
+                    self | other
+                }
+                fn intersection(self, other: Self) -> Self {
+                    self & other
+                }
+                fn complement(self) -> Self {
+                    !self
+                }
+            }
+        )*
+    };
+}
+impl_flags_repr!(u8 u16 u32 u64 u128 usize);
+
+/// A `Default` that is available in `const` contexts (`Default::default` is not `const`), so that
+/// `static`s and `const`s can hold default (zero) amounts and instants: see `Amount::ZERO` and
+/// `Amount::DEFAULT`.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` has no `const` default",
+    label = "no `repr::ConstDefault` for `{Self}`",
+    note = "`repr::ConstDefault` is implemented for primitives and arrays of them; implement it for your own `Repr`"
+)]
+pub trait ConstDefault: Sized {
+    /// The same as `Default::default()`, where `Self` implements `Default`.
+    const DEFAULT: Self;
+}
+
+macro_rules! impl_const_default {
+    ($($t:ty => $default:expr),*) => {
+        $(
+            impl ConstDefault for $t {
+                const DEFAULT: Self = $default;
+            }
+        )*
+    };
+}
+impl_const_default!(
+    u8 => 0, u16 => 0, u32 => 0, u64 => 0, u128 => 0, usize => 0,
+    i8 => 0, i16 => 0, i32 => 0, i64 => 0, i128 => 0, isize => 0,
+    f32 => 0.0, f64 => 0.0, bool => false, char => '\0', () => ()
+);
+
+impl<T: ConstDefault, const N: usize> ConstDefault for [T; N] {
+    const DEFAULT: Self = [T::DEFAULT; N];
+}
+
+/// Lossy conversion to `f64`, for statistics and other approximate computations.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` can't be converted to `f64`",
+    label = "no `repr::ToF64` for `{Self}`",
+    note = "`repr::ToF64` is implemented for primitive numbers; implement it for your own `Repr`"
+)]
+pub trait ToF64: Copy {
+    fn to_f64(self) -> f64;
+}
+
+/// Conversion from `f64`, rounding to the nearest value (half away from zero) and saturating, for
+/// integers.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` can't be converted from `f64`",
+    label = "no `repr::FromF64` for `{Self}`",
+    note = "`repr::FromF64` is implemented for primitive numbers; implement it for your own `Repr`"
+)]
+pub trait FromF64: Copy {
+    fn from_f64(value: f64) -> Self;
+}
+
+macro_rules! impl_f64 {
+    ($($t:ty => $round:expr;)*) => {
+        $(
+            impl ToF64 for $t {
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+            }
+            impl FromF64 for $t {
+                fn from_f64(value: f64) -> Self {
+                    let round: fn(f64) -> f64 = $round;
+                    round(value) as $t
+                }
+            }
+        )*
+    };
+}
+// `f64::round` is not in `core`, so integers round here by hand (half away from zero).
+impl_f64! {
+    u8 => round_half_away;
+    u16 => round_half_away;
+    u32 => round_half_away;
+    u64 => round_half_away;
+    u128 => round_half_away;
+    usize => round_half_away;
+    i8 => round_half_away;
+    i16 => round_half_away;
+    i32 => round_half_away;
+    i64 => round_half_away;
+    i128 => round_half_away;
+    isize => round_half_away;
+    f32 => |value| value;
+    f64 => |value| value;
+}
+
+/// From 2^52 on, every `f64` is a whole number.
+const F64_WHOLE_FROM: f64 = 4_503_599_627_370_496.0;
+
+fn round_half_away(value: f64) -> f64 {
+    // Also returns NaN and infinities unchanged (casting saturates them).
+    if !(-F64_WHOLE_FROM < value && value < F64_WHOLE_FROM) {
+        return value;
+    }
+    // Both exact in this range. (Adding 0.5 instead would round, like 0.49999999999999994 to 1.)
+    let whole = value as i64 as f64;
+    let fraction = value - whole;
+    if fraction >= 0.5 {
+        whole + 1.0
+    } else if fraction <= -0.5 {
+        whole - 1.0
+    } else {
+        whole
+    }
+}
+
+/// The arithmetic mean of values, as used by [crate::stats]. `None` if there are none.
+///
+/// Primitive integers average exactly (without overflow), rounding half away from zero. Floats
+/// average in `f64`.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` values can't be averaged",
+    label = "no `repr::Mean` for `{Self}`",
+    note = "`repr::Mean` is implemented for primitive numbers; implement it for your own `Repr`"
+)]
+pub trait Mean: Sized {
+    fn mean(values: impl ExactSizeIterator<Item = Self>) -> Option<Self>;
+}
+
+// The mean is `quotient + remainder / count`. Summing the quotients of each value (and carrying
+// the remainders over) stays within the range of the mean, so it doesn't overflow.
+macro_rules! impl_mean_int {
+    (@unsigned $($t:ty)*) => {
+        $(
+            impl Mean for $t {
+                fn mean(values: impl ExactSizeIterator<Item = Self>) -> Option<Self> {
+                    if values.len() == 0 {
+                        return None;
+                    }
+                    let count = values.len() as u128;
+                    let (mut quotient, mut remainder) = (0u128, 0u128);
+                    for value in values {
+                        let value = value as u128;
+                        quotient += value / count + (remainder + value % count) / count;
+                        remainder = (remainder + value % count) % count;
+                    }
+                    if remainder != 0 && remainder >= count - remainder {
+                        quotient += 1;
+                    }
+                    Some(quotient as $t)
+                }
+            }
+        )*
+    };
+    (@signed $($t:ty)*) => {
+        $(
+            impl Mean for $t {
+                fn mean(values: impl ExactSizeIterator<Item = Self>) -> Option<Self> {
+                    if values.len() == 0 {
+                        return None;
+                    }
+                    let count = values.len() as i128;
+                    let (mut quotient, mut remainder) = (0i128, 0i128);
+                    for value in values {
+                        let value = value as i128;
+                        quotient += value / count + (remainder + value % count) / count;
+                        remainder = (remainder + value % count) % count;
+                    }
+                    // Give the remainder the sign of the mean, then round it away from zero.
+                    if quotient > 0 && remainder < 0 {
+                        quotient -= 1;
+                        remainder += count;
+                    } else if quotient < 0 && remainder > 0 {
+                        quotient += 1;
+                        remainder -= count;
+                    }
+                    if remainder.abs() >= count - remainder.abs() && remainder != 0 {
+                        quotient += remainder.signum();
+                    }
+                    Some(quotient as $t)
+                }
+            }
+        )*
+    };
+}
+impl_mean_int!(@unsigned u8 u16 u32 u64 u128 usize);
+impl_mean_int!(@signed i8 i16 i32 i64 i128 isize);
+
+macro_rules! impl_mean_float {
+    ($($t:ty)*) => {
+        $(
+            impl Mean for $t {
+                fn mean(values: impl ExactSizeIterator<Item = Self>) -> Option<Self> {
+                    let count = values.len();
+                    if count == 0 {
+                        return None;
+                    }
+                    let sum: f64 = values.map(f64::from).sum();
+                    Some((sum / count as f64) as $t)
+                }
+            }
+        )*
+    };
+}
+impl_mean_float!(f32 f64);
+
+/// Multiplication and division of `Self` by a factor of type `S`, giving `Self`, as used by
+/// [crate::Scalar].
+///
+/// Implemented for primitive integers by themselves, by narrower integers that convert losslessly
+/// (like `u128` by `u64`), and by `f32` and `f64`; and for floats by themselves. An integer scaled
+/// by a whole `f32` or `f64` that fits in it is computed exactly (dividing rounds half away from
+/// zero). Otherwise it's computed in `f64` and rounded (see [FromF64]), so integers beyond 2^53
+/// lose precision.
+///
+/// Division by zero depends on the divisor's type. An integer divided by an integer `0` panics,
+/// like `/` of integers does. An integer divided by `0.0` saturates instead, through the infinity
+/// it gives in `f64`: to `MAX` if positive, to `MIN` if negative, and `0` stays `0` (from NaN).
+/// Floats divided by `0.0` give an infinity or NaN, as usual.
+#[diagnostic::on_unimplemented(
+    message = "a `{Self}` amount can't be scaled by `{S}`",
+    label = "no `repr::Scale<{S}>` for `{Self}`",
+    note = "a `Scalar` factor must convert losslessly to the `Repr` (or be `f32`/`f64` for an integer `Repr`)"
+)]
+pub trait Scale<S>: Sized {
+    fn scale_mul(self, factor: S) -> Self;
+    fn scale_div(self, divisor: S) -> Self;
+}
+
+macro_rules! impl_scale_same {
+    ($($t:ty)*) => {
+        $(
+            impl Scale<$t> for $t {
+                fn scale_mul(self, factor: $t) -> Self {
+                    self * factor
+                }
+                fn scale_div(self, divisor: $t) -> Self {
+                    self / divisor
+                }
+            }
+        )*
+    };
+}
+impl_scale_same!(u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize f32 f64);
+
+macro_rules! impl_scale_lossless {
+    ($($scalar:ty => $($t:ty)*;)*) => {
+        $($(
+            impl Scale<$scalar> for $t {
+                fn scale_mul(self, factor: $scalar) -> Self {
+                    self * <$t>::from(factor)
+                }
+                fn scale_div(self, divisor: $scalar) -> Self {
+                    self / <$t>::from(divisor)
+                }
+            }
+        )*)*
+    };
+}
+// The same pairs as `core`'s lossless `From` between integers.
+impl_scale_lossless! {
+    u8 => u16 u32 u64 u128 usize i16 i32 i64 i128 isize;
+    u16 => u32 u64 u128 usize i32 i64 i128;
+    u32 => u64 u128 i64 i128;
+    u64 => u128 i128;
+    i8 => i16 i32 i64 i128 isize;
+    i16 => i32 i64 i128 isize;
+    i32 => i64 i128;
+    i64 => i128;
+}
+
+macro_rules! impl_scale_float {
+    ($($t:ty => $abs:expr, $negative:expr;)*) => {
+        $(
+            impl Scale<f64> for $t {
+                fn scale_mul(self, factor: f64) -> Self {
+                    let whole = factor as $t;
+                    // `MAX as f64` rounds up, so a factor equal to it saturated.
+                    if factor < <$t>::MAX as f64 && whole as f64 == factor {
+                        if let Some(product) = self.checked_mul(whole) {
+                            return product;
+                        }
+                    }
+                    Self::from_f64(self.to_f64() * factor)
+                }
+                fn scale_div(self, divisor: f64) -> Self {
+                    let whole = divisor as $t;
+                    if divisor < <$t>::MAX as f64 && whole as f64 == divisor {
+                        if let Some(quotient) = self.checked_div(whole) {
+                            let abs: fn($t) -> _ = $abs;
+                            let negative: fn($t) -> bool = $negative;
+                            let remainder = abs(self % whole);
+                            if remainder == 0 || remainder < abs(whole) - remainder {
+                                return quotient;
+                            }
+                            // Away from zero. `whole` is at least 2 apart from zero, so this fits.
+                            return if negative(self) != negative(whole) {
+                                quotient - 1
+                            } else {
+                                quotient + 1
+                            };
+                        }
+                    }
+                    Self::from_f64(self.to_f64() / divisor)
+                }
+            }
+            impl Scale<f32> for $t {
+                fn scale_mul(self, factor: f32) -> Self {
+                    self.scale_mul(f64::from(factor))
+                }
+                fn scale_div(self, divisor: f32) -> Self {
+                    self.scale_div(f64::from(divisor))
+                }
+            }
+        )*
+    };
+}
+impl_scale_float! {
+    u8 => |x| x, |_| false;
+    u16 => |x| x, |_| false;
+    u32 => |x| x, |_| false;
+    u64 => |x| x, |_| false;
+    u128 => |x| x, |_| false;
+    usize => |x| x, |_| false;
+    i8 => i8::unsigned_abs, |x| x < 0;
+    i16 => i16::unsigned_abs, |x| x < 0;
+    i32 => i32::unsigned_abs, |x| x < 0;
+    i64 => i64::unsigned_abs, |x| x < 0;
+    i128 => i128::unsigned_abs, |x| x < 0;
+    isize => isize::unsigned_abs, |x| x < 0;
+}
+
+/// Multiplication by a ratio, with an intermediate product of twice the width (so it doesn't
+/// overflow), as used by [crate::Fraction].
+///
+/// Implemented for primitive integers up to 64 bits (and `usize` and `isize`). Not for `u128` and
+/// `i128`, which have no wider type.
+#[diagnostic::on_unimplemented(
+    message = "a `{Self}` amount can't be multiplied by a fraction",
+    label = "no `repr::MulDiv` for `{Self}`",
+    note = "`repr::MulDiv` is implemented for primitive integers up to 64 bits"
+)]
+pub trait MulDiv: Copy {
+    /// `self * numerator / denominator`, rounding toward zero. `None` if `denominator` is zero or
+    /// the result doesn't fit in `Self`.
+    fn mul_div(self, numerator: Self, denominator: Self) -> Option<Self>;
+}
+
+macro_rules! impl_mul_div {
+    ($($t:ty => $wide:ty;)*) => {
+        $(
+            impl MulDiv for $t {
+                fn mul_div(self, numerator: Self, denominator: Self) -> Option<Self> {
+                    if denominator == 0 {
+                        return None;
+                    }
+                    let product = self as $wide * numerator as $wide;
+                    <$t>::try_from(product / denominator as $wide).ok()
+                }
+            }
+        )*
+    };
+}
+impl_mul_div! {
+    u8 => u16;
+    u16 => u32;
+    u32 => u64;
+    u64 => u128;
+    usize => u128;
+    i8 => i16;
+    i16 => i32;
+    i32 => i64;
+    i64 => i128;
+    isize => i128;
+}
+
+/// A float `Repr` that is `Eq`, `Ord` and `Hash`, ordered by `total_cmp` (so `-0.0 < 0.0`, and NaNs
+/// order at the ends). Then float amounts can be sorted and be `BTreeMap` keys. See
+/// [crate::AmountTotalOrd].
+///
+/// Arithmetic operators pass through to the float.
+///
+/// ```
+/// #![cfg_attr(
+///     feature = "unstable_generic_const_own_type",
+///     feature(generic_const_exprs)
+/// )]
+///
+/// use phantom_newtype::repr::TotalOrd;
+/// use phantom_newtype::AmountTotalOrd;
+/// use std::collections::BTreeMap;
+///
+/// enum Celsius {}
+/// type Temperature = AmountTotalOrd<Celsius, f64>;
+///
+/// let mut readings = BTreeMap::new();
+/// readings.insert(Temperature::from(TotalOrd(21.5)), "kitchen");
+/// readings.insert(Temperature::from(TotalOrd(-3.0)), "garden");
+/// assert_eq!(readings.values().collect::<Vec<_>>(), [&"garden", &"kitchen"]);
+///
+/// let warmer = Temperature::from(TotalOrd(-3.0)) + Temperature::from(TotalOrd(0.5));
+/// assert_eq!(warmer.get().0, -2.5);
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TotalOrd<F>(pub F);
+
+impl<F: fmt::Display> fmt::Display for TotalOrd<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+macro_rules! impl_total_ord {
+    ($($float:ident)*) => {
+        $(
+            impl PartialEq for TotalOrd<$float> {
+                fn eq(&self, other: &Self) -> bool {
+                    self.cmp(other) == core::cmp::Ordering::Equal
+                }
+            }
+
+            impl Eq for TotalOrd<$float> {}
+
+            impl PartialOrd for TotalOrd<$float> {
+                fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+                    Some(self.cmp(other))
+                }
+            }
+
+            impl Ord for TotalOrd<$float> {
+                fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+                    self.0.total_cmp(&other.0)
+                }
+            }
+
+            impl core::hash::Hash for TotalOrd<$float> {
+                fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+                    // Consistent with `total_cmp`: equal exactly if the bits are equal.
+                    self.0.to_bits().hash(state)
+                }
+            }
+
+            impl From<$float> for TotalOrd<$float> {
+                fn from(value: $float) -> Self {
+                    Self(value)
+                }
+            }
+
+            impl_total_ord!(@ops $float: Add add AddAssign add_assign, Sub sub SubAssign sub_assign,
+                Mul mul MulAssign mul_assign, Div div DivAssign div_assign);
+
+            impl core::ops::Neg for TotalOrd<$float> {
+                type Output = Self;
+
+                fn neg(self) -> Self {
+                    Self(-self.0)
+                }
+            }
+        )*
+    };
+    (@ops $float:ident: $($op:ident $method:ident $op_assign:ident $method_assign:ident),*) => {
+        $(
+            impl core::ops::$op for TotalOrd<$float> {
+                type Output = Self;
+
+                fn $method(self, rhs: Self) -> Self {
+                    Self(core::ops::$op::$method(self.0, rhs.0))
+                }
+            }
+
+            impl core::ops::$op_assign for TotalOrd<$float> {
+                fn $method_assign(&mut self, rhs: Self) {
+                    core::ops::$op_assign::$method_assign(&mut self.0, rhs.0)
+                }
+            }
+        )*
+    };
+}
+impl_total_ord!(f32 f64);
+
+impl_const_default!(TotalOrd<f32> => TotalOrd(0.0), TotalOrd<f64> => TotalOrd(0.0));
+
+/// A secret `Repr`, like an API key or a session token, as used by [crate::IdRedacted]. It has no
+/// `Debug`, `Display` or other formatting (nor `serde`) impls, so the id's `Debug` and `Display`
+/// print `<redacted Entity>` instead. Access the secret intentionally through the public field.
+/// With feature `zeroize`, it is `Zeroize` and `ZeroizeOnDrop` if `R` is.
+///
+/// ```
+/// #![cfg_attr(
+///     feature = "unstable_generic_const_own_type",
+///     feature(generic_const_exprs)
+/// )]
+///
+/// use phantom_newtype::repr::Redacted;
+/// use phantom_newtype::IdRedacted;
+///
+/// enum ApiKey {}
+/// type Key = IdRedacted<ApiKey, &'static str>;
+///
+/// #[derive(Debug)]
+/// struct Client {
+///     name: &'static str,
+///     key: Key,
+/// }
+///
+/// let client = Client { name: "billing", key: Key::from(Redacted("sk-123")) };
+/// assert_eq!(
+///     format!("{:?}", client),
+///     r#"Client { name: "billing", key: <redacted ApiKey> }"#
+/// );
+/// assert_eq!(client.key.to_string(), "<redacted ApiKey>");
+/// assert_eq!(client.key.get().0, "sk-123");
+/// ```
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Redacted<R>(pub R);
+
+impl<R: ConstDefault> ConstDefault for Redacted<R> {
+    const DEFAULT: Self = Redacted(R::DEFAULT);
+}
+
+#[cfg(feature = "zeroize")]
+impl<R: zeroize::Zeroize> zeroize::Zeroize for Redacted<R> {
+    fn zeroize(&mut self) {
+        self.0.zeroize()
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<R: zeroize::ZeroizeOnDrop> zeroize::ZeroizeOnDrop for Redacted<R> {}
+
+/// A byte `Repr` (like `[u8; 32]` or `Vec<u8>`) whose `==` takes the same time wherever the bytes
+/// differ (using `subtle::ConstantTimeEq`), as used by [crate::IdCtEq]. Use it for tokens and
+/// credentials compared in authentication paths. Only the length may leak, so prefer fixed-size
+/// arrays. It has no `PartialOrd` or `Hash`, since those can't be constant-time. Requires feature
+/// `subtle`.
+///
+/// Combine it with [Redacted] (`IdRedacted<Entity, CtEq<[u8; 32]>>`) to keep the token out of logs,
+/// too.
+///
+/// ```
+/// #![cfg_attr(
+///     feature = "unstable_generic_const_own_type",
+///     feature(generic_const_exprs)
+/// )]
+///
+/// use phantom_newtype::repr::CtEq;
+/// use phantom_newtype::IdCtEq;
+///
+/// enum Session {}
+/// type SessionToken = IdCtEq<Session, [u8; 4]>;
+///
+/// let stored = SessionToken::from(CtEq(*b"abcd"));
+/// assert!(stored == SessionToken::from(CtEq(*b"abcd")));
+/// assert!(stored != SessionToken::from(CtEq(*b"abce")));
+/// ```
+#[cfg(feature = "subtle")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CtEq<R>(pub R);
+
+#[cfg(feature = "subtle")]
+impl<R: AsRef<[u8]>> AsRef<[u8]> for CtEq<R> {
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_ref()
+    }
+}
+
+#[cfg(feature = "subtle")]
+impl<R: AsRef<[u8]>> subtle::ConstantTimeEq for CtEq<R> {
+    fn ct_eq(&self, other: &Self) -> subtle::Choice {
+        self.0.as_ref().ct_eq(other.0.as_ref())
+    }
+}
+
+#[cfg(feature = "subtle")]
+impl<R: AsRef<[u8]>> PartialEq for CtEq<R> {
+    fn eq(&self, other: &Self) -> bool {
+        subtle::ConstantTimeEq::ct_eq(self, other).into()
+    }
+}
+
+#[cfg(feature = "subtle")]
+impl<R: AsRef<[u8]>> Eq for CtEq<R> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TWO_POW_52: u64 = 1 << 52;
+
+    #[test]
+    fn from_f64_rounds_half_away_from_zero() {
+        assert_eq!(u8::from_f64(0.49999999999999994), 0);
+        assert_eq!(i8::from_f64(-0.49999999999999994), 0);
+        assert_eq!(u8::from_f64(0.5), 1);
+        assert_eq!(i8::from_f64(-0.5), -1);
+        assert_eq!(i8::from_f64(-2.5), -3);
+        assert_eq!(u64::from_f64((TWO_POW_52 - 1) as f64 + 0.5), TWO_POW_52);
+        assert_eq!(u64::from_f64(TWO_POW_52 as f64 - 0.25), TWO_POW_52);
+        assert_eq!(u64::from_f64((TWO_POW_52 + 1) as f64), TWO_POW_52 + 1);
+        assert_eq!(
+            i64::from_f64(-((TWO_POW_52 + 1) as f64)),
+            -(TWO_POW_52 as i64 + 1)
+        );
+        assert_eq!(u64::from_f64(f64::NAN), 0);
+        assert_eq!(u8::from_f64(1e300), u8::MAX);
+        assert_eq!(i8::from_f64(f64::NEG_INFINITY), i8::MIN);
+    }
+
+    #[test]
+    fn scale_by_whole_float_is_exact() {
+        let big = 10u128.pow(20) + 1;
+        assert_eq!(big.scale_mul(1.0), big);
+        assert_eq!(big.scale_mul(3.0f32), 3 * big);
+        assert_eq!(big.scale_div(1.0), big);
+        assert_eq!((-(big as i128)).scale_mul(-1.0), big as i128);
+        assert_eq!(u128::MAX.scale_mul(2.0), u128::MAX);
+        assert_eq!(i128::MIN.scale_div(-1.0), i128::MAX);
+    }
+
+    #[test]
+    fn scale_div_by_whole_float_rounds_half_away_from_zero() {
+        assert_eq!(5u32.scale_div(2.0), 3);
+        assert_eq!(7u32.scale_div(3.0), 2);
+        assert_eq!(8u32.scale_div(3.0), 3);
+        assert_eq!((-5i32).scale_div(2.0), -3);
+        assert_eq!(5i32.scale_div(-2.0), -3);
+        assert_eq!((-5i32).scale_div(-2.0), 3);
+        assert_eq!((-7i32).scale_div(3.0), -2);
+        assert_eq!(u64::MAX.scale_div(2.0), u64::MAX / 2 + 1);
+    }
+
+    #[test]
+    fn scale_div_of_integer_by_zero_float_saturates() {
+        assert_eq!(7u32.scale_div(0.0), u32::MAX);
+        assert_eq!(7i32.scale_div(0.0f32), i32::MAX);
+        assert_eq!((-7i32).scale_div(0.0), i32::MIN);
+        assert_eq!(7i32.scale_div(-0.0), i32::MIN);
+        assert_eq!(0u64.scale_div(0.0), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn scale_div_of_integer_by_zero_integer_panics() {
+        let zero = core::hint::black_box(0u8);
+        let _ = 7u32.scale_div(zero);
+    }
+
+    #[test]
+    fn scale_by_fractional_float() {
+        assert_eq!(1_000u128.scale_mul(1.5), 1_500);
+        assert_eq!(1_000u128.scale_div(0.5), 2_000);
+        assert_eq!(3i32.scale_mul(0.5), 2);
+        assert_eq!((-3i32).scale_mul(0.5), -2);
+    }
+
+    #[test]
+    fn mean_of_integers_rounds_half_away_from_zero() {
+        extern crate std;
+        fn mean<T: Mean>(values: impl IntoIterator<Item = T>) -> Option<T> {
+            let values: std::vec::Vec<T> = values.into_iter().collect();
+            T::mean(values.into_iter())
+        }
+        assert_eq!(mean::<u8>([]), None);
+        assert_eq!(mean([1u8, 2]), Some(2));
+        assert_eq!(mean([1u8, 1, 2]), Some(1));
+        assert_eq!(mean([255u8; 300]), Some(255));
+        assert_eq!(mean([-1i8, -2]), Some(-2));
+        assert_eq!(mean([-1i8, -1, -2]), Some(-1));
+        assert_eq!(mean([i8::MIN, i8::MAX]), Some(-1));
+        assert_eq!(
+            mean([i128::MIN, i128::MIN, 3]),
+            Some(-113_427_455_640_312_821_154_458_202_477_256_070_484)
+        );
+        assert_eq!(mean([u128::MAX, u128::MAX - 1]), Some(u128::MAX));
+        assert_eq!(mean([-5i64, 8]), Some(2));
+        assert_eq!(mean([5i64, -8]), Some(-2));
+        assert_eq!(mean([0.5f32, 1.0]), Some(0.75));
+    }
+}
@@ -0,0 +1,88 @@
+// Copyright 2024 Peter Lyons Kehl
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! `schemars::JsonSchema` of [Amount]s, [Id]s and [Instant]s: the schema of their `Repr` (as they
+//! serialize as `Repr`), titled and named after the unit, like `UserId` for `Id<User, u64>`.
+//! Requires feature `schemars`.
+//!
+//! ```
+//! #![cfg_attr(
+//!     feature = "unstable_generic_const_own_type",
+//!     feature(generic_const_exprs)
+//! )]
+//!
+//! use phantom_newtype::{Amount, Id};
+//! use schemars::JsonSchema;
+//! use serde_json::json;
+//!
+//! enum User {}
+//! enum Cents {}
+//!
+//! #[derive(JsonSchema)]
+//! struct Payment {
+//!     payer: Id<User, u64>,
+//!     total: Amount<Cents, u32>,
+//! }
+//!
+//! let schema = schemars::schema_for!(Payment);
+//! assert_eq!(schema.get("properties").unwrap()["payer"], json!({"$ref": "#/$defs/UserId"}));
+//! assert_eq!(
+//!     schema.get("$defs").unwrap()["UserId"],
+//!     json!({"type": "integer", "format": "uint64", "minimum": 0, "title": "UserId"})
+//! );
+//! assert_eq!(schema.get("$defs").unwrap()["CentsAmount"]["title"], "CentsAmount");
+//! ```
+
+use crate::amount::Amount;
+use crate::displayer::unit_name;
+use crate::id::Id;
+use crate::instant::Instant;
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+use crate::trait_flag::TraitFlags;
+use alloc::borrow::Cow;
+use alloc::format;
+use schemars::{JsonSchema, Schema, SchemaGenerator};
+
+macro_rules! impl_json_schema {
+    ($($wrapper:ident)*) => {
+        $(
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<const TF: TraitFlags, Unit, Repr> JsonSchema for $wrapper<TF, Unit, Repr>
+            where
+                Repr: JsonSchema,
+            {
+                fn schema_name() -> Cow<'static, str> {
+                    format!("{}{}", unit_name::<Unit>(), stringify!($wrapper)).into()
+                }
+
+                fn schema_id() -> Cow<'static, str> {
+                    format!(
+                        "phantom_newtype::{}<{}, {}>",
+                        stringify!($wrapper),
+                        core::any::type_name::<Unit>(),
+                        Repr::schema_id()
+                    )
+                    .into()
+                }
+
+                fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+                    let mut schema = Repr::json_schema(generator);
+                    schema.insert("title".into(), Self::schema_name().into());
+                    schema
+                }
+            }
+        )*
+    };
+}
+impl_json_schema!(Amount Id Instant);
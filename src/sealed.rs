@@ -0,0 +1,104 @@
+// Copyright 2024 Peter Lyons Kehl
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! [crate::sealed_newtype]: newtypes whose value only a chosen scope can unwrap.
+
+/// Define a struct around an amount, id or instant (or any type) that hides its value, together
+/// with a trait that unwraps it. The struct has no `get()`; only code that can name the trait can
+/// `unseal()` it. So making the trait private to a module (like your storage layer) enforces that
+/// values are unwrapped only there, by the compiler.
+///
+/// Attributes (like `#[derive(...)]` and docs) apply to the struct. It is `From` its inner type,
+/// so anybody can seal a value.
+///
+/// ```
+/// #![cfg_attr(
+///     feature = "unstable_generic_const_own_type",
+///     feature(generic_const_exprs)
+/// )]
+///
+/// mod storage {
+///     use phantom_newtype::{sealed_newtype, Id};
+///
+///     pub enum User {}
+///
+///     sealed_newtype! {
+///         #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+///         pub struct UserId(Id<User, u64>);
+///         trait Unseal;
+///     }
+///
+///     pub fn row_key(user: UserId) -> u64 {
+///         user.unseal().get_copy()
+///     }
+/// }
+///
+/// use phantom_newtype::Id;
+///
+/// let user = storage::UserId::from(Id::from(7));
+/// assert_eq!(storage::row_key(user), 7);
+/// ```
+///
+/// Outside of `storage`, the value stays sealed:
+///
+/// ```compile_fail
+/// mod storage {
+///     use phantom_newtype::{sealed_newtype, Id};
+///
+///     pub enum User {}
+///
+///     sealed_newtype! {
+///         pub struct UserId(Id<User, u64>);
+///         trait Unseal;
+///     }
+/// }
+///
+/// use phantom_newtype::Id;
+///
+/// let user = storage::UserId::from(Id::from(7));
+/// let raw = user.unseal().get_copy();
+/// ```
+#[macro_export]
+macro_rules! sealed_newtype {
+    (
+        $(#[$attr:meta])*
+        $vis:vis struct $name:ident($inner:ty);
+        $unseal_vis:vis trait $unseal:ident;
+    ) => {
+        $(#[$attr])*
+        #[repr(transparent)]
+        $vis struct $name($inner);
+
+        impl ::core::convert::From<$inner> for $name {
+            fn from(inner: $inner) -> Self {
+                Self(inner)
+            }
+        }
+
+        #[doc = concat!("Unwrapping [", stringify!($name), "].")]
+        $unseal_vis trait $unseal {
+            fn unseal(&self) -> &$inner;
+            fn into_unsealed(self) -> $inner;
+        }
+
+        impl $unseal for $name {
+            fn unseal(&self) -> &$inner {
+                &self.0
+            }
+            fn into_unsealed(self) -> $inner {
+                self.0
+            }
+        }
+    };
+}
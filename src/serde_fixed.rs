@@ -0,0 +1,191 @@
+// Copyright 2024 Peter Lyons Kehl
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Byte-level guarantees of the serde impls, for code (like consensus or hashing) that depends on
+//! exact bytes. Requires feature `serde`.
+//!
+//! [crate::Amount]s, [crate::Id]s and [crate::Instant]s serialize exactly as their `Repr`, in any
+//! format: their `Serialize` forwards to `Repr` without a wrapping newtype struct, and their
+//! `Deserialize` accepts whatever `Repr` accepts. [SerializesAsRepr] states that at the type level.
+//! `tests/serde_formats.rs` checks it for JSON, CBOR, bincode and postcard.
+//!
+//! [FixedSerializedSize] gives the size in fixed-width binary formats: `bincode::serialize` of
+//! bincode 1.x (and bincode 2.x with `config::legacy()`). Formats with variable-length integers
+//! (postcard, CBOR) or text formats (JSON) don't have a fixed size, but their bytes still match
+//! those of `Repr`.
+//!
+//! ```
+//! #![cfg_attr(
+//!     feature = "unstable_generic_const_own_type",
+//!     feature(generic_const_exprs)
+//! )]
+//!
+//! use phantom_newtype::serde_fixed::{self, serialized_size};
+//! use phantom_newtype::{Amount, Id};
+//!
+//! enum Block {}
+//! enum Gas {}
+//!
+//! type BlockHash = Id<Block, [u8; 32]>;
+//! type GasUsed = Amount<Gas, u64>;
+//!
+//! // A buffer of the exact size.
+//! let buffer = [0u8; serialized_size::<(BlockHash, GasUsed)>()];
+//! assert_eq!(buffer.len(), 40);
+//!
+//! // Generic code can require a wrapper that serializes as its `Repr`.
+//! fn repr_size<W: serde_fixed::SerializesAsRepr>() -> usize
+//! where
+//!     W::Repr: serde_fixed::FixedSerializedSize,
+//! {
+//!     serialized_size::<W::Repr>()
+//! }
+//! assert_eq!(repr_size::<GasUsed>(), 8);
+//! ```
+//!
+//! With feature `unstable_generic_const_own_type` (and `generic_const_exprs`), the size is usable
+//! in generic code, too:
+//!
+//! ```
+//! #![cfg_attr(
+//!     feature = "unstable_generic_const_own_type",
+//!     feature(generic_const_exprs)
+//! )]
+//! #![cfg_attr(
+//!     feature = "unstable_generic_const_own_type",
+//!     allow(incomplete_features)
+//! )]
+//!
+//! #[cfg(feature = "unstable_generic_const_own_type")]
+//! fn zeroed<T>() -> [u8; phantom_newtype::serde_fixed::serialized_size::<T>()]
+//! where
+//!     T: phantom_newtype::serde_fixed::FixedSerializedSize,
+//!     [(); phantom_newtype::serde_fixed::serialized_size::<T>()]:,
+//! {
+//!     [0; phantom_newtype::serde_fixed::serialized_size::<T>()]
+//! }
+//!
+//! #[cfg(feature = "unstable_generic_const_own_type")]
+//! assert_eq!(zeroed::<phantom_newtype::Amount<(), u32>>().len(), 4);
+//! ```
+
+use crate::amount::Amount;
+use crate::id::Id;
+use crate::instant::Instant;
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+use crate::trait_flag::TraitFlags;
+
+/// The number of bytes that a value serializes into, in fixed-width binary formats (see the
+/// [module](self) documentation).
+///
+/// Implemented for primitive numbers, `bool`, `()`, arrays (up to 32, like serde's `Serialize`)
+/// and tuples (up to 6) of these, and for
+/// [crate::Amount]s, [crate::Id]s and [crate::Instant]s of these. Not for `char`, slices, `str` or
+/// collections, which are variable-length.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` has no fixed serialized size",
+    label = "no `serde_fixed::FixedSerializedSize` for `{Self}`",
+    note = "only primitive numbers, `bool`, and arrays, tuples and wrappers of these have a fixed size"
+)]
+pub trait FixedSerializedSize {
+    const SIZE: usize;
+}
+
+/// Like `<T as FixedSerializedSize>::SIZE`, but usable where a `const fn` reads better, like in
+/// array lengths.
+pub const fn serialized_size<T: FixedSerializedSize + ?Sized>() -> usize {
+    T::SIZE
+}
+
+macro_rules! impl_fixed_size {
+    ($($t:ty)*) => {
+        $(
+            impl FixedSerializedSize for $t {
+                const SIZE: usize = core::mem::size_of::<$t>();
+            }
+        )*
+    };
+}
+impl_fixed_size!(u8 u16 u32 u64 u128 i8 i16 i32 i64 i128 f32 f64 bool);
+
+impl FixedSerializedSize for () {
+    const SIZE: usize = 0;
+}
+
+macro_rules! impl_fixed_size_array {
+    ($($n:literal)*) => {
+        $(
+            impl<T: FixedSerializedSize> FixedSerializedSize for [T; $n] {
+                const SIZE: usize = T::SIZE * $n;
+            }
+        )*
+    };
+}
+// Arrays serialize as tuples, so without a length prefix. These are the lengths that serde
+// implements `Serialize` for.
+impl_fixed_size_array! {
+    0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16
+    17 18 19 20 21 22 23 24 25 26 27 28 29 30 31 32
+}
+
+macro_rules! impl_fixed_size_tuple {
+    ($(($($t:ident)+))*) => {
+        $(
+            impl<$($t: FixedSerializedSize),+> FixedSerializedSize for ($($t,)+) {
+                const SIZE: usize = 0 $(+ $t::SIZE)+;
+            }
+        )*
+    };
+}
+impl_fixed_size_tuple! {
+    (A)
+    (A B)
+    (A B C)
+    (A B C D)
+    (A B C D E)
+    (A B C D E F)
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// [crate::Amount], [crate::Id] or [crate::Instant]: it serializes and deserializes exactly as its
+/// [Self::Repr], in any serde format. Sealed.
+pub trait SerializesAsRepr: sealed::Sealed {
+    type Repr;
+}
+
+macro_rules! impl_serializes_as_repr {
+    ($($wrapper:ident)*) => {
+        $(
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<const TF: TraitFlags, Unit, Repr> sealed::Sealed for $wrapper<TF, Unit, Repr> {}
+
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<const TF: TraitFlags, Unit, Repr> SerializesAsRepr for $wrapper<TF, Unit, Repr> {
+                type Repr = Repr;
+            }
+
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<const TF: TraitFlags, Unit, Repr> FixedSerializedSize for $wrapper<TF, Unit, Repr>
+            where
+                Repr: FixedSerializedSize,
+            {
+                const SIZE: usize = Repr::SIZE;
+            }
+        )*
+    };
+}
+impl_serializes_as_repr!(Amount Id Instant);
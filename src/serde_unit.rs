@@ -0,0 +1,473 @@
+// Copyright 2024 Peter Lyons Kehl
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Unit-tagged serialization, for `#[serde(with = "phantom_newtype::serde_unit")]`. Requires
+//! feature `serde`.
+//!
+//! By default, [crate::Amount]s, [crate::Id]s and [crate::Instant]s serialize as their `Repr`
+//! only. With this adapter, they serialize as a map with one entry: the unit's
+//! [UnitName::NAME] and the value. That way persisted data records which unit it is in.
+//!
+//! When a unit is renamed, keep its old names in [UnitName::ALIASES]. Deserialization accepts
+//! them, so data persisted before the rename still loads (and it gets the new name once it's
+//! serialized again).
+//!
+//! ```
+//! #![cfg_attr(
+//!     feature = "unstable_generic_const_own_type",
+//!     feature(generic_const_exprs)
+//! )]
+//!
+//! use phantom_newtype::serde_unit::UnitName;
+//! use phantom_newtype::Amount;
+//! use serde::{Deserialize, Serialize};
+//!
+//! enum Meters {}
+//! impl UnitName for Meters {
+//!     const NAME: &'static str = "meters";
+//!     // This unit used to be called `Metres`.
+//!     const ALIASES: &'static [&'static str] = &["metres"];
+//! }
+//!
+//! #[derive(Serialize, Deserialize, PartialEq, Debug)]
+//! struct Run {
+//!     #[serde(with = "phantom_newtype::serde_unit")]
+//!     distance: Amount<Meters, u32>,
+//! }
+//!
+//! let run = Run { distance: Amount::from(5000) };
+//! let json = serde_json::to_string(&run).unwrap();
+//! assert_eq!(json, r#"{"distance":{"meters":5000}}"#);
+//! assert_eq!(serde_json::from_str::<Run>(&json).unwrap(), run);
+//!
+//! let legacy = r#"{"distance":{"metres":5000}}"#;
+//! assert_eq!(serde_json::from_str::<Run>(legacy).unwrap(), run);
+//! assert!(serde_json::from_str::<Run>(r#"{"distance":{"feet":5000}}"#).is_err());
+//! ```
+//!
+//! To match your JSON conventions, tags can be written in a different case (see [snake_case],
+//! [kebab_case]), or in any way with a [TagCase] of your own (see [cased]). Aliases get the
+//! same treatment.
+//!
+//! ```
+//! #![cfg_attr(
+//!     feature = "unstable_generic_const_own_type",
+//!     feature(generic_const_exprs)
+//! )]
+//!
+//! use core::fmt;
+//! use phantom_newtype::serde_unit::{TagCase, UnitName};
+//! use phantom_newtype::{Amount, Id};
+//! use serde::{Deserialize, Serialize};
+//!
+//! enum FileSize {}
+//! impl UnitName for FileSize {
+//!     const NAME: &'static str = "FileSize";
+//! }
+//! enum HTTPRequest {}
+//! impl UnitName for HTTPRequest {
+//!     const NAME: &'static str = "HTTPRequest";
+//! }
+//!
+//! /// Upper case, with a prefix.
+//! enum Prefixed {}
+//! impl TagCase for Prefixed {
+//!     fn write_tag<W: fmt::Write>(name: &str, out: &mut W) -> fmt::Result {
+//!         out.write_str("unit:")?;
+//!         name.chars().try_for_each(|c| out.write_char(c.to_ascii_uppercase()))
+//!     }
+//! }
+//!
+//! #[derive(Serialize, Deserialize, PartialEq, Debug)]
+//! struct Upload {
+//!     #[serde(with = "phantom_newtype::serde_unit::snake_case")]
+//!     size: Amount<FileSize, u64>,
+//!     #[serde(with = "phantom_newtype::serde_unit::kebab_case")]
+//!     request: Id<HTTPRequest, u32>,
+//!     #[serde(
+//!         serialize_with = "phantom_newtype::serde_unit::cased::serialize::<Prefixed, _, _>",
+//!         deserialize_with = "phantom_newtype::serde_unit::cased::deserialize::<Prefixed, _, _>"
+//!     )]
+//!     limit: Amount<FileSize, u64>,
+//! }
+//!
+//! let upload = Upload { size: Amount::from(10), request: Id::from(3), limit: Amount::from(99) };
+//! let json = serde_json::to_string(&upload).unwrap();
+//! assert_eq!(
+//!     json,
+//!     r#"{"size":{"file_size":10},"request":{"http-request":3},"limit":{"unit:FILESIZE":99}}"#
+//! );
+//! assert_eq!(serde_json::from_str::<Upload>(&json).unwrap(), upload);
+//! ```
+
+use crate::amount::Amount;
+use crate::id::Id;
+use crate::instant::Instant;
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+use crate::trait_flag::TraitFlags;
+use core::fmt;
+use core::marker::PhantomData;
+use serde::de::{self, DeserializeSeed, MapAccess, Visitor};
+use serde::ser::SerializeMap;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Names of a unit (or entity) in serialized data. See the module docs.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` has no name for unit-tagged serialization",
+    label = "no `UnitName` for `{Self}`",
+    note = "`serde_unit` needs `impl UnitName for {Self}`"
+)]
+pub trait UnitName {
+    /// The name to serialize with.
+    const NAME: &'static str;
+    /// Former names, still accepted when deserializing.
+    const ALIASES: &'static [&'static str] = &[];
+}
+
+/// Implements [UnitName] for a marker type. The name is the type's name, unless set with
+/// `#[unit_name(name = "...")]`. Each former name is given with `#[unit_name(alias = "...")]`.
+/// Requires feature `derive`.
+///
+/// ```
+/// use phantom_newtype::serde_unit::UnitName;
+///
+/// #[derive(UnitName)]
+/// #[unit_name(name = "meters", alias = "metres", alias = "m")]
+/// enum Meters {}
+///
+/// assert_eq!(Meters::NAME, "meters");
+/// assert_eq!(Meters::ALIASES, ["metres", "m"]);
+/// ```
+#[cfg(feature = "derive")]
+pub use phantom_newtype_derive::UnitName;
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// [crate::Amount], [crate::Id] or [crate::Instant]. Sealed.
+pub trait HasUnit: sealed::Sealed {
+    type Unit;
+}
+
+macro_rules! impl_has_unit {
+    ($($wrapper:ident)*) => {
+        $(
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<const TF: TraitFlags, Unit, Repr> sealed::Sealed for $wrapper<TF, Unit, Repr> {}
+
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<const TF: TraitFlags, Unit, Repr> HasUnit for $wrapper<TF, Unit, Repr> {
+                type Unit = Unit;
+            }
+        )*
+    };
+}
+impl_has_unit!(Amount Id Instant);
+
+/// How to write a unit's name (or alias) as the tag, like in a different case. See [AsIs],
+/// [SnakeCase] and [KebabCase], and implement it for your own conventions.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not a tag case",
+    label = "no `TagCase` for `{Self}`",
+    note = "use `AsIs`, `SnakeCase`, `KebabCase`, or implement `TagCase` for `{Self}`"
+)]
+pub trait TagCase {
+    fn write_tag<W: fmt::Write>(name: &str, out: &mut W) -> fmt::Result;
+}
+
+/// Tags are the names as they are. Used by [serialize] and [deserialize].
+pub enum AsIs {}
+
+impl TagCase for AsIs {
+    fn write_tag<W: fmt::Write>(name: &str, out: &mut W) -> fmt::Result {
+        out.write_str(name)
+    }
+}
+
+/// Tags are the names in `snake_case`, for example `FileSize` becomes `file_size`.
+pub enum SnakeCase {}
+
+impl TagCase for SnakeCase {
+    fn write_tag<W: fmt::Write>(name: &str, out: &mut W) -> fmt::Result {
+        write_lower_words(name, '_', out)
+    }
+}
+
+/// Tags are the names in `kebab-case`, for example `FileSize` becomes `file-size`.
+pub enum KebabCase {}
+
+impl TagCase for KebabCase {
+    fn write_tag<W: fmt::Write>(name: &str, out: &mut W) -> fmt::Result {
+        write_lower_words(name, '-', out)
+    }
+}
+
+/// Write the words of `name` in lower case, separated with `separator`. Words are separated by
+/// `_`, `-` or spaces (any run of them, and none are written at either end), or start with an upper
+/// case letter that follows a lower case letter or a digit (`fileSize`), or that is followed by a
+/// lower case letter after upper case (`HTTPCode`).
+fn write_lower_words<W: fmt::Write>(name: &str, separator: char, out: &mut W) -> fmt::Result {
+    let mut chars = name.chars().peekable();
+    let mut previous: Option<char> = None;
+    // Whether a separator goes before the next letter.
+    let mut separate = false;
+    while let Some(c) = chars.next() {
+        if c == '_' || c == '-' || c == ' ' {
+            separate |= previous.is_some();
+            previous = None;
+            continue;
+        }
+        if c.is_uppercase() {
+            separate |= match previous {
+                Some(p) if p.is_lowercase() || p.is_numeric() => true,
+                Some(p) if p.is_uppercase() => chars.peek().is_some_and(|n| n.is_lowercase()),
+                _ => false,
+            };
+        }
+        if separate {
+            out.write_char(separator)?;
+            separate = false;
+        }
+        for lower in c.to_lowercase() {
+            out.write_char(lower)?;
+        }
+        previous = Some(c);
+    }
+    Ok(())
+}
+
+/// A tag (as per `L`) of a name, written out by `Display`.
+struct Tag<L>(&'static str, PhantomData<L>);
+
+impl<L: TagCase> fmt::Display for Tag<L> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        L::write_tag(self.0, f)
+    }
+}
+
+impl<L: TagCase> Serialize for Tag<L> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<L: TagCase> Tag<L> {
+    /// Whether this tag equals `key`, without allocating.
+    fn matches(&self, key: &str) -> bool {
+        struct Matcher<'a> {
+            rest: &'a str,
+        }
+        impl fmt::Write for Matcher<'_> {
+            fn write_str(&mut self, s: &str) -> fmt::Result {
+                match self.rest.strip_prefix(s) {
+                    Some(rest) => {
+                        self.rest = rest;
+                        Ok(())
+                    }
+                    None => Err(fmt::Error),
+                }
+            }
+        }
+        let mut matcher = Matcher { rest: key };
+        L::write_tag(self.0, &mut matcher).is_ok() && matcher.rest.is_empty()
+    }
+}
+
+pub fn serialize<W, S>(value: &W, serializer: S) -> Result<S::Ok, S::Error>
+where
+    W: HasUnit + Serialize,
+    W::Unit: UnitName,
+    S: Serializer,
+{
+    cased::serialize::<AsIs, W, S>(value, serializer)
+}
+
+pub fn deserialize<'de, W, D>(deserializer: D) -> Result<W, D::Error>
+where
+    W: HasUnit + Deserialize<'de>,
+    W::Unit: UnitName,
+    D: Deserializer<'de>,
+{
+    cased::deserialize::<AsIs, W, D>(deserializer)
+}
+
+/// Like [serialize] and [deserialize], but with tags written as per a [TagCase] `L`. Use with
+/// `#[serde(serialize_with = "phantom_newtype::serde_unit::cased::serialize::<MyCase, _, _>")]`
+/// and the same for `deserialize_with`.
+pub mod cased {
+    use super::*;
+
+    pub fn serialize<L, W, S>(value: &W, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        L: TagCase,
+        W: HasUnit + Serialize,
+        W::Unit: UnitName,
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(Some(1))?;
+        map.serialize_entry(&Tag::<L>(W::Unit::NAME, PhantomData), value)?;
+        map.end()
+    }
+
+    pub fn deserialize<'de, L, W, D>(deserializer: D) -> Result<W, D::Error>
+    where
+        L: TagCase,
+        W: HasUnit + Deserialize<'de>,
+        W::Unit: UnitName,
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(TaggedVisitor::<L, W>(PhantomData))
+    }
+}
+
+macro_rules! casing_module {
+    ($($module:ident: $case:ident, $example:literal;)*) => {
+        $(
+            #[doc = concat!("[serialize] and [deserialize] with [", stringify!($case), "] tags (like `", $example, "`), for")]
+            #[doc = concat!("`#[serde(with = \"phantom_newtype::serde_unit::", stringify!($module), "\")]`.")]
+            pub mod $module {
+                use super::*;
+
+                pub fn serialize<W, S>(value: &W, serializer: S) -> Result<S::Ok, S::Error>
+                where
+                    W: HasUnit + Serialize,
+                    W::Unit: UnitName,
+                    S: Serializer,
+                {
+                    cased::serialize::<$case, W, S>(value, serializer)
+                }
+
+                pub fn deserialize<'de, W, D>(deserializer: D) -> Result<W, D::Error>
+                where
+                    W: HasUnit + Deserialize<'de>,
+                    W::Unit: UnitName,
+                    D: Deserializer<'de>,
+                {
+                    cased::deserialize::<$case, W, D>(deserializer)
+                }
+            }
+        )*
+    };
+}
+casing_module! {
+    snake_case: SnakeCase, "file_size";
+    kebab_case: KebabCase, "file-size";
+}
+
+struct TaggedVisitor<L, W>(PhantomData<(L, W)>);
+
+impl<'de, L, W> Visitor<'de> for TaggedVisitor<L, W>
+where
+    L: TagCase,
+    W: HasUnit + Deserialize<'de>,
+    W::Unit: UnitName,
+{
+    type Value = W;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "a map with a single entry keyed by {}",
+            UnitNames::<L, W::Unit>(PhantomData)
+        )
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<W, A::Error> {
+        if map
+            .next_key_seed(UnitNames::<L, W::Unit>(PhantomData))?
+            .is_none()
+        {
+            return Err(de::Error::invalid_length(0, &self));
+        }
+        let value = map.next_value()?;
+        if map.next_key::<de::IgnoredAny>()?.is_some() {
+            return Err(de::Error::invalid_length(2, &self));
+        }
+        Ok(value)
+    }
+}
+
+/// Accepts a key that is a tag (as per `L`) of one of the names of `Unit`.
+struct UnitNames<L, Unit>(PhantomData<(L, Unit)>);
+
+impl<L: TagCase, Unit: UnitName> UnitNames<L, Unit> {
+    fn tags() -> impl Iterator<Item = Tag<L>> {
+        core::iter::once(Unit::NAME)
+            .chain(Unit::ALIASES.iter().copied())
+            .map(|name| Tag(name, PhantomData))
+    }
+}
+
+impl<L: TagCase, Unit: UnitName> fmt::Display for UnitNames<L, Unit> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, tag) in Self::tags().enumerate() {
+            if i > 0 {
+                f.write_str(" or ")?;
+            }
+            write!(f, "`{}`", tag)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'de, L: TagCase, Unit: UnitName> DeserializeSeed<'de> for UnitNames<L, Unit> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_str(self)
+    }
+}
+
+impl<'de, L: TagCase, Unit: UnitName> Visitor<'de> for UnitNames<L, Unit> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unit name {}", self)
+    }
+
+    fn visit_str<E: de::Error>(self, key: &str) -> Result<(), E> {
+        if Self::tags().any(|tag| tag.matches(key)) {
+            Ok(())
+        } else {
+            Err(E::invalid_value(de::Unexpected::Str(key), &self))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::write_lower_words;
+    extern crate std;
+    use std::string::String;
+
+    fn snake(name: &str) -> String {
+        let mut out = String::new();
+        write_lower_words(name, '_', &mut out).unwrap();
+        out
+    }
+
+    #[test]
+    fn lower_words() {
+        assert_eq!(snake("FileSize"), "file_size");
+        assert_eq!(snake("HTTPRequest"), "http_request");
+        assert_eq!(snake("utf8Bytes"), "utf8_bytes");
+        assert_eq!(snake("file size"), "file_size");
+        assert_eq!(snake("File_Size"), "file_size");
+        assert_eq!(snake("file__size"), "file_size");
+        assert_eq!(snake("file_-Size"), "file_size");
+        assert_eq!(snake("_file_size_"), "file_size");
+        assert_eq!(snake("__"), "");
+    }
+}
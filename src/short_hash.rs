@@ -0,0 +1,169 @@
+// Copyright 2024 Peter Lyons Kehl
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Short, human-scannable display of long byte ids (hashes), like git's abbreviated commit
+//! hashes.
+//!
+//! Implement [ShortHashDisplay] for an `Entity` to display its ids (with any `Repr` that is
+//! `AsRef<[u8]>`) by their first few hex digits. When listing many ids, use
+//! [unambiguous_hex_len] to find how many digits are needed to tell them apart, and
+//! [crate::Id::short_hash] to display them with that many digits.
+//!
+//! ```
+//! #![cfg_attr(
+//!     feature = "unstable_generic_const_own_type",
+//!     feature(generic_const_exprs)
+//! )]
+//!
+//! use phantom_newtype::short_hash::{self, ShortHashDisplay};
+//!
+//! enum Commit {}
+//! impl ShortHashDisplay for Commit {}
+//!
+//! #[cfg(not(feature = "unstable_generic_const_own_type"))]
+//! type CommitId = phantom_newtype::Id<Commit, [u8; 20]>;
+//! #[cfg(feature = "unstable_generic_const_own_type")]
+//! type CommitId = phantom_newtype::IdForFlags<{phantom_newtype::trait_flag::TRAIT_FLAGS_NO_COPY_NO_DEFAULT}, Commit, [u8; 20]>;
+//!
+//! let mut a = [0u8; 20];
+//! a[..4].copy_from_slice(&[0xde, 0xad, 0xbe, 0xef]);
+//! let mut b = a;
+//! b[3] = 0xe0;
+//! let ids = [CommitId::from(a), CommitId::from(b)];
+//!
+//! assert_eq!(format!("{}", ids[0].display()), "deadbee");
+//! assert_eq!(short_hash::unambiguous_hex_len(&ids), Some(8));
+//! assert_eq!(format!("{}", ids[1].short_hash(8)), "deadbee0");
+//! ```
+
+use crate::displayer::DisplayerOf;
+use crate::id::Id;
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+use crate::trait_flag::TraitFlags;
+use core::fmt;
+
+/// Opt-in for an `Entity`: display its ids by their first [ShortHashDisplay::HEX_DIGITS] hex
+/// digits.
+#[diagnostic::on_unimplemented(
+    message = "entity `{Self}` doesn't opt in to short hash display",
+    label = "no `ShortHashDisplay` for `{Self}`",
+    note = "opt in with `impl ShortHashDisplay for {Self} {{}}`"
+)]
+pub trait ShortHashDisplay {
+    /// Number of hex digits displayed by [crate::Id::display]. Git's default is 7.
+    const HEX_DIGITS: usize = 7;
+}
+
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+impl<const TF: TraitFlags, Entity: ShortHashDisplay, Repr: AsRef<[u8]>>
+    DisplayerOf<Id<TF, Entity, Repr>> for Entity
+{
+    fn display(id: &Id<TF, Entity, Repr>, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&ShortHash::new(id.get().as_ref(), Entity::HEX_DIGITS), f)
+    }
+}
+
+/// Displays (at most) the first `hex_digits` lowercase hex digits of bytes. Returned by
+/// [crate::Id::short_hash].
+#[derive(Clone, Copy, Debug)]
+pub struct ShortHash<'a> {
+    bytes: &'a [u8],
+    hex_digits: usize,
+}
+
+impl<'a> ShortHash<'a> {
+    pub fn new(bytes: &'a [u8], hex_digits: usize) -> Self {
+        Self { bytes, hex_digits }
+    }
+}
+
+impl fmt::Display for ShortHash<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let hex_digits = self.hex_digits.min(self.bytes.len() * 2);
+        self.bytes[..hex_digits / 2]
+            .iter()
+            .try_for_each(|b| write!(f, "{:02x}", b))?;
+        if hex_digits % 2 == 1 {
+            write!(f, "{:x}", self.bytes[hex_digits / 2] >> 4)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+impl<const TF: TraitFlags, Entity, Repr: AsRef<[u8]>> Id<TF, Entity, Repr> {
+    /// Display the first `hex_digits` hex digits of the id, regardless of
+    /// [ShortHashDisplay::HEX_DIGITS].
+    pub fn short_hash(&self, hex_digits: usize) -> ShortHash<'_> {
+        ShortHash::new(self.get().as_ref(), hex_digits)
+    }
+}
+
+/// Number of hex digits shared at the start of `a` and `b`.
+fn common_hex_prefix(a: &[u8], b: &[u8]) -> usize {
+    let mut digits = 0;
+    for (a, b) in a.iter().zip(b) {
+        if a == b {
+            digits += 2;
+        } else {
+            if a >> 4 == b >> 4 {
+                digits += 1;
+            }
+            break;
+        }
+    }
+    digits
+}
+
+/// The minimal number of hex digits such that the short forms of all `ids` differ (at least 1).
+///
+/// Return `None` if some ids collide: they are equal, or one is a prefix of another, so no short
+/// form tells them apart. This compares every pair, so it is meant for lists shown to humans,
+/// rather than for huge collections.
+///
+/// ```
+/// #![cfg_attr(
+///     feature = "unstable_generic_const_own_type",
+///     feature(generic_const_exprs)
+/// )]
+///
+/// use phantom_newtype::{short_hash, Id};
+///
+/// enum Blob {}
+/// type BlobId = Id<Blob, [u8; 2]>;
+///
+/// let ids = [BlobId::from([0x12, 0x34]), BlobId::from([0x13, 0x00])];
+/// assert_eq!(short_hash::unambiguous_hex_len(&ids), Some(2));
+///
+/// let colliding = [BlobId::from([0x12, 0x34]), BlobId::from([0x12, 0x34])];
+/// assert_eq!(short_hash::unambiguous_hex_len(&colliding), None);
+/// ```
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+pub fn unambiguous_hex_len<const TF: TraitFlags, Entity, Repr: AsRef<[u8]>>(
+    ids: &[Id<TF, Entity, Repr>],
+) -> Option<usize> {
+    let mut needed = 1;
+    for (i, a) in ids.iter().enumerate() {
+        let a = a.get().as_ref();
+        for b in &ids[i + 1..] {
+            let b = b.get().as_ref();
+            let common = common_hex_prefix(a, b);
+            if common >= a.len().min(b.len()) * 2 {
+                return None;
+            }
+            needed = needed.max(common + 1);
+        }
+    }
+    Some(needed)
+}
@@ -0,0 +1,100 @@
+// Copyright 2024 Peter Lyons Kehl
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Bulk arithmetic over slices of [Amount]s on `core::simd`. Requires feature `unstable_simd`
+//! (and nightly).
+//!
+//! ```
+//! #![cfg_attr(
+//!     feature = "unstable_generic_const_own_type",
+//!     feature(generic_const_exprs)
+//! )]
+//!
+//! use phantom_newtype::Amount;
+//!
+//! enum Cents {}
+//! type Balance = Amount<Cents, i64>;
+//!
+//! let mut balances = [100, 200, 300, 400, 500].map(Balance::from);
+//! let deposits = [1, 2, 3, 4, 5].map(Balance::from);
+//! Balance::add_slices(&mut balances, &deposits);
+//! assert_eq!(balances, [101, 202, 303, 404, 505].map(Balance::from));
+//!
+//! Balance::sub_slices(&mut balances, &deposits);
+//! assert_eq!(balances, [100, 200, 300, 400, 500].map(Balance::from));
+//! ```
+
+use crate::amount::Amount;
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+use crate::trait_flag::TraitFlags;
+use core::simd::{Mask, Simd, SimdElement};
+
+/// Applies `op` to `dst` and `src`, `LANES` items at a time, and stores the results in `dst`. The
+/// remainder is padded with `T::default()`.
+fn zip_lanes<T: SimdElement + Default, const LANES: usize>(
+    dst: &mut [T],
+    src: &[T],
+    op: impl Fn(Simd<T, LANES>, Simd<T, LANES>) -> Simd<T, LANES>,
+) {
+    assert_eq!(dst.len(), src.len(), "slices of different lengths");
+    let mut dst = dst.chunks_exact_mut(LANES);
+    let mut src = src.chunks_exact(LANES);
+    for (dst, src) in (&mut dst).zip(&mut src) {
+        op(Simd::from_slice(dst), Simd::from_slice(src)).copy_to_slice(dst);
+    }
+    let (dst, src) = (dst.into_remainder(), src.remainder());
+    // Stores only the lanes within `dst`.
+    op(Simd::load_or_default(dst), Simd::load_or_default(src)).store_select(dst, Mask::splat(true));
+}
+
+macro_rules! impl_simd {
+    ($($t:ty => $lanes:literal)*) => {
+        $(
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<const TF: TraitFlags, Unit> Amount<TF, Unit, $t> {
+                /// Adds each amount of `src` to the respective amount of `dst`, with SIMD.
+                /// Integers wrap around on overflow (even in debug builds).
+                ///
+                /// # Panics
+                /// If `dst` and `src` have different lengths.
+                pub fn add_slices(dst: &mut [Self], src: &[Self]) {
+                    zip_lanes::<$t, $lanes>(
+                        Self::peel_slice_mut(dst),
+                        Self::peel_slice(src),
+                        |a, b| a + b,
+                    );
+                }
+
+                /// Subtracts each amount of `src` from the respective amount of `dst`, with SIMD.
+                /// Integers wrap around on overflow (even in debug builds).
+                ///
+                /// # Panics
+                /// If `dst` and `src` have different lengths.
+                pub fn sub_slices(dst: &mut [Self], src: &[Self]) {
+                    zip_lanes::<$t, $lanes>(
+                        Self::peel_slice_mut(dst),
+                        Self::peel_slice(src),
+                        |a, b| a - b,
+                    );
+                }
+            }
+        )*
+    };
+}
+// 256-bit vectors.
+impl_simd!(
+    u8 => 32 u16 => 16 u32 => 8 u64 => 4 usize => 4
+    i8 => 32 i16 => 16 i32 => 8 i64 => 4 isize => 4
+    f32 => 8 f64 => 4
+);
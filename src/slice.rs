@@ -0,0 +1,123 @@
+// Copyright 2024 Peter Lyons Kehl
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Zero-cost reinterpretation of slices (and, with feature `alloc`, `Vec`s) of `Repr` as slices of
+//! [crate::Amount]s, [crate::Id]s or [crate::Instant]s, and back.
+//!
+//! All of them are `#[repr(transparent)]` over `Repr` (their only other field is a zero-sized
+//! `PhantomData`), so `[Repr]` and `[Wrapper]` have the same layout. See also [crate::layout].
+
+use crate::amount::Amount;
+use crate::id::Id;
+use crate::instant::Instant;
+use crate::to::transmute_unchecked;
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+use crate::trait_flag::TraitFlags;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+#[cfg(feature = "alloc")]
+use core::mem::ManuallyDrop;
+
+macro_rules! impl_slices {
+    ($($wrapper:ident)*) => {
+        $(
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<const TF: TraitFlags, Unit, Repr> $wrapper<TF, Unit, Repr> {
+                /// View a slice of `Repr` as a slice of `Self`, without copying.
+                ///
+                /// ```
+                /// #![cfg_attr(
+                ///     feature = "unstable_generic_const_own_type",
+                ///     feature(generic_const_exprs)
+                /// )]
+                ///
+                #[doc = concat!("use phantom_newtype::", stringify!($wrapper), ";")]
+                ///
+                /// enum Unit {}
+                #[doc = concat!("type T = ", stringify!($wrapper), "<Unit, u32>;")]
+                ///
+                /// let mut raw = [1u32, 2, 3];
+                /// assert_eq!(T::wrap_slice(&raw), [T::from(1), T::from(2), T::from(3)]);
+                ///
+                /// T::wrap_slice_mut(&mut raw)[0] = T::from(10);
+                /// assert_eq!(T::peel_slice(T::wrap_slice(&raw)), [10, 2, 3]);
+                /// ```
+                pub const fn wrap_slice(slice: &[Repr]) -> &[Self] {
+                    // SAFETY: `Self` is `#[repr(transparent)]` over `Repr`.
+                    unsafe { transmute_unchecked(slice) }
+                }
+
+                /// View a mutable slice of `Repr` as a mutable slice of `Self`, without copying.
+                pub fn wrap_slice_mut(slice: &mut [Repr]) -> &mut [Self] {
+                    // SAFETY: `Self` is `#[repr(transparent)]` over `Repr`.
+                    unsafe { transmute_unchecked(slice) }
+                }
+
+                /// The inverse of `wrap_slice`.
+                pub const fn peel_slice(slice: &[Self]) -> &[Repr] {
+                    // SAFETY: `Self` is `#[repr(transparent)]` over `Repr`.
+                    unsafe { transmute_unchecked(slice) }
+                }
+
+                /// The inverse of `wrap_slice_mut`.
+                pub fn peel_slice_mut(slice: &mut [Self]) -> &mut [Repr] {
+                    // SAFETY: `Self` is `#[repr(transparent)]` over `Repr`.
+                    unsafe { transmute_unchecked(slice) }
+                }
+
+                /// Turn a `Vec` of `Repr` into a `Vec` of `Self`, reusing its buffer. Requires
+                /// feature `alloc`.
+                ///
+                /// ```
+                /// #![cfg_attr(
+                ///     feature = "unstable_generic_const_own_type",
+                ///     feature(generic_const_exprs)
+                /// )]
+                ///
+                /// # #[cfg(feature = "alloc")] {
+                #[doc = concat!("use phantom_newtype::", stringify!($wrapper), ";")]
+                ///
+                /// enum Unit {}
+                #[doc = concat!("type T = ", stringify!($wrapper), "<Unit, u32>;")]
+                ///
+                /// let raw = vec![1u32, 2, 3];
+                /// let ptr = raw.as_ptr();
+                /// let wrapped = T::wrap_vec(raw);
+                /// assert_eq!(wrapped, [T::from(1), T::from(2), T::from(3)]);
+                ///
+                /// let peeled = T::peel_vec(wrapped);
+                /// assert_eq!(peeled.as_ptr(), ptr);
+                /// # }
+                /// ```
+                #[cfg(feature = "alloc")]
+                pub fn wrap_vec(vec: Vec<Repr>) -> Vec<Self> {
+                    let mut vec = ManuallyDrop::new(vec);
+                    // SAFETY: `Self` is `#[repr(transparent)]` over `Repr`, so the allocation has
+                    // the right size and alignment for `Self`, and the elements are valid.
+                    unsafe { Vec::from_raw_parts(vec.as_mut_ptr().cast(), vec.len(), vec.capacity()) }
+                }
+
+                /// The inverse of `wrap_vec`. Requires feature `alloc`.
+                #[cfg(feature = "alloc")]
+                pub fn peel_vec(vec: Vec<Self>) -> Vec<Repr> {
+                    let mut vec = ManuallyDrop::new(vec);
+                    // SAFETY: `Self` is `#[repr(transparent)]` over `Repr`, so the allocation has
+                    // the right size and alignment for `Repr`, and the elements are valid.
+                    unsafe { Vec::from_raw_parts(vec.as_mut_ptr().cast(), vec.len(), vec.capacity()) }
+                }
+            }
+        )*
+    };
+}
+impl_slices!(Amount Id Instant);
@@ -0,0 +1,145 @@
+// Copyright 2024 Peter Lyons Kehl
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Order-preserving byte encoding of [crate::Amount]s, [crate::Id]s and [crate::Instant]s, for
+//! use as keys in ordered key-value stores. See [crate::repr::SortableBytes].
+
+use crate::amount::Amount;
+use crate::id::Id;
+use crate::instant::Instant;
+use crate::repr::SortableBytes;
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+use crate::trait_flag::TraitFlags;
+use core::convert::TryFrom;
+
+// Each wrapper comes with an expression getting the `Repr` by value (`Id::get` returns a reference).
+macro_rules! impl_sortable_bytes {
+    ($($wrapper:ident($value:ident => $get:expr);)*) => {
+        $(
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<const TF: TraitFlags, Unit, Repr: SortableBytes + Copy> $wrapper<TF, Unit, Repr> {
+                /// Encode so that the byte-wise (lexicographic) order of encodings matches the
+                /// order of values.
+                ///
+                /// ```
+                /// #![cfg_attr(
+                ///     feature = "unstable_generic_const_own_type",
+                ///     feature(generic_const_exprs)
+                /// )]
+                ///
+                #[doc = concat!("use phantom_newtype::", stringify!($wrapper), ";")]
+                ///
+                /// enum Unit {}
+                #[doc = concat!("type T = ", stringify!($wrapper), "<Unit, i32>;")]
+                ///
+                /// let (a, b) = (T::from(-3), T::from(2));
+                /// assert!(a.to_sortable_bytes() < b.to_sortable_bytes());
+                /// assert_eq!(T::from_sortable_bytes(a.to_sortable_bytes()), a);
+                /// assert_eq!(T::from_sortable_slice(&b.to_sortable_bytes()), Some(b));
+                /// assert_eq!(T::from_sortable_slice(&[0, 1]), None);
+                /// ```
+                pub fn to_sortable_bytes(&self) -> Repr::Bytes {
+                    let $value = self;
+                    $get.to_sortable_bytes()
+                }
+
+                /// Decode the result of `to_sortable_bytes`.
+                pub fn from_sortable_bytes(bytes: Repr::Bytes) -> Self {
+                    Self::new(Repr::from_sortable_bytes(bytes))
+                }
+
+                /// Decode the result of `to_sortable_bytes`, for example a key read from a
+                /// key-value store. Return `None` if `bytes` has a wrong length.
+                pub fn from_sortable_slice(bytes: &[u8]) -> Option<Self> {
+                    Repr::Bytes::try_from(bytes)
+                        .ok()
+                        .map(Self::from_sortable_bytes)
+                }
+            }
+        )*
+    };
+}
+impl_sortable_bytes! {
+    Amount(value => *value.get());
+    Id(value => *value.get());
+    Instant(value => *value.get());
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repr::SortableBytes;
+    use core::fmt::Debug;
+
+    fn check<T: SortableBytes + Copy + Ord + Debug>(a: T, b: T)
+    where
+        T::Bytes: Debug,
+    {
+        let (ea, eb) = (a.to_sortable_bytes(), b.to_sortable_bytes());
+        assert_eq!(
+            a.cmp(&b),
+            ea.as_ref().cmp(eb.as_ref()),
+            "{:?} vs {:?}",
+            a,
+            b
+        );
+        assert_eq!(T::from_sortable_bytes(ea), a);
+    }
+
+    #[test]
+    fn exhaustive_8_bit() {
+        for a in 0..=u8::MAX {
+            for b in 0..=u8::MAX {
+                check(a, b);
+                check(a as i8, b as i8);
+            }
+        }
+    }
+
+    /// Pseudo-random pairs (from a fixed-seed xorshift), plus extremes, for wider integers.
+    #[test]
+    fn sampled_wide() {
+        let mut state = 0x2545_f491_4f6c_dd1du64;
+        let mut next = || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+        let extremes = [0, 1, u64::MAX, u64::MAX / 2, u64::MAX / 2 + 1];
+        for _ in 0..10_000 {
+            let (a, b) = (next(), next());
+            // Shifting keeps small magnitudes (and thus sign changes near zero) well covered.
+            let (a16, b16) = ((a >> 48) as u16, (b >> (b % 64)) as u16);
+            check(a16, b16);
+            check(a16 as i16, b16 as i16);
+            check(a as u32, (b >> (a % 64)) as u32);
+            check(a as i32, (b >> (a % 64)) as i32);
+            check(a, b >> (a % 64));
+            check(a as i64, (b >> (a % 64)) as i64);
+            check(u128::from(a) << 64 | u128::from(b), u128::from(b));
+            check(
+                (u128::from(a) << 64 | u128::from(b)) as i128,
+                b as i64 as i128,
+            );
+            check(a as usize, b as usize);
+            check(a as isize, (b >> (a % 64)) as isize);
+        }
+        for &a in &extremes {
+            for &b in &extremes {
+                check(a, b);
+                check(a as i64, b as i64);
+            }
+        }
+    }
+}
@@ -0,0 +1,90 @@
+// Copyright 2024 Peter Lyons Kehl
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! `sqlx` `Type`, `Encode` and `Decode` of [Amount]s, [Id]s and [Instant]s: they delegate to
+//! `Repr`, so that a wrapper binds into queries and decodes from rows like its `Repr`. Requires
+//! feature `sqlx`.
+
+use crate::amount::Amount;
+use crate::id::Id;
+use crate::instant::Instant;
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+use crate::trait_flag::TraitFlags;
+use sqlx_core::database::Database;
+use sqlx_core::decode::Decode;
+use sqlx_core::encode::{Encode, IsNull};
+use sqlx_core::error::BoxDynError;
+use sqlx_core::types::Type;
+
+macro_rules! impl_sqlx {
+    ($($wrapper:ident)*) => {
+        $(
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<const TF: TraitFlags, Unit, Repr, DB> Type<DB> for $wrapper<TF, Unit, Repr>
+            where
+                DB: Database,
+                Repr: Type<DB>,
+            {
+                fn type_info() -> DB::TypeInfo {
+                    Repr::type_info()
+                }
+
+                fn compatible(ty: &DB::TypeInfo) -> bool {
+                    Repr::compatible(ty)
+                }
+            }
+
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<'q, const TF: TraitFlags, Unit, Repr, DB> Encode<'q, DB> for $wrapper<TF, Unit, Repr>
+            where
+                DB: Database,
+                Repr: Encode<'q, DB>,
+            {
+                fn encode(
+                    self,
+                    buf: &mut <DB as Database>::ArgumentBuffer<'q>,
+                ) -> Result<IsNull, BoxDynError> {
+                    self.0.encode(buf)
+                }
+
+                fn encode_by_ref(
+                    &self,
+                    buf: &mut <DB as Database>::ArgumentBuffer<'q>,
+                ) -> Result<IsNull, BoxDynError> {
+                    self.0.encode_by_ref(buf)
+                }
+
+                fn produces(&self) -> Option<DB::TypeInfo> {
+                    self.0.produces()
+                }
+
+                fn size_hint(&self) -> usize {
+                    self.0.size_hint()
+                }
+            }
+
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<'r, const TF: TraitFlags, Unit, Repr, DB> Decode<'r, DB> for $wrapper<TF, Unit, Repr>
+            where
+                DB: Database,
+                Repr: Decode<'r, DB>,
+            {
+                fn decode(value: <DB as Database>::ValueRef<'r>) -> Result<Self, BoxDynError> {
+                    Repr::decode(value).map($wrapper::new)
+                }
+            }
+        )*
+    };
+}
+impl_sqlx!(Amount Id Instant);
@@ -0,0 +1,321 @@
+// Copyright 2024 Peter Lyons Kehl
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Descriptive statistics of slices of [Amount]s, keeping the unit. Requires feature `stats`.
+//!
+//! [mean] and [median] average with [Mean], which is exact for integers. The others compute in
+//! `f64` (see [ToF64] and [FromF64]). All return `None` for an empty slice.
+//! [median] and [percentile] reorder the slice (instead of allocating a sorted copy).
+//!
+//! ```
+//! #![cfg_attr(
+//!     feature = "unstable_generic_const_own_type",
+//!     feature(generic_const_exprs)
+//! )]
+//!
+//! use phantom_newtype::{stats, Amount, Squared};
+//!
+//! enum Millis {}
+//! type Latency = Amount<Millis, u32>;
+//!
+//! let mut latencies = [2, 4, 4, 4, 5, 5, 7, 9].map(Latency::from);
+//!
+//! assert_eq!(stats::mean(&latencies), Some(Latency::from(5)));
+//! let variance: Amount<Squared<Millis>, u32> = stats::variance(&latencies).unwrap();
+//! assert_eq!(variance.get_copy(), 4);
+//! assert_eq!(stats::stddev(&latencies), Some(Latency::from(2)));
+//! assert_eq!(stats::median(&mut latencies), Some(Latency::from(5)));
+//! assert_eq!(stats::percentile(&mut latencies, 90.0), Some(Latency::from(9)));
+//! let none: &[Latency] = &[];
+//! assert_eq!(stats::mean(none), None);
+//! ```
+
+use crate::amount::{Amount, Squared};
+use crate::repr::{FromF64, Mean, ToF64};
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+use crate::trait_flag::TraitFlags;
+use core::cmp::Ordering;
+
+/// Arithmetic mean.
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+pub fn mean<const TF: TraitFlags, Unit, Repr: Mean + Copy>(
+    amounts: &[Amount<TF, Unit, Repr>],
+) -> Option<Amount<TF, Unit, Repr>> {
+    mean_repr(amounts).map(Amount::new)
+}
+
+/// Population variance (the mean of squared differences from the mean).
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+pub fn variance<const TF: TraitFlags, Unit, Repr: ToF64 + FromF64>(
+    amounts: &[Amount<TF, Unit, Repr>],
+) -> Option<Amount<TF, Squared<Unit>, Repr>> {
+    variance_f64(amounts).map(|variance| Amount::new(Repr::from_f64(variance)))
+}
+
+/// Population standard deviation (the square root of [variance]).
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+pub fn stddev<const TF: TraitFlags, Unit, Repr: ToF64 + FromF64>(
+    amounts: &[Amount<TF, Unit, Repr>],
+) -> Option<Amount<TF, Unit, Repr>> {
+    variance_f64(amounts).map(|variance| Amount::new(Repr::from_f64(sqrt(variance))))
+}
+
+/// Median: the middle amount, or the mean of the two middle amounts if their count is even.
+/// Reorders `amounts`.
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+pub fn median<const TF: TraitFlags, Unit, Repr: Mean + Copy + PartialOrd>(
+    amounts: &mut [Amount<TF, Unit, Repr>],
+) -> Option<Amount<TF, Unit, Repr>> {
+    let len = amounts.len();
+    if len == 0 {
+        return None;
+    }
+    let (lower, upper, _) = amounts.select_nth_unstable_by(len / 2, compare);
+    if len % 2 == 1 {
+        return Some(Amount::new(upper.get_copy()));
+    }
+    // The lower middle is the greatest of the lower half.
+    let lower = lower.iter().max_by(|a, b| compare(a, b)).unwrap();
+    Repr::mean(IntoIterator::into_iter([
+        lower.get_copy(),
+        upper.get_copy(),
+    ]))
+    .map(Amount::new)
+}
+
+/// The smallest amount that is greater than or equal to `percent` % of `amounts` (the
+/// nearest-rank method). `percent` is clamped to `0.0..=100.0`. Reorders `amounts`.
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+pub fn percentile<const TF: TraitFlags, Unit, Repr: Copy + PartialOrd>(
+    amounts: &mut [Amount<TF, Unit, Repr>],
+    percent: f64,
+) -> Option<Amount<TF, Unit, Repr>> {
+    let len = amounts.len();
+    if len == 0 {
+        return None;
+    }
+    // The rank is `position` rounded up (`f64::ceil` is not in `core`), and is 1-based.
+    let position = percent.clamp(0.0, 100.0) / 100.0 * len as f64;
+    let truncated = position as usize;
+    let rank = if (truncated as f64) < position {
+        truncated + 1
+    } else {
+        truncated
+    };
+    let index = rank.saturating_sub(1);
+    let (_, nth, _) = amounts.select_nth_unstable_by(index.min(len - 1), compare);
+    Some(Amount::new(nth.get_copy()))
+}
+
+/// Mean of the last (up to) `N` amounts pushed, for smoothing a series of measurements. A ring
+/// buffer, so it doesn't allocate.
+///
+/// ```
+/// #![cfg_attr(
+///     feature = "unstable_generic_const_own_type",
+///     feature(generic_const_exprs)
+/// )]
+///
+/// use phantom_newtype::stats::MovingAverage;
+/// use phantom_newtype::Amount;
+///
+/// enum Millis {}
+/// type Latency = Amount<Millis, u32>;
+///
+/// let mut smoothed = MovingAverage::<Millis, u32, 3>::new();
+/// assert_eq!(smoothed.average(), None);
+/// assert_eq!(smoothed.push(Latency::from(10)), Latency::from(10));
+/// assert_eq!(smoothed.push(Latency::from(20)), Latency::from(15));
+/// assert_eq!(smoothed.push(Latency::from(30)), Latency::from(20));
+/// // 10 leaves the window.
+/// assert_eq!(smoothed.push(Latency::from(100)), Latency::from(50));
+/// assert!(smoothed.is_full());
+/// ```
+pub struct MovingAverage<Unit, Repr, const N: usize> {
+    window: [crate::Amount<Unit, Repr>; N],
+    // Number of amounts in `window`, up to `N`.
+    len: usize,
+    // Where the next amount goes.
+    next: usize,
+}
+
+impl<Unit, Repr: Mean + Copy + Default, const N: usize> MovingAverage<Unit, Repr, N> {
+    const NON_EMPTY: () = assert!(N > 0, "the window must not be empty");
+
+    pub fn new() -> Self {
+        // Fails to compile if `N` is zero.
+        #[allow(clippy::let_unit_value)]
+        let () = Self::NON_EMPTY;
+        Self {
+            window: core::array::from_fn(|_| crate::Amount::new(Repr::default())),
+            len: 0,
+            next: 0,
+        }
+    }
+
+    /// Add `amount` (replacing the oldest one once there are `N`), and return the new average.
+    pub fn push(&mut self, amount: crate::Amount<Unit, Repr>) -> crate::Amount<Unit, Repr> {
+        self.window[self.next] = amount;
+        self.next = (self.next + 1) % N;
+        self.len = (self.len + 1).min(N);
+        // Recomputed (rather than kept as a running sum), so that float rounding doesn't drift.
+        crate::Amount::new(mean_repr(self.amounts()).unwrap())
+    }
+
+    /// The average of the amounts in the window, or `None` if none have been pushed.
+    pub fn average(&self) -> Option<crate::Amount<Unit, Repr>> {
+        mean_repr(self.amounts()).map(crate::Amount::new)
+    }
+
+    /// The amounts in the window, in no particular order.
+    pub fn amounts(&self) -> &[crate::Amount<Unit, Repr>] {
+        &self.window[..self.len]
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Whether the window has `N` amounts, so that each push replaces the oldest one.
+    pub fn is_full(&self) -> bool {
+        self.len == N
+    }
+
+    pub fn clear(&mut self) {
+        self.len = 0;
+        self.next = 0;
+    }
+}
+
+impl<Unit, Repr: Mean + Copy + Default, const N: usize> Default for MovingAverage<Unit, Repr, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+fn compare<const TF: TraitFlags, Unit, Repr: Copy + PartialOrd>(
+    a: &Amount<TF, Unit, Repr>,
+    b: &Amount<TF, Unit, Repr>,
+) -> Ordering {
+    // Incomparable values (NaN) are treated as equal, so they don't break the selection.
+    a.get().partial_cmp(b.get()).unwrap_or(Ordering::Equal)
+}
+
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+fn mean_repr<const TF: TraitFlags, Unit, Repr: Mean + Copy>(
+    amounts: &[Amount<TF, Unit, Repr>],
+) -> Option<Repr> {
+    Repr::mean(amounts.iter().map(Amount::get_copy))
+}
+
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+fn mean_f64<const TF: TraitFlags, Unit, Repr: ToF64>(
+    amounts: &[Amount<TF, Unit, Repr>],
+) -> Option<f64> {
+    if amounts.is_empty() {
+        return None;
+    }
+    let sum: f64 = amounts.iter().map(|amount| amount.get().to_f64()).sum();
+    Some(sum / amounts.len() as f64)
+}
+
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+fn variance_f64<const TF: TraitFlags, Unit, Repr: ToF64>(
+    amounts: &[Amount<TF, Unit, Repr>],
+) -> Option<f64> {
+    let mean = mean_f64(amounts)?;
+    let sum: f64 = amounts
+        .iter()
+        .map(|amount| {
+            let difference = amount.get().to_f64() - mean;
+            difference * difference
+        })
+        .sum();
+    Some(sum / amounts.len() as f64)
+}
+
+/// `f64::sqrt` is not in `core`. Newton's method, for non-negative `value`.
+fn sqrt(value: f64) -> f64 {
+    if value == 0.0 || !value.is_finite() {
+        return value;
+    }
+    // Start at or above the root, so that the iterations decrease until they converge.
+    let mut root = value.max(1.0);
+    loop {
+        let next = (root + value / root) / 2.0;
+        if next >= root {
+            return root;
+        }
+        root = next;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sqrt_matches_std() {
+        extern crate std;
+        for value in [0.0, 0.25, 1.0, 2.0, 4.0, 1e-10, 12345.678, 1e300] {
+            let expected = std::primitive::f64::sqrt(value);
+            assert!(
+                (sqrt(value) - expected).abs() <= expected * 1e-15,
+                "{}",
+                value
+            );
+        }
+    }
+
+    #[test]
+    fn percentile_nearest_rank() {
+        enum Unit {}
+        let mut amounts = [15, 20, 35, 40, 50].map(crate::Amount::<Unit, u8>::from);
+        for (percent, expected) in [
+            (0.0, 15),
+            (5.0, 15),
+            (30.0, 20),
+            (40.0, 20),
+            (50.0, 35),
+            (100.0, 50),
+        ] {
+            assert_eq!(
+                percentile(&mut amounts, percent).unwrap().get_copy(),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn integer_means_are_exact() {
+        enum Unit {}
+        let big = [(1u64 << 53) + 1, (1u64 << 53) + 3].map(crate::Amount::<Unit, u64>::from);
+        assert_eq!(mean(&big).unwrap().get_copy(), (1 << 53) + 2);
+        let mut extremes = [i128::MAX, i128::MAX - 2].map(crate::Amount::<Unit, i128>::from);
+        assert_eq!(mean(&extremes).unwrap().get_copy(), i128::MAX - 1);
+        assert_eq!(median(&mut extremes).unwrap().get_copy(), i128::MAX - 1);
+
+        let mut window = MovingAverage::<Unit, u64, 2>::new();
+        window.push(crate::Amount::from(u64::MAX));
+        assert_eq!(
+            window.push(crate::Amount::from(u64::MAX - 1)).get_copy(),
+            u64::MAX
+        );
+    }
+}
@@ -0,0 +1,444 @@
+// Copyright 2024 Peter Lyons Kehl
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Units of time, and interop of [Instant]s and [Amount]s with time types.
+//!
+//! A unit marker implementing [TimeUnit] has a fixed length. Its [Amount]s of `u64` or `u128`
+//! convert from and to `core::time::Duration` (with `TryFrom`, as they may be out of range).
+//!
+//! A [TimeUnit] implementing [EpochUnit] also counts [Instant]s from the Unix epoch, for example
+//! [UnixMillis]. Its [Instant]s
+//! - of `u64` or `u128` convert from and to `std::time::SystemTime` (the same, with feature
+//!   `std`),
+//! - of `i64` convert from and to `chrono::DateTime<Utc>` (with feature `chrono`) and
+//!   `time::OffsetDateTime` (with feature `time`), and
+//! - have `Instant::now()`, which reads the system clock (with feature `std`).
+//!
+//! Instants of an [EpochUnit] display as RFC 3339 timestamps (in UTC) with [Rfc3339Displayer],
+//! like `2023-11-14T22:13:20.123Z`. See [Instant::rfc3339].
+//!
+//! Other markers declare their clock source by implementing [ClockSource] themselves. See also
+//! [crate::clock], for clocks that are passed around (and replaced in tests).
+//!
+//! ```
+//! #![cfg_attr(
+//!     feature = "unstable_generic_const_own_type",
+//!     feature(generic_const_exprs)
+//! )]
+//!
+//! use core::convert::TryFrom;
+//! use core::time::Duration;
+//! use phantom_newtype::time::{UnixMillis, UnixSeconds};
+//! use phantom_newtype::{Amount, Instant};
+//!
+//! type Elapsed = Amount<UnixMillis, u64>;
+//!
+//! let timeout = Elapsed::try_from(Duration::from_secs(2)).unwrap();
+//! assert_eq!(timeout.get_copy(), 2_000);
+//! assert_eq!(Duration::try_from(timeout), Ok(Duration::from_secs(2)));
+//!
+//! #[cfg(feature = "std")]
+//! {
+//!     use std::time::{SystemTime, UNIX_EPOCH};
+//!
+//!     type Timestamp = Instant<UnixMillis, u64>;
+//!
+//!     let moment = UNIX_EPOCH + Duration::from_millis(1_700_000_000_123);
+//!     let timestamp = Timestamp::try_from(moment).unwrap();
+//!     assert_eq!(timestamp.get_copy(), 1_700_000_000_123);
+//!     assert_eq!(SystemTime::try_from(timestamp), Ok(moment));
+//!
+//!     let now = Instant::<UnixSeconds, u64>::now();
+//!     assert!(now.get_copy() > 1_700_000_000);
+//! }
+//! ```
+//!
+//! With `chrono` or `time`, instants of `i64` may also be before the epoch. Conversions into
+//! instants round down to a whole unit.
+//!
+//! ```
+//! #![cfg_attr(
+//!     feature = "unstable_generic_const_own_type",
+//!     feature(generic_const_exprs)
+//! )]
+//!
+//! use core::convert::TryFrom;
+//! use phantom_newtype::time::UnixSeconds;
+//! use phantom_newtype::Instant;
+//!
+//! type Timestamp = Instant<UnixSeconds, i64>;
+//!
+//! #[cfg(feature = "chrono")]
+//! {
+//!     use chrono::{DateTime, TimeZone, Utc};
+//!
+//!     let moon_landing = Utc.with_ymd_and_hms(1969, 7, 20, 20, 17, 40).unwrap();
+//!     let timestamp = Timestamp::try_from(moon_landing).unwrap();
+//!     assert_eq!(timestamp.get_copy(), -14_182_940);
+//!     assert_eq!(DateTime::<Utc>::try_from(timestamp), Ok(moon_landing));
+//! }
+//!
+//! #[cfg(feature = "time")]
+//! {
+//!     use time::{Date, Month, OffsetDateTime, Time};
+//!
+//!     let date = Date::from_calendar_date(1969, Month::July, 20).unwrap();
+//!     let moon_landing = date.with_time(Time::from_hms(20, 17, 40).unwrap()).assume_utc();
+//!     let timestamp = Timestamp::try_from(moon_landing).unwrap();
+//!     assert_eq!(timestamp.get_copy(), -14_182_940);
+//!     assert_eq!(OffsetDateTime::try_from(timestamp), Ok(moon_landing));
+//! }
+//! ```
+
+use crate::amount::Amount;
+use crate::displayer::{DisplayProxy, DisplayerOf};
+use crate::instant::Instant;
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+use crate::trait_flag::TraitFlags;
+use core::convert::TryFrom;
+use core::fmt;
+use core::time::Duration;
+#[cfg(feature = "std")]
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A unit of time of a fixed length. See the module docs.
+pub trait TimeUnit {
+    /// Length of the unit in nanoseconds. Not zero.
+    const NANOS_PER_UNIT: u64;
+}
+
+/// A [TimeUnit] whose [Instant]s count from the Unix epoch. See the module docs.
+///
+/// [Instant]: crate::Instant
+pub trait EpochUnit: TimeUnit {}
+
+/// Seconds, since the Unix epoch for [crate::Instant]s.
+pub enum UnixSeconds {}
+/// Milliseconds, since the Unix epoch for [crate::Instant]s.
+pub enum UnixMillis {}
+/// Microseconds, since the Unix epoch for [crate::Instant]s.
+pub enum UnixMicros {}
+/// Nanoseconds, since the Unix epoch for [crate::Instant]s.
+pub enum UnixNanos {}
+
+impl TimeUnit for UnixSeconds {
+    const NANOS_PER_UNIT: u64 = 1_000_000_000;
+}
+impl EpochUnit for UnixSeconds {}
+impl TimeUnit for UnixMillis {
+    const NANOS_PER_UNIT: u64 = 1_000_000;
+}
+impl EpochUnit for UnixMillis {}
+impl TimeUnit for UnixMicros {
+    const NANOS_PER_UNIT: u64 = 1_000;
+}
+impl EpochUnit for UnixMicros {}
+impl TimeUnit for UnixNanos {
+    const NANOS_PER_UNIT: u64 = 1;
+}
+impl EpochUnit for UnixNanos {}
+
+/// Where `Instant::now()` of a unit marker reads the time from. With feature `std`, implemented
+/// for all [EpochUnit]s (reading `SystemTime`) for `u64` and `u128`.
+pub trait ClockSource<Repr> {
+    fn now() -> Repr;
+}
+
+/// A time (or duration) that is before the Unix epoch, or that doesn't fit the target type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct TimeOutOfRange;
+
+impl fmt::Display for TimeOutOfRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("time out of range")
+    }
+}
+
+impl core::error::Error for TimeOutOfRange {}
+
+const NANOS_PER_SEC: i128 = 1_000_000_000;
+
+pub(crate) fn duration_to_units<Unit: TimeUnit>(duration: Duration) -> u128 {
+    duration.as_nanos() / u128::from(Unit::NANOS_PER_UNIT)
+}
+
+fn units_to_duration<Unit: TimeUnit>(units: u128) -> Result<Duration, TimeOutOfRange> {
+    let nanos = units
+        .checked_mul(u128::from(Unit::NANOS_PER_UNIT))
+        .ok_or(TimeOutOfRange)?;
+    let secs = u64::try_from(nanos / NANOS_PER_SEC as u128).map_err(|_| TimeOutOfRange)?;
+    Ok(Duration::new(secs, (nanos % NANOS_PER_SEC as u128) as u32))
+}
+
+// Signed counterparts, for `chrono` and `time`, rounding down (towards the past).
+#[cfg(any(feature = "chrono", feature = "time"))]
+fn nanos_to_units<Unit: TimeUnit>(nanos: i128) -> Result<i64, TimeOutOfRange> {
+    i64::try_from(nanos.div_euclid(i128::from(Unit::NANOS_PER_UNIT))).map_err(|_| TimeOutOfRange)
+}
+
+#[cfg(any(feature = "chrono", feature = "time"))]
+fn units_to_nanos<Unit: TimeUnit>(units: i64) -> i128 {
+    // Can't overflow: both factors are within 64 bits.
+    i128::from(units) * i128::from(Unit::NANOS_PER_UNIT)
+}
+
+/// A [DisplayerOf] instants of an [EpochUnit] that writes them as RFC 3339 timestamps in UTC,
+/// like `2023-11-14T22:13:20Z`. Fractions of a second have as many digits as the unit resolves
+/// (3 for [UnixMillis], 6 for [UnixMicros] and 9 for [UnixNanos]). Use it with
+/// [crate::DisplayProxy] or [crate::WithDisplayer], or through [Instant::rfc3339].
+///
+/// Years before 0 or after 9999 (which RFC 3339 can't express) are written with a sign, as in
+/// ISO 8601's expanded representation. Instants too far off to count their nanoseconds in `i128`
+/// fail to format.
+pub enum Rfc3339Displayer {}
+
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+impl<const TF: TraitFlags, Unit: EpochUnit, Repr: Copy> DisplayerOf<Instant<TF, Unit, Repr>>
+    for Rfc3339Displayer
+where
+    i128: From<Repr>,
+{
+    fn display(value: &Instant<TF, Unit, Repr>, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Not `i128::from`, which the bound on `Repr` shadows.
+        let nanos_per_unit = Unit::NANOS_PER_UNIT as i128;
+        let nanos = i128::from(value.0)
+            .checked_mul(nanos_per_unit)
+            .ok_or(fmt::Error)?;
+        let secs = nanos.div_euclid(NANOS_PER_SEC);
+        let subsec_nanos = nanos.rem_euclid(NANOS_PER_SEC);
+        let (year, month, day) = civil_from_days(secs.div_euclid(SECS_PER_DAY));
+        let secs_of_day = secs.rem_euclid(SECS_PER_DAY);
+        if (0..=9999).contains(&year) {
+            write!(f, "{:04}", year)?;
+        } else {
+            write!(f, "{:+}", year)?;
+        }
+        write!(
+            f,
+            "-{:02}-{:02}T{:02}:{:02}:{:02}",
+            month,
+            day,
+            secs_of_day / 3600,
+            secs_of_day / 60 % 60,
+            secs_of_day % 60
+        )?;
+        let digits = fraction_digits(Unit::NANOS_PER_UNIT);
+        if digits > 0 {
+            let fraction = subsec_nanos / 10i128.pow(9 - digits as u32);
+            write!(f, ".{:0width$}", fraction, width = digits)?;
+        }
+        f.write_str("Z")
+    }
+}
+
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+impl<const TF: TraitFlags, Unit: EpochUnit, Repr: Copy> Instant<TF, Unit, Repr>
+where
+    i128: From<Repr>,
+{
+    /// Display as an RFC 3339 timestamp in UTC. See [Rfc3339Displayer].
+    ///
+    /// ```
+    /// #![cfg_attr(
+    ///     feature = "unstable_generic_const_own_type",
+    ///     feature(generic_const_exprs)
+    /// )]
+    ///
+    /// use phantom_newtype::time::{UnixMillis, UnixSeconds};
+    /// use phantom_newtype::Instant;
+    ///
+    /// let created = Instant::<UnixSeconds, u64>::from(1_700_000_000);
+    /// assert_eq!(created.rfc3339().to_string(), "2023-11-14T22:13:20Z");
+    ///
+    /// let moon_landing = Instant::<UnixMillis, i64>::from(-14_182_939_500);
+    /// assert_eq!(moon_landing.rfc3339().to_string(), "1969-07-20T20:17:40.500Z");
+    /// ```
+    pub fn rfc3339(&self) -> DisplayProxy<'_, Self, Rfc3339Displayer> {
+        DisplayProxy::new(self)
+    }
+}
+
+const SECS_PER_DAY: i128 = 24 * 60 * 60;
+
+/// Number of decimal digits of a second that a unit of `nanos_per_unit` resolves.
+fn fraction_digits(nanos_per_unit: u64) -> usize {
+    match nanos_per_unit {
+        n if n % 1_000_000_000 == 0 => 0,
+        n if n % 1_000_000 == 0 => 3,
+        n if n % 1_000 == 0 => 6,
+        _ => 9,
+    }
+}
+
+/// Year, month and day of the proleptic Gregorian calendar, `days` after 1970-01-01. Howard
+/// Hinnant's `civil_from_days` (http://howardhinnant.github.io/date_algorithms.html).
+fn civil_from_days(days: i128) -> (i128, u8, u8) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    // Months from March.
+    let month_from_march = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_from_march + 2) / 5 + 1) as u8;
+    let month = if month_from_march < 10 {
+        month_from_march + 3
+    } else {
+        month_from_march - 9
+    } as u8;
+    let year = year_of_era + era * 400 + i128::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+impl<const TF: TraitFlags, Unit: ClockSource<Repr>, Repr> Instant<TF, Unit, Repr> {
+    /// The current time, from the `Unit`'s [ClockSource].
+    pub fn now() -> Self {
+        Self::new(Unit::now())
+    }
+}
+
+macro_rules! impl_unsigned_repr {
+    ($($repr:ty)*) => {
+        $(
+            #[cfg(feature = "std")]
+            impl<Unit: EpochUnit> ClockSource<$repr> for Unit {
+                /// # Panics
+                /// If the system clock is before the Unix epoch, or out of range of `Repr`.
+                fn now() -> $repr {
+                    let since_epoch = SystemTime::now()
+                        .duration_since(UNIX_EPOCH)
+                        .expect("system clock before the Unix epoch");
+                    <$repr>::try_from(duration_to_units::<Unit>(since_epoch))
+                        .expect("system clock out of range")
+                }
+            }
+
+            #[cfg(feature = "std")]
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<const TF: TraitFlags, Unit: EpochUnit> TryFrom<SystemTime>
+                for Instant<TF, Unit, $repr>
+            {
+                type Error = TimeOutOfRange;
+
+                /// Rounds down to a whole unit.
+                fn try_from(time: SystemTime) -> Result<Self, TimeOutOfRange> {
+                    let since_epoch = time.duration_since(UNIX_EPOCH).map_err(|_| TimeOutOfRange)?;
+                    <$repr>::try_from(duration_to_units::<Unit>(since_epoch))
+                        .map(Self::new)
+                        .map_err(|_| TimeOutOfRange)
+                }
+            }
+
+            #[cfg(feature = "std")]
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<const TF: TraitFlags, Unit: EpochUnit> TryFrom<Instant<TF, Unit, $repr>>
+                for SystemTime
+            {
+                type Error = TimeOutOfRange;
+
+                fn try_from(instant: Instant<TF, Unit, $repr>) -> Result<Self, TimeOutOfRange> {
+                    let since_epoch = units_to_duration::<Unit>(u128::from(instant.0))?;
+                    UNIX_EPOCH.checked_add(since_epoch).ok_or(TimeOutOfRange)
+                }
+            }
+
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<const TF: TraitFlags, Unit: TimeUnit> TryFrom<Duration>
+                for Amount<TF, Unit, $repr>
+            {
+                type Error = TimeOutOfRange;
+
+                /// Rounds down to a whole unit.
+                fn try_from(duration: Duration) -> Result<Self, TimeOutOfRange> {
+                    <$repr>::try_from(duration_to_units::<Unit>(duration))
+                        .map(Self::new)
+                        .map_err(|_| TimeOutOfRange)
+                }
+            }
+
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<const TF: TraitFlags, Unit: TimeUnit> TryFrom<Amount<TF, Unit, $repr>>
+                for Duration
+            {
+                type Error = TimeOutOfRange;
+
+                fn try_from(amount: Amount<TF, Unit, $repr>) -> Result<Self, TimeOutOfRange> {
+                    units_to_duration::<Unit>(u128::from(amount.0))
+                }
+            }
+        )*
+    };
+}
+impl_unsigned_repr!(u64 u128);
+
+#[cfg(feature = "chrono")]
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+impl<const TF: TraitFlags, Unit: EpochUnit> TryFrom<chrono::DateTime<chrono::Utc>>
+    for Instant<TF, Unit, i64>
+{
+    type Error = TimeOutOfRange;
+
+    /// Rounds down to a whole unit.
+    fn try_from(time: chrono::DateTime<chrono::Utc>) -> Result<Self, TimeOutOfRange> {
+        // `timestamp_subsec_nanos` exceeds a second during a leap second.
+        let nanos = i128::from(time.timestamp()) * NANOS_PER_SEC
+            + i128::from(time.timestamp_subsec_nanos());
+        nanos_to_units::<Unit>(nanos).map(Self::new)
+    }
+}
+
+#[cfg(feature = "chrono")]
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+impl<const TF: TraitFlags, Unit: EpochUnit> TryFrom<Instant<TF, Unit, i64>>
+    for chrono::DateTime<chrono::Utc>
+{
+    type Error = TimeOutOfRange;
+
+    fn try_from(instant: Instant<TF, Unit, i64>) -> Result<Self, TimeOutOfRange> {
+        let nanos = units_to_nanos::<Unit>(instant.0);
+        let secs = i64::try_from(nanos.div_euclid(NANOS_PER_SEC)).map_err(|_| TimeOutOfRange)?;
+        let subsec_nanos = nanos.rem_euclid(NANOS_PER_SEC) as u32;
+        chrono::DateTime::from_timestamp(secs, subsec_nanos).ok_or(TimeOutOfRange)
+    }
+}
+
+#[cfg(feature = "time")]
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+impl<const TF: TraitFlags, Unit: EpochUnit> TryFrom<::time::OffsetDateTime>
+    for Instant<TF, Unit, i64>
+{
+    type Error = TimeOutOfRange;
+
+    /// Rounds down to a whole unit.
+    fn try_from(time: ::time::OffsetDateTime) -> Result<Self, TimeOutOfRange> {
+        nanos_to_units::<Unit>(time.unix_timestamp_nanos()).map(Self::new)
+    }
+}
+
+#[cfg(feature = "time")]
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+impl<const TF: TraitFlags, Unit: EpochUnit> TryFrom<Instant<TF, Unit, i64>>
+    for ::time::OffsetDateTime
+{
+    type Error = TimeOutOfRange;
+
+    /// In UTC.
+    fn try_from(instant: Instant<TF, Unit, i64>) -> Result<Self, TimeOutOfRange> {
+        ::time::OffsetDateTime::from_unix_timestamp_nanos(units_to_nanos::<Unit>(instant.0))
+            .map_err(|_| TimeOutOfRange)
+    }
+}
@@ -12,33 +12,62 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use core::marker::PhantomData;
+//! Opt-in conversions between [crate::Amount]s, [crate::Id]s or [crate::Instant]s that differ only
+//! in their `Unit` (or `Entity`). They are zero-cost: the value, and even a reference to it, is
+//! reinterpreted rather than copied.
+//!
+//! Nothing converts unless you opt in, by implementing one of the indicator traits for the
+//! archetype, `Repr` and units involved:
+//! - [As] on the source type activates [To] (and [AsMut] activates [ToMut]),
+//! - [AsFrom] on the target type activates [ToFrom] (and [AsFromMut] activates [ToFromMut]).
+//!
+//! Use [AsFrom] when the source unit is not local to your crate (so you can't implement [As] for
+//! it), or when you want to opt in for a whole family of source units.
+//!
+//! ```
+//! #![cfg_attr(
+//!     feature = "unstable_generic_const_own_type",
+//!     feature(generic_const_exprs)
+//! )]
+//!
+//! use phantom_newtype::{Amount, As, To};
+//!
+//! enum Input {}
+//! enum Output {}
+//! // Any `Amount<Input, _>` may be treated as an `Amount<Output, _>`:
+//! impl<Repr> As<Output> for Amount<Input, Repr> {}
+//!
+//! let input = Amount::<Input, u32>::from(5);
+//! let output: &Amount<Output, u32> = input.to_ref();
+//! assert_eq!(output.get_copy(), 5);
+//! assert_eq!(To::<Output>::to(input), Amount::<Output, u32>::from(5));
+//! ```
+//!
+//! Without the opt-in, there is no conversion:
+//!
+//! ```compile_fail
+//! use phantom_newtype::{Amount, To};
+//!
+//! enum Input {}
+//! enum Output {}
+//!
+//! let output: Amount<Output, u32> = To::<Output>::to(Amount::<Input, u32>::from(5));
+//! ```
 
-/// @TODO
-/// - copied()
-/// - cloned() <-> Iterator::cloned(self) => then use: .ref().cloned()
-/// - into()
-/// - ref(), mut() - same as Deref/DerefMut, but shorter.
-///
-/// multiple From<xxx> are OK! Multiple Into<xxxx> are OK, too.
-///
-/// AsRef, Borrow, Deref, DerefMut
-
-fn _caller() {
-    // A caller creates a value as Out.
-    //
-    // But it enters a callee as In.
-    // -> <T: Into<xxx<In, yyy>>
-}
+use crate::amount::Amount;
+use crate::id::Id;
+use crate::instant::Instant;
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+use crate::trait_flag::TraitFlags;
 
 #[cfg(feature = "unstable_transmute_unchecked")]
-pub const unsafe fn transmute_unchecked<T, U>(x: T) -> U {
+pub(crate) const unsafe fn transmute_unchecked<T, U>(x: T) -> U {
     core::intrinsics::transmute_unchecked(x)
 }
 
 #[cfg(not(feature = "unstable_transmute_unchecked"))]
 /// Thanks to Helix (noop_noob).
-pub const unsafe fn transmute_unchecked<T, U>(x: T) -> U {
+pub(crate) const unsafe fn transmute_unchecked<T, U>(x: T) -> U {
     use core::mem::ManuallyDrop;
 
     union Transmuter<T, U> {
@@ -53,120 +82,348 @@ pub const unsafe fn transmute_unchecked<T, U>(x: T) -> U {
     )
 }
 
-/// Indicator trait that activates a blanket `impl` of [To].
+/// Indicator trait that activates a blanket `impl` of [To] for `Self`, converting to the same
+/// archetype, flags and `Repr`, but with unit `T`.
 ///
 /// This can't activate any blanket `impl` of [core::ops::Deref], because anything like the
-/// following fails to compile:
+/// following fails to compile (`O` is not constrained by the `impl`):
 /// ```compile_fail
-/// impl<T, Repr, O> Deref for Amm<T, Repr>
+/// use core::ops::Deref;
+/// use phantom_newtype::{Amount, As};
+///
+/// impl<T, Repr, O> Deref for Amount<T, Repr>
 /// where
-/// Self: As<O> {
-/// /// ...
+///     Self: As<O>,
+/// {
+///     type Target = Amount<O, Repr>;
+///     fn deref(&self) -> &Self::Target {
+///         unimplemented!()
+///     }
 /// }
 /// ```
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` doesn't opt in to conversion to unit `{T}`",
+    label = "no `As<{T}>` for `{Self}`",
+    note = "opt in with `impl As<{T}> for {Self} {{}}`, if the conversion keeps the meaning"
+)]
 pub trait As<T> {}
+/// Like [As], but for [ToMut].
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` doesn't opt in to mutable conversion to unit `{T}`",
+    label = "no `AsMut<{T}>` for `{Self}`",
+    note = "opt in with `impl AsMut<{T}> for {Self} {{}}`, if the conversion keeps the meaning"
+)]
 pub trait AsMut<T> {}
+/// Indicator trait that activates a blanket `impl` of [ToFrom] for the same archetype, flags and
+/// `Repr` as `Self`, but with unit `T`, converting to `Self`.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` doesn't opt in to conversion from unit `{T}`",
+    label = "no `AsFrom<{T}>` for `{Self}`",
+    note = "opt in with `impl AsFrom<{T}> for {Self} {{}}`, if the conversion keeps the meaning"
+)]
 pub trait AsFrom<T> {}
+/// Like [AsFrom], but for [ToFromMut].
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` doesn't opt in to mutable conversion from unit `{T}`",
+    label = "no `AsFromMut<{T}>` for `{Self}`",
+    note = "opt in with `impl AsFromMut<{T}> for {Self} {{}}`, if the conversion keeps the meaning"
+)]
 pub trait AsFromMut<T> {}
 
-#[derive(Copy, Clone)]
-pub struct Amm<T, Repr>(PhantomData<core::sync::atomic::AtomicPtr<T>>, Repr);
+/// Conversion to unit `O`, activated by [As].
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` can't be converted to unit `{O}`",
+    label = "no `To<{O}>` for `{Self}`",
+    note = "conversion between units is opt-in: implement `As<{O}>` for `{Self}`"
+)]
+pub trait To<O> {
+    /// The same archetype, flags and `Repr` as `Self`, with unit `O`.
+    type Output;
 
-pub trait To<O, Repr> {
-    fn to(self) -> Amm<O, Repr>;
-    fn to_ref(&self) -> &Amm<O, Repr>;
-}
-pub trait ToMut<O, Repr> {
-    fn to_mut(&mut self) -> &mut Amm<O, Repr>;
+    fn to(self) -> Self::Output;
+    fn to_ref(&self) -> &Self::Output;
 }
+/// Conversion of a mutable reference to unit `O`, activated by [AsMut].
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` can't be mutably converted to unit `{O}`",
+    label = "no `ToMut<{O}>` for `{Self}`",
+    note = "conversion between units is opt-in: implement `AsMut<{O}>` for `{Self}`"
+)]
+pub trait ToMut<O> {
+    type Output;
 
-impl<T, Repr, O> To<O, Repr> for Amm<T, Repr>
-where
-    Self: As<O>,
-{
-    fn to(self) -> Amm<O, Repr> {
-        unsafe { transmute_unchecked(self) }
-    }
-    fn to_ref(&self) -> &Amm<O, Repr> {
-        unsafe { transmute_unchecked(self) }
-    }
-}
-impl<T, Repr, O> ToMut<O, Repr> for Amm<T, Repr>
-where
-    Self: AsMut<O>,
-{
-    fn to_mut(&mut self) -> &mut Amm<O, Repr> {
-        unsafe { transmute_unchecked(self) }
-    }
+    fn to_mut(&mut self) -> &mut Self::Output;
 }
 
-// ------
+/// Conversion to unit `O`, activated by [AsFrom] (implemented for the target type).
+///
 /// This trait doesn't have a generic parameter indicating the type we're transforming from.
 /// However, it has "From" in its name, because it's related to [AsFrom].
-pub trait ToFrom<O, Repr> {
-    fn to(self) -> Amm<O, Repr>;
-    fn to_ref(&self) -> &Amm<O, Repr>;
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` can't be converted to unit `{O}`",
+    label = "no `ToFrom<{O}>` for `{Self}`",
+    note = "conversion between units is opt-in: implement `AsFrom` for the target type"
+)]
+pub trait ToFrom<O> {
+    type Output;
+
+    fn to(self) -> Self::Output;
+    fn to_ref(&self) -> &Self::Output;
 }
-pub trait ToFromMut<O, Repr> {
-    fn to_mut(&mut self) -> &mut Amm<O, Repr>;
+/// Conversion of a mutable reference to unit `O`, activated by [AsFromMut].
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` can't be mutably converted to unit `{O}`",
+    label = "no `ToFromMut<{O}>` for `{Self}`",
+    note = "conversion between units is opt-in: implement `AsFromMut` for the target type"
+)]
+pub trait ToFromMut<O> {
+    type Output;
+
+    fn to_mut(&mut self) -> &mut Self::Output;
 }
 
-impl<T, Repr, O> ToFrom<O, Repr> for Amm<T, Repr>
-where
-    Amm<O, Repr>: AsFrom<T>,
-{
-    fn to(self) -> Amm<O, Repr> {
-        unsafe { transmute_unchecked(self) }
+// SAFETY (for all calls of `transmute_unchecked` in this module): each archetype is
+// `#[repr(transparent)]` over `Repr`, and its only other field is a zero-sized `PhantomData` of
+// the unit. So the source and the target (which differ only in the unit) have the same layout and
+// validity, and neither has any invariant beyond those of `Repr`.
+macro_rules! impl_to {
+    ($($wrapper:ident)*) => {
+        $(
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<const TF: TraitFlags, T, Repr, O> To<O> for $wrapper<TF, T, Repr>
+            where
+                Self: As<O>,
+            {
+                type Output = $wrapper<TF, O, Repr>;
+
+                fn to(self) -> Self::Output {
+                    unsafe { transmute_unchecked(self) }
+                }
+                fn to_ref(&self) -> &Self::Output {
+                    unsafe { transmute_unchecked(self) }
+                }
+            }
+
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<const TF: TraitFlags, T, Repr, O> ToMut<O> for $wrapper<TF, T, Repr>
+            where
+                Self: AsMut<O>,
+            {
+                type Output = $wrapper<TF, O, Repr>;
+
+                fn to_mut(&mut self) -> &mut Self::Output {
+                    unsafe { transmute_unchecked(self) }
+                }
+            }
+
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<const TF: TraitFlags, T, Repr, O> ToFrom<O> for $wrapper<TF, T, Repr>
+            where
+                $wrapper<TF, O, Repr>: AsFrom<T>,
+            {
+                type Output = $wrapper<TF, O, Repr>;
+
+                fn to(self) -> Self::Output {
+                    unsafe { transmute_unchecked(self) }
+                }
+                fn to_ref(&self) -> &Self::Output {
+                    unsafe { transmute_unchecked(self) }
+                }
+            }
+
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<const TF: TraitFlags, T, Repr, O> ToFromMut<O> for $wrapper<TF, T, Repr>
+            where
+                $wrapper<TF, O, Repr>: AsFromMut<T>,
+            {
+                type Output = $wrapper<TF, O, Repr>;
+
+                fn to_mut(&mut self) -> &mut Self::Output {
+                    unsafe { transmute_unchecked(self) }
+                }
+            }
+        )*
+    };
+}
+impl_to!(Amount Id Instant);
+
+/// Opt-in, declared on a unit (or entity) marker, allowing values of that unit to be retagged as
+/// unit `Target` with `cast`, `cast_ref` or `cast_mut` (on [crate::Amount], [crate::Id] and
+/// [crate::Instant], for any `Repr`).
+///
+/// Unlike [As] and [AsFrom], which you implement per archetype (and possibly per `Repr`), this
+/// covers all of them.
+///
+/// ```
+/// #![cfg_attr(
+///     feature = "unstable_generic_const_own_type",
+///     feature(generic_const_exprs)
+/// )]
+///
+/// use phantom_newtype::{CastUnit, Id};
+///
+/// enum Node {}
+/// enum Peer {}
+/// // Every node is a peer:
+/// impl CastUnit<Peer> for Node {}
+///
+/// let node = Id::<Node, u64>::from(7);
+/// let peer: Id<Peer, u64> = node.cast();
+/// assert_eq!(*peer.get(), 7);
+/// assert_eq!(*node.cast_ref::<Peer>(), peer);
+/// ```
+///
+/// Without the opt-in, there is no cast:
+///
+/// ```compile_fail
+/// use phantom_newtype::Id;
+///
+/// enum Node {}
+/// enum Peer {}
+///
+/// let peer: Id<Peer, u64> = Id::<Node, u64>::from(7).cast();
+/// ```
+#[diagnostic::on_unimplemented(
+    message = "unit `{Self}` can't be cast to unit `{Target}`",
+    label = "no `CastUnit<{Target}>` for `{Self}`",
+    note = "casts between units are opt-in: `impl CastUnit<{Target}> for {Self} {{}}`"
+)]
+pub trait CastUnit<Target> {}
+
+macro_rules! impl_cast {
+    ($($wrapper:ident)*) => {
+        $(
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<const TF: TraitFlags, Unit, Repr> $wrapper<TF, Unit, Repr> {
+                /// Retag as unit `Target`, as allowed by [CastUnit].
+                pub fn cast<Target>(self) -> $wrapper<TF, Target, Repr>
+                where
+                    Unit: CastUnit<Target>,
+                {
+                    unsafe { transmute_unchecked(self) }
+                }
+
+                /// Retag a reference as unit `Target`, as allowed by [CastUnit].
+                pub fn cast_ref<Target>(&self) -> &$wrapper<TF, Target, Repr>
+                where
+                    Unit: CastUnit<Target>,
+                {
+                    unsafe { transmute_unchecked(self) }
+                }
+
+                /// Retag a mutable reference as unit `Target`, as allowed by [CastUnit].
+                pub fn cast_mut<Target>(&mut self) -> &mut $wrapper<TF, Target, Repr>
+                where
+                    Unit: CastUnit<Target>,
+                {
+                    unsafe { transmute_unchecked(self) }
+                }
+            }
+        )*
+    };
+}
+impl_cast!(Amount Id Instant);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trait_flag::{
+        TRAIT_FLAGS_IS_COPY_IS_DEFAULT, TRAIT_FLAGS_IS_COPY_NO_DEFAULT,
+        TRAIT_FLAGS_NO_COPY_IS_DEFAULT, TRAIT_FLAGS_NO_COPY_NO_DEFAULT,
+    };
+    use core::cell::Cell;
+    use core::fmt;
+    extern crate std;
+    use std::format;
+
+    struct In;
+    struct Out;
+    struct Property;
+
+    /// A non-`Copy` `Repr` that counts its drops, to catch a conversion dropping (or duplicating)
+    /// the value. The wrappers expose it through `Debug`.
+    struct Counted<'a> {
+        value: u64,
+        drops: &'a Cell<u32>,
     }
-    fn to_ref(&self) -> &Amm<O, Repr> {
-        unsafe { transmute_unchecked(self) }
+    impl Drop for Counted<'_> {
+        fn drop(&mut self) {
+            self.drops.set(self.drops.get() + 1);
+        }
     }
-}
-impl<T, Repr, O> ToFromMut<O, Repr> for Amm<T, Repr>
-where
-    Amm<O, Repr>: AsFromMut<T>,
-{
-    fn to_mut(&mut self) -> &mut Amm<O, Repr> {
-        unsafe { transmute_unchecked(self) }
+    impl fmt::Debug for Counted<'_> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "{}", self.value)
+        }
     }
-}
 
-//-------
-// USERLAND:
-#[derive(Clone, Copy)]
-pub struct In;
-#[derive(Clone, Copy)]
-pub struct Out;
-#[derive(Clone, Copy)]
-pub struct Out2;
-
-/// Indicate/activate the blanket impl.
-//impl<UNIT: Copy> As<Out> for Amm<In, UNIT> {}
-//impl<UNIT: Copy> As<Out2> for Amm<In, UNIT> {}
-impl<UNIT> As<Out> for Amm<In, UNIT> {}
-impl<UNIT> As<Out2> for Amm<In, UNIT> {}
-
-fn _in_to_out_f32(inp: Amm<In, f32>) -> Amm<Out, f32> {
-    let inp2 = inp;
-    let _inp2: Amm<Out2, _> = inp2.to();
-    let _inp3 = inp2.to() as Amm<Out, _>;
-    // @TODO consider:
-    //
-    // let inp3 =  Amm<Out, _>::fr(inp2);
-
-    // the above `impl` automatically enables this:
-    inp.to()
-}
-//-----
+    // Opt-ins for every archetype and flag combination: `As`/`AsMut` from `In` to `Out`, and
+    // `AsFrom`/`AsFromMut` from `(In, Property)` to `(Out, Property)`.
+    macro_rules! opt_in {
+        ($($wrapper:ident)*) => {
+            $(
+                #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+                impl<const TF: TraitFlags, Repr> As<Out> for $wrapper<TF, In, Repr> {}
+                #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+                impl<const TF: TraitFlags, Repr> AsMut<Out> for $wrapper<TF, In, Repr> {}
+                #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+                impl<const TF: TraitFlags, Repr> AsFrom<(In, Property)>
+                    for $wrapper<TF, (Out, Property), Repr>
+                {
+                }
+                #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+                impl<const TF: TraitFlags, Repr> AsFromMut<(In, Property)>
+                    for $wrapper<TF, (Out, Property), Repr>
+                {
+                }
+            )*
+        };
+    }
+    opt_in!(Amount Id Instant);
 
-/// Indicate/activate the blanket impl.
-impl<PROPERTY, UNIT> AsFrom<(In, PROPERTY)> for Amm<(Out, PROPERTY), UNIT> {}
+    // Round-trip a `Counted` through every conversion, for one archetype and flag combination.
+    macro_rules! check {
+        ($wrapper:ident, $flags:expr) => {{
+            crate::static_assert_transparent!($wrapper<{ $flags }, In, u16>, $wrapper<{ $flags }, Out, u16>);
 
-pub fn _in_to_out_f64<PROPERTY>(inp: Amm<(In, PROPERTY), f64>) -> Amm<(Out, PROPERTY), f64> {
-    // the above `impl` automatically enables this:
-    inp.to()
-}
-pub fn _in_to_out<PROPERTY, UNIT>(inp: Amm<(In, PROPERTY), UNIT>) -> Amm<(Out, PROPERTY), UNIT> {
-    // the above `impl` automatically enables this:
-    inp.to()
+            let drops = Cell::new(0);
+            {
+                let new = |value| Counted {
+                    value,
+                    drops: &drops,
+                };
+                let mut x = $wrapper::<{ $flags }, In, Counted>::new(new(1));
+                assert_eq!(format!("{:?}", To::<Out>::to_ref(&x)), "1");
+                *ToMut::<Out>::to_mut(&mut x) = $wrapper::new(new(2));
+                let y: $wrapper<{ $flags }, Out, Counted> = To::<Out>::to(x);
+                assert_eq!(format!("{:?}", y), "2");
+
+                let mut x = $wrapper::<{ $flags }, (In, Property), Counted>::new(new(3));
+                assert_eq!(format!("{:?}", ToFrom::<(Out, Property)>::to_ref(&x)), "3");
+                *ToFromMut::<(Out, Property)>::to_mut(&mut x) = $wrapper::new(new(4));
+                let y: $wrapper<{ $flags }, (Out, Property), Counted> =
+                    ToFrom::<(Out, Property)>::to(x);
+                assert_eq!(format!("{:?}", y), "4");
+            }
+            // Values 1 and 3 were dropped when overwritten, 2 and 4 at the end of the scope.
+            assert_eq!(drops.get(), 4);
+        }};
+    }
+
+    #[test]
+    #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+    fn all_archetypes_and_flags() {
+        check!(Id, TRAIT_FLAGS_IS_COPY_IS_DEFAULT);
+        check!(Id, TRAIT_FLAGS_IS_COPY_NO_DEFAULT);
+        check!(Id, TRAIT_FLAGS_NO_COPY_IS_DEFAULT);
+        check!(Id, TRAIT_FLAGS_NO_COPY_NO_DEFAULT);
+        check!(Amount, TRAIT_FLAGS_IS_COPY_IS_DEFAULT);
+        check!(Amount, TRAIT_FLAGS_IS_COPY_NO_DEFAULT);
+        check!(Amount, TRAIT_FLAGS_NO_COPY_IS_DEFAULT);
+        check!(Amount, TRAIT_FLAGS_NO_COPY_NO_DEFAULT);
+        check!(Instant, TRAIT_FLAGS_IS_COPY_IS_DEFAULT);
+        check!(Instant, TRAIT_FLAGS_IS_COPY_NO_DEFAULT);
+        check!(Instant, TRAIT_FLAGS_NO_COPY_IS_DEFAULT);
+        check!(Instant, TRAIT_FLAGS_NO_COPY_NO_DEFAULT);
+    }
 }
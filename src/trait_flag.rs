@@ -1,30 +1,66 @@
-//! Unstable.
+//! Unstable: the trait flags of the wrappers. Prefer the type aliases (like [crate::AmountNoCopy])
+//! to naming flags directly.
+//!
+//! Flags are built from, and queried for, their bits with `const fn`s:
+//!
+//! ```
+//! #![cfg_attr(
+//!     feature = "unstable_generic_const_own_type",
+//!     feature(generic_const_exprs)
+//! )]
+//! #![allow(deprecated)]
+//!
+//! use phantom_newtype::trait_flag::{
+//!     is_copy, is_default, trait_flags_bits, trait_flags_from_bits, TRAIT_FLAGS_NO_COPY_IS_DEFAULT,
+//!     TRAIT_FLAG_BITS_KNOWN, TRAIT_FLAG_BIT_COPY, TRAIT_FLAG_BIT_DEFAULT,
+//! };
+//!
+//! let copy_only = trait_flags_from_bits(TRAIT_FLAG_BIT_COPY);
+//! assert!(is_copy(copy_only) && !is_default(copy_only));
+//! assert_eq!(trait_flags_bits(copy_only), TRAIT_FLAG_BIT_COPY);
+//!
+//! assert!(!is_copy(TRAIT_FLAGS_NO_COPY_IS_DEFAULT));
+//! assert_eq!(
+//!     trait_flags_bits(TRAIT_FLAGS_NO_COPY_IS_DEFAULT),
+//!     TRAIT_FLAG_BIT_DEFAULT
+//! );
+//! assert_eq!(TRAIT_FLAG_BITS_KNOWN, TRAIT_FLAG_BIT_COPY | TRAIT_FLAG_BIT_DEFAULT);
+//! ```
 
-#[derive(Eq, PartialEq, PartialOrd, core::fmt::Debug)]
+#[derive(Clone, Copy, Eq, PartialEq, PartialOrd, core::fmt::Debug)]
+#[allow(clippy::enum_variant_names)]
 #[cfg_attr(
     feature = "unstable_generic_const_own_type",
     derive(core::marker::ConstParamTy)
 )]
+#[repr(u16)]
 pub enum TraitFlagsValues {
-    TraitFlagsNoCopyNoDefault,
-    TraitFlagsIsCopyNoDefault,
-    TraitFlagsNoCopyIsDefault,
-    TraitFlagsIsCopyIsDefault,
+    // The discriminants are the bits, see [TRAIT_FLAG_BIT_COPY] and [TRAIT_FLAG_BIT_DEFAULT].
+    TraitFlagsNoCopyNoDefault = 0b00,
+    TraitFlagsIsCopyNoDefault = 0b01,
+    TraitFlagsNoCopyIsDefault = 0b10,
+    TraitFlagsIsCopyIsDefault = 0b11,
 }
 
 /// Use for a const generic `TRAIT_FLAGS` parameter to indicate some optional functionality of
-/// [Amount], [Id] or [Instant].
+/// [crate::Amount], [crate::Id] or [crate::Instant].
 ///
 /// Do not hard code any values. Instead, use `TRAIT_FLAGS_*` constants (like
-/// [TRAIT_FLAGS_IS_COPY_IS_DEFAULT]). Even better, use the type aliases like [Amount],
-/// [AmountNoCopy], [AmountNoCopyNoDefault].
+/// [TRAIT_FLAGS_IS_COPY_IS_DEFAULT]). Even better, use the type aliases like [crate::Amount],
+/// [crate::AmountNoCopy], [crate::AmountNoCopyNoDefault].
+///
+/// On stable Rust, this is a bitset: each bit enables one optional trait (see
+/// [TRAIT_FLAG_BIT_COPY] and [TRAIT_FLAG_BIT_DEFAULT]), and the others are reserved for future
+/// ones (see [TRAIT_FLAG_BITS_KNOWN]). A new bit will be off in the existing constants, so the
+/// existing aliases keep their meaning. Read the bits with the `const fn`s [trait_flags_bits],
+/// [is_copy] and [is_default].
 ///
 /// Subject to change. Once `#![feature(adt_const_params)]` becomes stable`:
 /// [rust-lang/rust/issues/95174](https://github.com/rust-lang/rust/issues/95174), we switch to
 /// using a proper struct here (which will derive [core::marker::ConstParamTy], derive/implement
 /// [PartialEq] and [Eq]).
 #[cfg(not(feature = "unstable_generic_const_own_type"))]
-pub type TraitFlags = u8;
+pub type TraitFlags = u16;
 #[cfg(feature = "unstable_generic_const_own_type")]
 /// `nightly` warning: Direct use of [IdForFlags] (and hence direct use of TraitFlags) is unstable!
 #[deprecated(
@@ -32,79 +68,90 @@ pub type TraitFlags = u8;
 )]
 pub type TraitFlags = TraitFlagsValues;
 
+/// Bit of [TraitFlags] that makes the wrappers `Copy` (if `Repr` is).
+pub const TRAIT_FLAG_BIT_COPY: u16 = 0b1;
+/// Bit of [TraitFlags] that makes the wrappers `Default` (if `Repr` is).
+pub const TRAIT_FLAG_BIT_DEFAULT: u16 = 0b10;
+/// All bits of [TraitFlags] that have a meaning so far. The others are reserved.
+pub const TRAIT_FLAG_BITS_KNOWN: u16 = TRAIT_FLAG_BIT_COPY | TRAIT_FLAG_BIT_DEFAULT;
+
+/// [TraitFlags] of the given bits. Panics (in `const` contexts: fails to compile) on reserved
+/// bits, so that flags meant for a newer version of this crate are not silently ignored.
 #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
-const fn trait_flags_new(tfv: TraitFlagsValues) -> TraitFlags {
+pub const fn trait_flags_from_bits(bits: u16) -> TraitFlags {
+    assert!(
+        bits & !TRAIT_FLAG_BITS_KNOWN == 0,
+        "reserved trait flag bits"
+    );
     #[cfg(not(feature = "unstable_generic_const_own_type"))]
     {
-        tfv as u8
+        bits
     }
     #[cfg(feature = "unstable_generic_const_own_type")]
     {
-        // See also https://doc.rust-lang.org/nightly/reference/items/enumerations.html#implicit-discriminants and https://doc.rust-lang.org/nightly/core/mem/fn.discriminant.html#accessing-the-numeric-value-of-the-discriminant
-        tfv
+        match bits {
+            0b00 => TraitFlagsValues::TraitFlagsNoCopyNoDefault,
+            0b01 => TraitFlagsValues::TraitFlagsIsCopyNoDefault,
+            0b10 => TraitFlagsValues::TraitFlagsNoCopyIsDefault,
+            _ => TraitFlagsValues::TraitFlagsIsCopyIsDefault,
+        }
     }
 }
 
-#[cfg(test)]
-const TRAIT_FLAG_BIT_COPY: u8 = 0b1;
-#[cfg(test)]
-const TRAIT_FLAG_BIT_DEFAULT: u8 = 0b10;
-
 #[cfg_attr(
     feature = "unstable_generic_const_own_type",
     deprecated(note = "`nightly` warning: Direct use is unstable!")
 )]
 #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
 pub const TRAIT_FLAGS_IS_COPY_IS_DEFAULT: TraitFlags =
-    trait_flags_new(TraitFlagsValues::TraitFlagsIsCopyIsDefault);
+    trait_flags_from_bits(TRAIT_FLAG_BIT_COPY | TRAIT_FLAG_BIT_DEFAULT);
 #[cfg_attr(
     feature = "unstable_generic_const_own_type",
     deprecated(note = "`nightly` warning: Direct use is unstable!")
 )]
 #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
-pub const TRAIT_FLAGS_IS_COPY_NO_DEFAULT: TraitFlags =
-    trait_flags_new(TraitFlagsValues::TraitFlagsIsCopyNoDefault);
+pub const TRAIT_FLAGS_IS_COPY_NO_DEFAULT: TraitFlags = trait_flags_from_bits(TRAIT_FLAG_BIT_COPY);
 #[cfg_attr(
     feature = "unstable_generic_const_own_type",
     deprecated(note = "`nightly` warning: Direct use is unstable!")
 )]
 #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
 pub const TRAIT_FLAGS_NO_COPY_IS_DEFAULT: TraitFlags =
-    trait_flags_new(TraitFlagsValues::TraitFlagsNoCopyIsDefault);
+    trait_flags_from_bits(TRAIT_FLAG_BIT_DEFAULT);
 #[cfg_attr(
     feature = "unstable_generic_const_own_type",
     deprecated(note = "`nightly` warning: Direct use is unstable!")
 )]
 #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
-pub const TRAIT_FLAGS_NO_COPY_NO_DEFAULT: TraitFlags =
-    trait_flags_new(TraitFlagsValues::TraitFlagsNoCopyNoDefault);
+pub const TRAIT_FLAGS_NO_COPY_NO_DEFAULT: TraitFlags = trait_flags_from_bits(0);
 
-#[cfg(test)]
+/// The bits of `tf`. See [TRAIT_FLAG_BIT_COPY] and [TRAIT_FLAG_BIT_DEFAULT].
 #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
-const fn trait_flags_bits(tf: TraitFlags) -> u8 {
+pub const fn trait_flags_bits(tf: TraitFlags) -> u16 {
     #[cfg(not(feature = "unstable_generic_const_own_type"))]
     return tf;
     #[cfg(feature = "unstable_generic_const_own_type")]
-    return tf as u8;
+    return tf as u16;
 }
 
-#[cfg(test)]
+/// Whether `flags` make the wrappers `Copy`.
 #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
-const fn is_copy(flags: TraitFlags) -> bool {
+pub const fn is_copy(flags: TraitFlags) -> bool {
     trait_flags_bits(flags) & TRAIT_FLAG_BIT_COPY != 0
 }
-#[cfg(test)]
+/// Whether `flags` make the wrappers `Default`.
 #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
-const fn is_default(flags: TraitFlags) -> bool {
+pub const fn is_default(flags: TraitFlags) -> bool {
     trait_flags_bits(flags) & TRAIT_FLAG_BIT_DEFAULT != 0
 }
+
 #[cfg(test)]
 mod test_flags {
     extern crate std;
     use super::*;
 
     #[test]
-    #[allow(deprecated)]
+    #[allow(deprecated, clippy::bool_assert_comparison)]
     fn all() {
         assert_eq!(is_copy(TRAIT_FLAGS_IS_COPY_IS_DEFAULT), true);
         assert_eq!(is_copy(TRAIT_FLAGS_IS_COPY_NO_DEFAULT), true);
@@ -116,6 +163,25 @@ mod test_flags {
         assert_eq!(is_default(TRAIT_FLAGS_IS_COPY_NO_DEFAULT), false);
         assert_eq!(is_default(TRAIT_FLAGS_NO_COPY_NO_DEFAULT), false);
     }
+
+    #[test]
+    #[allow(deprecated)]
+    fn from_bits() {
+        for flags in [
+            TRAIT_FLAGS_NO_COPY_NO_DEFAULT,
+            TRAIT_FLAGS_IS_COPY_NO_DEFAULT,
+            TRAIT_FLAGS_NO_COPY_IS_DEFAULT,
+            TRAIT_FLAGS_IS_COPY_IS_DEFAULT,
+        ] {
+            assert!(trait_flags_from_bits(trait_flags_bits(flags)) == flags);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "reserved trait flag bits")]
+    fn from_reserved_bits() {
+        trait_flags_from_bits(0b100);
+    }
 }
 
 // Move to Amount, Instant:
@@ -0,0 +1,98 @@
+// Copyright 2024 Peter Lyons Kehl
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Projections into tuple `Repr`s (of up to 4 elements) of [Amount]s, [Id]s and [Instant]s:
+//! `get_0`, `get_mut_0` and `map_0` for the first element, and so on.
+//!
+//! ```
+//! #![cfg_attr(
+//!     feature = "unstable_generic_const_own_type",
+//!     feature(generic_const_exprs)
+//! )]
+//!
+//! use phantom_newtype::{Id, Instant};
+//!
+//! enum Shard {}
+//! enum Ledger {}
+//! // A shard number and a sequence number within the shard.
+//! type RecordId = Id<Shard, (u16, u64)>;
+//! // Seconds and nanoseconds.
+//! type Timestamp = Instant<Ledger, (i64, u32)>;
+//!
+//! let id = RecordId::from((3, 41));
+//! assert_eq!(*id.get_0(), 3);
+//! assert_eq!(id.map_1(|sequence| sequence + 1), RecordId::from((3, 42)));
+//!
+//! let mut time = Timestamp::from((1_700_000_000, 500));
+//! *time.get_mut_1() = 0;
+//! assert_eq!(time, Timestamp::from((1_700_000_000, 0)));
+//! ```
+
+use crate::amount::Amount;
+use crate::id::Id;
+use crate::instant::Instant;
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+use crate::trait_flag::TraitFlags;
+
+macro_rules! impl_tuple_projections {
+    ($($wrapper:ident)*) => {
+        $(
+            impl_tuple_projections! {
+                @tuple $wrapper (A B) {
+                    0 A get_0 get_mut_0 map_0
+                    1 B get_1 get_mut_1 map_1
+                }
+                @tuple $wrapper (A B C) {
+                    0 A get_0 get_mut_0 map_0
+                    1 B get_1 get_mut_1 map_1
+                    2 C get_2 get_mut_2 map_2
+                }
+                @tuple $wrapper (A B C D) {
+                    0 A get_0 get_mut_0 map_0
+                    1 B get_1 get_mut_1 map_1
+                    2 C get_2 get_mut_2 map_2
+                    3 D get_3 get_mut_3 map_3
+                }
+            }
+        )*
+    };
+    ($(@tuple $wrapper:ident ($($t:ident)+) {
+        $($index:tt $element:ident $get:ident $get_mut:ident $map:ident)+
+    })+) => {
+        $(
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<const TF: TraitFlags, Unit, $($t),+> $wrapper<TF, Unit, ($($t,)+)> {
+                $(
+                    #[doc = concat!("The element ", stringify!($index), " of the tuple.")]
+                    pub const fn $get(&self) -> &$element {
+                        &self.0.$index
+                    }
+
+                    #[doc = concat!("A mutable reference to the element ", stringify!($index), " of the tuple.")]
+                    pub fn $get_mut(&mut self) -> &mut $element {
+                        &mut self.0.$index
+                    }
+
+                    #[doc = concat!("Replace the element ", stringify!($index), " of the tuple with `f` of it.")]
+                    pub fn $map(self, f: impl FnOnce($element) -> $element) -> Self {
+                        let mut repr = self.0;
+                        repr.$index = f(repr.$index);
+                        Self::new(repr)
+                    }
+                )+
+            }
+        )+
+    };
+}
+impl_tuple_projections!(Amount Id Instant);
@@ -0,0 +1,384 @@
+// Copyright 2024 Peter Lyons Kehl
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Slices and (with feature `alloc`) `Vec`s indexed by [Id]`<Entity, usize>` only, so that an
+//! index of one kind of entity can't index items of another kind.
+
+use crate::Id;
+#[cfg(feature = "alloc")]
+use alloc::vec::{self, Vec};
+use core::fmt;
+use core::hash::{Hash, Hasher};
+#[cfg(feature = "alloc")]
+use core::iter::FromIterator;
+use core::marker::PhantomData;
+#[cfg(feature = "alloc")]
+use core::ops::{Deref, DerefMut};
+use core::ops::{Index, IndexMut};
+use core::slice;
+
+/// A slice of `T` indexed by [Id]`<Entity, usize>`. Get one from a slice with [TypedIndex], or
+/// from a [TypedVec].
+///
+/// ```
+/// #![cfg_attr(
+///     feature = "unstable_generic_const_own_type",
+///     feature(generic_const_exprs)
+/// )]
+///
+/// use phantom_newtype::{Id, TypedIndex};
+///
+/// enum Node {}
+/// type NodeIndex = Id<Node, usize>;
+///
+/// let mut loads = [10, 20, 30];
+/// let nodes = loads.typed_mut::<Node>();
+/// nodes[NodeIndex::from(1)] += 5;
+/// assert_eq!(nodes[NodeIndex::from(1)], 25);
+/// assert_eq!(nodes.get(NodeIndex::from(3)), None);
+/// assert_eq!(nodes.ids().last(), Some(NodeIndex::from(2)));
+/// ```
+///
+/// An index of a different entity doesn't compile:
+///
+/// ```compile_fail
+/// # use phantom_newtype::{Id, TypedIndex};
+/// # enum Node {}
+/// # enum Shard {}
+/// let loads = [10, 20, 30];
+/// let _ = loads.typed::<Node>()[Id::<Shard, usize>::from(1)];
+/// ```
+#[repr(transparent)]
+pub struct TypedSlice<Entity, T> {
+    entity: PhantomData<fn() -> Entity>,
+    items: [T],
+}
+
+impl<Entity, T> TypedSlice<Entity, T> {
+    pub const fn from_slice(items: &[T]) -> &Self {
+        // SAFETY: `Self` is `#[repr(transparent)]` over `[T]`.
+        unsafe { &*(items as *const [T] as *const Self) }
+    }
+
+    pub fn from_slice_mut(items: &mut [T]) -> &mut Self {
+        // SAFETY: `Self` is `#[repr(transparent)]` over `[T]`.
+        unsafe { &mut *(items as *mut [T] as *mut Self) }
+    }
+
+    /// The items, indexed by `usize`.
+    pub const fn as_slice(&self) -> &[T] {
+        &self.items
+    }
+
+    /// The items, indexed by `usize`.
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.items
+    }
+
+    pub const fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn get(&self, id: Id<Entity, usize>) -> Option<&T> {
+        self.items.get(id.0)
+    }
+
+    pub fn get_mut(&mut self, id: Id<Entity, usize>) -> Option<&mut T> {
+        self.items.get_mut(id.0)
+    }
+
+    pub fn iter(&self) -> slice::Iter<'_, T> {
+        self.items.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> slice::IterMut<'_, T> {
+        self.items.iter_mut()
+    }
+
+    /// The valid indices, in order.
+    pub fn ids(&self) -> impl Iterator<Item = Id<Entity, usize>> {
+        (0..self.items.len()).map(Id::new)
+    }
+
+    /// The items with their indices.
+    pub fn iter_enumerated(&self) -> impl Iterator<Item = (Id<Entity, usize>, &T)> {
+        self.items
+            .iter()
+            .enumerate()
+            .map(|(index, item)| (Id::new(index), item))
+    }
+}
+
+impl<Entity, T> Index<Id<Entity, usize>> for TypedSlice<Entity, T> {
+    type Output = T;
+
+    fn index(&self, id: Id<Entity, usize>) -> &T {
+        &self.items[id.0]
+    }
+}
+
+impl<Entity, T> IndexMut<Id<Entity, usize>> for TypedSlice<Entity, T> {
+    fn index_mut(&mut self, id: Id<Entity, usize>) -> &mut T {
+        &mut self.items[id.0]
+    }
+}
+
+impl<Entity, T: PartialEq> PartialEq for TypedSlice<Entity, T> {
+    fn eq(&self, rhs: &Self) -> bool {
+        self.items == rhs.items
+    }
+}
+
+impl<Entity, T: Eq> Eq for TypedSlice<Entity, T> {}
+
+impl<Entity, T: Hash> Hash for TypedSlice<Entity, T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.items.hash(state)
+    }
+}
+
+impl<Entity, T: fmt::Debug> fmt::Debug for TypedSlice<Entity, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.items, f)
+    }
+}
+
+impl<'a, Entity, T> IntoIterator for &'a TypedSlice<Entity, T> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, Entity, T> IntoIterator for &'a mut TypedSlice<Entity, T> {
+    type Item = &'a mut T;
+    type IntoIter = slice::IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+/// Views a slice (or a `Vec`) as a [TypedSlice].
+pub trait TypedIndex<T> {
+    fn typed<Entity>(&self) -> &TypedSlice<Entity, T>;
+
+    fn typed_mut<Entity>(&mut self) -> &mut TypedSlice<Entity, T>;
+}
+
+impl<T> TypedIndex<T> for [T] {
+    fn typed<Entity>(&self) -> &TypedSlice<Entity, T> {
+        TypedSlice::from_slice(self)
+    }
+
+    fn typed_mut<Entity>(&mut self) -> &mut TypedSlice<Entity, T> {
+        TypedSlice::from_slice_mut(self)
+    }
+}
+
+/// A `Vec` of `T` indexed by [Id]`<Entity, usize>`, like in crate `index_vec`. It dereferences to
+/// [TypedSlice]. Requires feature `alloc`.
+///
+/// ```
+/// #![cfg_attr(
+///     feature = "unstable_generic_const_own_type",
+///     feature(generic_const_exprs)
+/// )]
+///
+/// use phantom_newtype::{Id, TypedVec};
+///
+/// enum Node {}
+/// type NodeIndex = Id<Node, usize>;
+///
+/// let mut names = TypedVec::<Node, &str>::new();
+/// let root: NodeIndex = names.push("root");
+/// let leaf = names.push("leaf");
+/// assert_eq!(leaf, NodeIndex::from(1));
+/// assert_eq!(names[root], "root");
+///
+/// names[leaf] = "branch";
+/// let all: Vec<_> = names.iter_enumerated().collect();
+/// assert_eq!(all, [(root, &"root"), (leaf, &"branch")]);
+/// assert_eq!(names.into_vec(), ["root", "branch"]);
+/// ```
+#[cfg(feature = "alloc")]
+pub struct TypedVec<Entity, T> {
+    entity: PhantomData<fn() -> Entity>,
+    items: Vec<T>,
+}
+
+#[cfg(feature = "alloc")]
+impl<Entity, T> TypedVec<Entity, T> {
+    pub const fn new() -> Self {
+        Self::from_vec(Vec::new())
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::from_vec(Vec::with_capacity(capacity))
+    }
+
+    pub const fn from_vec(items: Vec<T>) -> Self {
+        Self {
+            entity: PhantomData,
+            items,
+        }
+    }
+
+    pub fn into_vec(self) -> Vec<T> {
+        self.items
+    }
+
+    /// The index that the next [TypedVec::push] will return.
+    pub fn next_id(&self) -> Id<Entity, usize> {
+        Id::new(self.items.len())
+    }
+
+    /// Appends `item`, and returns its index.
+    pub fn push(&mut self, item: T) -> Id<Entity, usize> {
+        let id = self.next_id();
+        self.items.push(item);
+        id
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        self.items.pop()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<Entity, T> Deref for TypedVec<Entity, T> {
+    type Target = TypedSlice<Entity, T>;
+
+    fn deref(&self) -> &Self::Target {
+        TypedSlice::from_slice(&self.items)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<Entity, T> DerefMut for TypedVec<Entity, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        TypedSlice::from_slice_mut(&mut self.items)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<Entity, T> Index<Id<Entity, usize>> for TypedVec<Entity, T> {
+    type Output = T;
+
+    fn index(&self, id: Id<Entity, usize>) -> &T {
+        &self.items[id.0]
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<Entity, T> IndexMut<Id<Entity, usize>> for TypedVec<Entity, T> {
+    fn index_mut(&mut self, id: Id<Entity, usize>) -> &mut T {
+        &mut self.items[id.0]
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<Entity, T> Default for TypedVec<Entity, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<Entity, T: Clone> Clone for TypedVec<Entity, T> {
+    fn clone(&self) -> Self {
+        Self::from_vec(self.items.clone())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<Entity, T: PartialEq> PartialEq for TypedVec<Entity, T> {
+    fn eq(&self, rhs: &Self) -> bool {
+        self.items == rhs.items
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<Entity, T: Eq> Eq for TypedVec<Entity, T> {}
+
+#[cfg(feature = "alloc")]
+impl<Entity, T: Hash> Hash for TypedVec<Entity, T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.items.hash(state)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<Entity, T: fmt::Debug> fmt::Debug for TypedVec<Entity, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.items, f)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<Entity, T> From<Vec<T>> for TypedVec<Entity, T> {
+    fn from(items: Vec<T>) -> Self {
+        Self::from_vec(items)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<Entity, T> FromIterator<T> for TypedVec<Entity, T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::from_vec(iter.into_iter().collect())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<Entity, T> Extend<T> for TypedVec<Entity, T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.items.extend(iter)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<Entity, T> IntoIterator for TypedVec<Entity, T> {
+    type Item = T;
+    type IntoIter = vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.into_iter()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, Entity, T> IntoIterator for &'a TypedVec<Entity, T> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.iter()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, Entity, T> IntoIterator for &'a mut TypedVec<Entity, T> {
+    type Item = &'a mut T;
+    type IntoIter = slice::IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.iter_mut()
+    }
+}
@@ -0,0 +1,170 @@
+// Copyright 2024 Peter Lyons Kehl
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! [UnitKey]: a key that carries its unit, for maps that mix the ids (or amounts, or instants) of
+//! different units.
+
+use crate::amount::Amount;
+use crate::id::Id;
+use crate::instant::Instant;
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+use crate::trait_flag::TraitFlags;
+use core::any::{type_name, TypeId};
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+
+/// A `Repr` together with the `TypeId` of its unit (or entity). Both take part in equality,
+/// ordering and hashing, so that equal `Repr`s of different units are different keys.
+///
+/// [Amount], [Id] and [Instant] themselves hash (and compare) like their `Repr` only, which
+/// keeps `Borrow<Repr>`-based lookups (like `IdMap::get(&repr)`) working. A [UnitKey] can't
+/// keep that promise, so it deliberately does not implement `Borrow<Repr>`: look it up by a
+/// [UnitKey], converted from a wrapper.
+///
+/// The wrapper kind is not part of the key: an `Amount` and an `Id` of the same unit and `Repr`
+/// make equal keys.
+///
+/// ```
+/// #![cfg_attr(
+///     feature = "unstable_generic_const_own_type",
+///     feature(generic_const_exprs)
+/// )]
+///
+/// use phantom_newtype::{Id, UnitKey};
+/// use std::collections::HashMap;
+///
+/// enum User {}
+/// enum Group {}
+///
+/// let mut names: HashMap<UnitKey<u64>, &str> = HashMap::new();
+/// names.insert(Id::<User, u64>::from(7).into(), "Ada");
+/// names.insert(Id::<Group, u64>::from(7).into(), "admins");
+/// assert_eq!(names.len(), 2);
+/// assert_eq!(names[&UnitKey::from(Id::<Group, u64>::from(7))], "admins");
+///
+/// let key = UnitKey::from(Id::<User, u64>::from(7));
+/// assert_eq!(key.get::<User>(), Some(&7));
+/// assert_eq!(key.get::<Group>(), None);
+/// ```
+#[derive(Clone, Copy)]
+pub struct UnitKey<Repr> {
+    unit: TypeId,
+    // Only for `Debug`.
+    unit_name: &'static str,
+    repr: Repr,
+}
+
+impl<Repr> UnitKey<Repr> {
+    /// A key of `repr` in `Unit`.
+    pub fn new<Unit: 'static>(repr: Repr) -> Self {
+        Self {
+            unit: TypeId::of::<Unit>(),
+            unit_name: type_name::<Unit>(),
+            repr,
+        }
+    }
+
+    /// `TypeId` of the unit.
+    pub fn unit(&self) -> TypeId {
+        self.unit
+    }
+
+    /// Whether the unit is `Unit`.
+    pub fn is<Unit: 'static>(&self) -> bool {
+        self.unit == TypeId::of::<Unit>()
+    }
+
+    /// The `Repr`, regardless of the unit.
+    pub fn repr(&self) -> &Repr {
+        &self.repr
+    }
+
+    /// The `Repr`, if the unit is `Unit`.
+    pub fn get<Unit: 'static>(&self) -> Option<&Repr> {
+        if self.is::<Unit>() {
+            Some(&self.repr)
+        } else {
+            None
+        }
+    }
+
+    /// The `Repr`, if the unit is `Unit`. Otherwise `Err(self)`.
+    pub fn into_repr<Unit: 'static>(self) -> Result<Repr, Self> {
+        if self.is::<Unit>() {
+            Ok(self.repr)
+        } else {
+            Err(self)
+        }
+    }
+}
+
+impl<Repr: PartialEq> PartialEq for UnitKey<Repr> {
+    fn eq(&self, other: &Self) -> bool {
+        self.unit == other.unit && self.repr == other.repr
+    }
+}
+
+impl<Repr: Eq> Eq for UnitKey<Repr> {}
+
+/// Ordered by the unit's `TypeId` first (which is arbitrary, but consistent within a build), then
+/// by `Repr`.
+impl<Repr: PartialOrd> PartialOrd for UnitKey<Repr> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match self.unit.cmp(&other.unit) {
+            Ordering::Equal => self.repr.partial_cmp(&other.repr),
+            unequal => Some(unequal),
+        }
+    }
+}
+
+impl<Repr: Ord> Ord for UnitKey<Repr> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.unit
+            .cmp(&other.unit)
+            .then_with(|| self.repr.cmp(&other.repr))
+    }
+}
+
+impl<Repr: Hash> Hash for UnitKey<Repr> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.unit.hash(state);
+        self.repr.hash(state);
+    }
+}
+
+impl<Repr: fmt::Debug> fmt::Debug for UnitKey<Repr> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UnitKey")
+            .field("unit", &self.unit_name)
+            .field("repr", &self.repr)
+            .finish()
+    }
+}
+
+macro_rules! impl_from_wrapper {
+    ($($wrapper:ident)*) => {
+        $(
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<const TF: TraitFlags, Unit: 'static, Repr> From<$wrapper<TF, Unit, Repr>>
+                for UnitKey<Repr>
+            {
+                fn from(value: $wrapper<TF, Unit, Repr>) -> Self {
+                    Self::new::<Unit>(value.0)
+                }
+            }
+        )*
+    };
+}
+impl_from_wrapper!(Amount Id Instant);
@@ -0,0 +1,201 @@
+// Copyright 2024 Peter Lyons Kehl
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Errors of validated construction of typed values.
+//!
+//! [ValidationError] is a small `Copy` enum that needs no `alloc`, so validation is usable on
+//! embedded targets. Unit-specific failures carry a [StaticError]: a numeric code plus a
+//! `&'static str` message. With feature `ufmt`, the errors implement `ufmt::uDisplay` in addition
+//! to [core::fmt::Display].
+//!
+//! ```
+//! use phantom_newtype::validate::{StaticError, ValidationError};
+//!
+//! const BAD_CHECKSUM: StaticError = StaticError::new(17, "bad account checksum");
+//!
+//! let error = ValidationError::Custom(BAD_CHECKSUM);
+//! assert_eq!(error.to_string(), "bad account checksum (code 17)");
+//! assert_eq!(ValidationError::TooLarge.to_string(), "value too large");
+//! ```
+//!
+//! A marker type opts in to validation by implementing [ValidatorOf] for its wrappers. That adds
+//! `try_new` constructors (and `validate`) to them; `new` and `from` keep constructing unchecked.
+//!
+//! ```
+//! #![cfg_attr(
+//!     feature = "unstable_generic_const_own_type",
+//!     feature(generic_const_exprs)
+//! )]
+//!
+//! use phantom_newtype::validate::{StaticError, ValidationError, ValidatorOf};
+//! use phantom_newtype::Id;
+//!
+//! enum Account {}
+//! type AccountId = Id<Account, u32>;
+//!
+//! const BAD_CHECKSUM: StaticError = StaticError::new(17, "bad account checksum");
+//!
+//! // The last decimal digit is the sum of the other digits, modulo 10.
+//! impl ValidatorOf<AccountId> for Account {
+//!     fn validate(id: &AccountId) -> Result<(), ValidationError> {
+//!         let (mut rest, check) = (id.get() / 10, id.get() % 10);
+//!         let mut sum = 0;
+//!         while rest > 0 {
+//!             sum += rest % 10;
+//!             rest /= 10;
+//!         }
+//!         if sum % 10 == check {
+//!             Ok(())
+//!         } else {
+//!             Err(BAD_CHECKSUM.into())
+//!         }
+//!     }
+//! }
+//!
+//! assert_eq!(AccountId::try_new(1236), Ok(AccountId::new(1236)));
+//! assert_eq!(AccountId::try_new(1235), Err(ValidationError::Custom(BAD_CHECKSUM)));
+//! assert!(AccountId::new(1235).validate().is_err());
+//! ```
+
+use crate::amount::Amount;
+use crate::id::Id;
+use crate::instant::Instant;
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+use crate::trait_flag::TraitFlags;
+use core::fmt;
+
+/// Validation of `T` (an [Amount], [Id] or [Instant] of this marker type), run by `try_new`.
+/// Implemented by the marker type, like [crate::DisplayerOf].
+pub trait ValidatorOf<T> {
+    fn validate(value: &T) -> Result<(), ValidationError>;
+}
+
+macro_rules! impl_validated {
+    ($($wrapper:ident)*) => {
+        $(
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<const TF: TraitFlags, Unit, Repr> $wrapper<TF, Unit, Repr>
+            where
+                Unit: ValidatorOf<Self>,
+            {
+                /// Like `new`, but only if the `Unit`'s [ValidatorOf] accepts the value.
+                pub fn try_new(repr: Repr) -> Result<Self, ValidationError> {
+                    let value = Self::new(repr);
+                    Unit::validate(&value)?;
+                    Ok(value)
+                }
+
+                /// Runs the `Unit`'s [ValidatorOf] on this (for example, on a value that was
+                /// constructed unchecked, or deserialized).
+                pub fn validate(&self) -> Result<(), ValidationError> {
+                    Unit::validate(self)
+                }
+            }
+        )*
+    };
+}
+impl_validated!(Amount Id Instant);
+
+/// Why a value was rejected.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ValidationError {
+    /// The value is below the allowed range.
+    TooSmall,
+    /// The value is above the allowed range.
+    TooLarge,
+    /// The value is in range, but otherwise not acceptable (for example, a wrong format).
+    Invalid,
+    /// A failure specific to the unit (or entity).
+    Custom(StaticError),
+}
+
+impl ValidationError {
+    /// A short, fixed description. For [ValidationError::Custom] this is its message.
+    pub const fn message(&self) -> &'static str {
+        match self {
+            Self::TooSmall => "value too small",
+            Self::TooLarge => "value too large",
+            Self::Invalid => "invalid value",
+            Self::Custom(error) => error.message(),
+        }
+    }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Custom(error) => fmt::Display::fmt(error, f),
+            _ => f.write_str(self.message()),
+        }
+    }
+}
+
+impl core::error::Error for ValidationError {}
+
+/// A unit-specific validation failure: a code (for example, for logging or for a wire protocol)
+/// and a static message. Both are chosen by the unit's validator.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct StaticError {
+    code: u16,
+    message: &'static str,
+}
+
+impl StaticError {
+    pub const fn new(code: u16, message: &'static str) -> Self {
+        Self { code, message }
+    }
+
+    pub const fn code(&self) -> u16 {
+        self.code
+    }
+
+    pub const fn message(&self) -> &'static str {
+        self.message
+    }
+}
+
+impl fmt::Display for StaticError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (code {})", self.message, self.code)
+    }
+}
+
+impl From<StaticError> for ValidationError {
+    fn from(error: StaticError) -> Self {
+        Self::Custom(error)
+    }
+}
+
+#[cfg(feature = "ufmt")]
+impl ufmt::uDisplay for ValidationError {
+    fn fmt<W: ufmt::uWrite + ?Sized>(
+        &self,
+        f: &mut ufmt::Formatter<'_, W>,
+    ) -> Result<(), W::Error> {
+        match self {
+            Self::Custom(error) => ufmt::uDisplay::fmt(error, f),
+            _ => f.write_str(self.message()),
+        }
+    }
+}
+
+#[cfg(feature = "ufmt")]
+impl ufmt::uDisplay for StaticError {
+    fn fmt<W: ufmt::uWrite + ?Sized>(
+        &self,
+        f: &mut ufmt::Formatter<'_, W>,
+    ) -> Result<(), W::Error> {
+        ufmt::uwrite!(f, "{} (code {})", self.message, self.code)
+    }
+}
@@ -0,0 +1,44 @@
+// Copyright 2024 Peter Lyons Kehl
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! `valuable::Valuable` of [Amount]s, [Id]s and [Instant]s: it delegates to `Repr`, so that
+//! structured loggers (like `tracing` with `valuable` support) record a wrapper as its `Repr`,
+//! keeping it numeric. Requires feature `valuable`.
+
+use crate::amount::Amount;
+use crate::id::Id;
+use crate::instant::Instant;
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+use crate::trait_flag::TraitFlags;
+use valuable::{Valuable, Value, Visit};
+
+macro_rules! impl_valuable {
+    ($($wrapper:ident)*) => {
+        $(
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<const TF: TraitFlags, Unit, Repr: Valuable> Valuable for $wrapper<TF, Unit, Repr> {
+                fn as_value(&self) -> Value<'_> {
+                    self.0.as_value()
+                }
+                fn visit(&self, visit: &mut dyn Visit) {
+                    self.0.visit(visit)
+                }
+                fn visit_slice(slice: &[Self], visit: &mut dyn Visit) {
+                    Repr::visit_slice($wrapper::peel_slice(slice), visit)
+                }
+            }
+        )*
+    };
+}
+impl_valuable!(Amount Id Instant);
@@ -0,0 +1,72 @@
+// Copyright 2024 Peter Lyons Kehl
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Variance of the wrappers in their unit (or entity) marker.
+//!
+//! [crate::Amount]s, [crate::Id]s and [crate::Instant]s hold their marker as `PhantomData<fn() ->
+//! Unit>`. So, no matter the marker, they are `Send`, `Sync`, `Unpin`, `UnwindSafe` and
+//! `RefUnwindSafe` as far as `Repr` is, and they are covariant in the marker: a marker that
+//! borrows data shortens its lifetime like a reference does.
+//!
+//! ```
+//! #![cfg_attr(
+//!     feature = "unstable_generic_const_own_type",
+//!     feature(generic_const_exprs)
+//! )]
+//!
+//! use phantom_newtype::Id;
+//!
+//! struct Row<'a>(&'a str);
+//!
+//! fn shorten<'a>(id: Id<Row<'static>, u64>) -> Id<Row<'a>, u64> {
+//!     id
+//! }
+//!
+//! fn assert_unwind_safe<T: std::panic::UnwindSafe + std::panic::RefUnwindSafe>() {}
+//! assert_unwind_safe::<Id<core::cell::Cell<u8>, u64>>();
+//! assert_eq!(shorten(Id::from(1)), Id::from(1));
+//! ```
+//!
+//! But a `Repr` that isn't `UnwindSafe` (or `RefUnwindSafe`) still makes the wrapper so:
+//!
+//! ```compile_fail
+//! use phantom_newtype::Amount;
+//!
+//! let counter = Amount::<(), core::cell::Cell<u32>>::new(core::cell::Cell::new(1));
+//! let _ = std::panic::catch_unwind(|| counter.get().set(2));
+//! ```
+//!
+//! Wrap the marker in [Invariant] to make the wrapper invariant in it instead.
+
+use core::marker::PhantomData;
+
+/// A marker adapter that makes [crate::Amount]s, [crate::Id]s and [crate::Instant]s of it invariant
+/// in `T`, like when `T` carries a lifetime that must not be shortened (or lengthened), such as a
+/// brand. It has no values; it only tags.
+///
+/// Traits that the wrappers take from their marker (like [crate::DisplayerOf] or
+/// [crate::UnitLabel]) must be implemented for `Invariant<T>` itself.
+///
+/// ```compile_fail
+/// use phantom_newtype::{Id, Invariant};
+///
+/// struct Row<'a>(&'a str);
+///
+/// fn shorten<'a>(id: Id<Invariant<Row<'static>>, u64>) -> Id<Invariant<Row<'a>>, u64> {
+///     id
+/// }
+/// ```
+pub struct Invariant<T: ?Sized>(PhantomData<fn(&T) -> &T>, Uninhabited);
+
+enum Uninhabited {}
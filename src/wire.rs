@@ -0,0 +1,232 @@
+// Copyright 2024 Peter Lyons Kehl
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Cursor-based reading and writing of typed values from/to byte buffers, so that protocol
+//! decoders get units right at the first parse step.
+//!
+//! Readers take `&mut &[u8]` and advance it past what they read. Writers take `&mut &mut [u8]` and
+//! advance it past what they wrote. On error, the cursor is left unchanged.
+//!
+//! ```
+//! #![cfg_attr(
+//!     feature = "unstable_generic_const_own_type",
+//!     feature(generic_const_exprs)
+//! )]
+//!
+//! use phantom_newtype::wire::{self, Endian, WireError};
+//! use phantom_newtype::{Amount, Id};
+//!
+//! enum Account {}
+//! enum Cents {}
+//!
+//! let packet = [0, 0, 0, 7, 0xE8, 0x03, 0, 0, 0, 0, 0, 0];
+//! let mut cursor = &packet[..];
+//!
+//! let account: Id<Account, u32> = wire::read_id(&mut cursor, Endian::Big).unwrap();
+//! let amount: Amount<Cents, u64> = wire::read_amount(&mut cursor, Endian::Little).unwrap();
+//! assert_eq!(account, Id::from(7));
+//! assert_eq!(amount, Amount::new(1000));
+//! assert!(cursor.is_empty());
+//! let missing: Result<Id<Account, u32>, _> = wire::read_id(&mut cursor, Endian::Big);
+//! assert_eq!(missing, Err(WireError::UnexpectedEnd));
+//!
+//! let mut buffer = [0u8; 12];
+//! let mut out = &mut buffer[..];
+//! wire::write_id(&mut out, &account, Endian::Big).unwrap();
+//! wire::write_amount(&mut out, &amount, Endian::Little).unwrap();
+//! assert!(out.is_empty());
+//! assert_eq!(buffer, packet);
+//! ```
+
+use crate::amount::Amount;
+use crate::id::Id;
+use crate::instant::Instant;
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+use crate::trait_flag::TraitFlags;
+use core::fmt;
+
+/// Byte order of integers on the wire.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Endian {
+    Big,
+    Little,
+}
+
+/// Why reading or writing failed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum WireError {
+    /// The input ended before the whole value.
+    UnexpectedEnd,
+    /// The output has no space left for the whole value.
+    NoSpace,
+}
+
+impl fmt::Display for WireError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::UnexpectedEnd => "unexpected end of input",
+            Self::NoSpace => "no space left in output",
+        })
+    }
+}
+
+impl core::error::Error for WireError {}
+
+/// A `Repr` with a fixed-size wire encoding. Implemented for primitive integers, and for byte
+/// arrays (which ignore [Endian]).
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` has no wire encoding",
+    label = "no `WireRepr` for `{Self}`",
+    note = "`WireRepr` is implemented for primitive integers and byte arrays; implement it for your own `Repr`"
+)]
+pub trait WireRepr: Sized {
+    /// Number of bytes on the wire.
+    const SIZE: usize;
+
+    /// Decode from exactly [WireRepr::SIZE] bytes.
+    fn decode(bytes: &[u8], endian: Endian) -> Self;
+    /// Encode into exactly [WireRepr::SIZE] bytes.
+    fn encode(&self, bytes: &mut [u8], endian: Endian);
+}
+
+macro_rules! impl_wire_repr {
+    ($($t:ty)*) => {
+        $(
+            impl WireRepr for $t {
+                const SIZE: usize = core::mem::size_of::<$t>();
+
+                fn decode(bytes: &[u8], endian: Endian) -> Self {
+                    let mut array = [0; core::mem::size_of::<$t>()];
+                    array.copy_from_slice(bytes);
+                    match endian {
+                        Endian::Big => <$t>::from_be_bytes(array),
+                        Endian::Little => <$t>::from_le_bytes(array),
+                    }
+                }
+                fn encode(&self, bytes: &mut [u8], endian: Endian) {
+                    bytes.copy_from_slice(&match endian {
+                        Endian::Big => self.to_be_bytes(),
+                        Endian::Little => self.to_le_bytes(),
+                    });
+                }
+            }
+        )*
+    };
+}
+impl_wire_repr!(u8 u16 u32 u64 u128 i8 i16 i32 i64 i128);
+
+impl<const N: usize> WireRepr for [u8; N] {
+    const SIZE: usize = N;
+
+    fn decode(bytes: &[u8], _endian: Endian) -> Self {
+        let mut array = [0; N];
+        array.copy_from_slice(bytes);
+        array
+    }
+    fn encode(&self, bytes: &mut [u8], _endian: Endian) {
+        bytes.copy_from_slice(self);
+    }
+}
+
+/// Read a `Repr` and advance `input` past it.
+pub fn read_repr<Repr: WireRepr>(input: &mut &[u8], endian: Endian) -> Result<Repr, WireError> {
+    if input.len() < Repr::SIZE {
+        return Err(WireError::UnexpectedEnd);
+    }
+    let (head, rest) = input.split_at(Repr::SIZE);
+    *input = rest;
+    Ok(Repr::decode(head, endian))
+}
+
+/// Write a `Repr` and advance `output` past it.
+pub fn write_repr<Repr: WireRepr>(
+    output: &mut &mut [u8],
+    repr: &Repr,
+    endian: Endian,
+) -> Result<(), WireError> {
+    if output.len() < Repr::SIZE {
+        return Err(WireError::NoSpace);
+    }
+    let (head, rest) = core::mem::take(output).split_at_mut(Repr::SIZE);
+    repr.encode(head, endian);
+    *output = rest;
+    Ok(())
+}
+
+macro_rules! impl_read_write {
+    ($($wrapper:ident $read:ident $write:ident;)*) => {
+        $(
+            #[doc = concat!("Read an [", stringify!($wrapper), "] and advance `input` past it.")]
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            pub fn $read<const TF: TraitFlags, Unit, Repr: WireRepr>(
+                input: &mut &[u8],
+                endian: Endian,
+            ) -> Result<$wrapper<TF, Unit, Repr>, WireError> {
+                read_repr(input, endian).map($wrapper::new)
+            }
+
+            #[doc = concat!("Write an [", stringify!($wrapper), "] and advance `output` past it.")]
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            pub fn $write<const TF: TraitFlags, Unit, Repr: WireRepr>(
+                output: &mut &mut [u8],
+                value: &$wrapper<TF, Unit, Repr>,
+                endian: Endian,
+            ) -> Result<(), WireError> {
+                write_repr(output, value.get(), endian)
+            }
+        )*
+    };
+}
+impl_read_write! {
+    Amount read_amount write_amount;
+    Id read_id write_id;
+    Instant read_instant write_instant;
+}
+
+/// Borrow an [Id] with a byte array `Repr` (like a hash) directly from `input`, without copying,
+/// and advance `input` past it.
+///
+/// ```
+/// #![cfg_attr(
+///     feature = "unstable_generic_const_own_type",
+///     feature(generic_const_exprs)
+/// )]
+///
+/// use phantom_newtype::wire;
+///
+/// enum Block {}
+///
+/// #[cfg(not(feature = "unstable_generic_const_own_type"))]
+/// type BlockHash = phantom_newtype::Id<Block, [u8; 4]>;
+/// #[cfg(feature = "unstable_generic_const_own_type")]
+/// type BlockHash = phantom_newtype::IdForFlags<{phantom_newtype::trait_flag::TRAIT_FLAGS_NO_COPY_NO_DEFAULT}, Block, [u8; 4]>;
+///
+/// let packet = [1, 2, 3, 4, 5];
+/// let mut cursor = &packet[..];
+/// let hash: &BlockHash = wire::read_id_ref(&mut cursor).unwrap();
+/// assert_eq!(hash.get(), &[1, 2, 3, 4]);
+/// assert_eq!(cursor, [5]);
+/// ```
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+pub fn read_id_ref<'a, const TF: TraitFlags, Entity, const N: usize>(
+    input: &mut &'a [u8],
+) -> Result<&'a Id<TF, Entity, [u8; N]>, WireError> {
+    if input.len() < N {
+        return Err(WireError::UnexpectedEnd);
+    }
+    let (head, rest) = input.split_at(N);
+    *input = rest;
+    // SAFETY: `Id` is `#[repr(transparent)]` over its `Repr`, and `[u8; N]` has alignment 1.
+    Ok(unsafe { &*(head.as_ptr() as *const Id<TF, Entity, [u8; N]>) })
+}
@@ -0,0 +1,44 @@
+// Copyright 2024 Peter Lyons Kehl
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! `zeroize::Zeroize` and `zeroize::ZeroizeOnDrop` of [Amount]s, [Id]s and [Instant]s: they
+//! delegate to `Repr`, so dropping a wrapper drops its `Repr`, which wipes itself if it is
+//! `ZeroizeOnDrop`. Requires feature `zeroize`.
+
+use crate::amount::Amount;
+use crate::id::Id;
+use crate::instant::Instant;
+#[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+use crate::trait_flag::TraitFlags;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+macro_rules! impl_zeroize {
+    ($($wrapper:ident)*) => {
+        $(
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<const TF: TraitFlags, Unit, Repr: Zeroize> Zeroize for $wrapper<TF, Unit, Repr> {
+                fn zeroize(&mut self) {
+                    self.0.zeroize()
+                }
+            }
+
+            #[cfg_attr(feature = "unstable_generic_const_own_type", allow(deprecated))]
+            impl<const TF: TraitFlags, Unit, Repr: ZeroizeOnDrop> ZeroizeOnDrop
+                for $wrapper<TF, Unit, Repr>
+            {
+            }
+        )*
+    };
+}
+impl_zeroize!(Amount Id Instant);
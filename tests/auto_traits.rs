@@ -0,0 +1,73 @@
+// Copyright 2024 Peter Lyons Kehl
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Auto traits of amounts, ids and instants depend on `Repr` only, never on the unit (or entity)
+//! marker, so they cross threads and `catch_unwind` boundaries whenever `Repr` does.
+#![cfg_attr(
+    feature = "unstable_generic_const_own_type",
+    feature(generic_const_exprs)
+)]
+#![cfg_attr(
+    feature = "unstable_generic_const_own_type",
+    allow(incomplete_features)
+)]
+
+use core::cell::{Cell, RefCell};
+use phantom_newtype::{Amount, Id, Instant, Invariant};
+use std::panic::{self, AssertUnwindSafe, RefUnwindSafe, UnwindSafe};
+use std::rc::Rc;
+
+/// A marker that is neither `Send`, `Sync`, `UnwindSafe` nor `RefUnwindSafe`.
+type Hostile = (*mut u8, Rc<()>, Cell<u8>, RefCell<u8>, &'static mut u8);
+
+fn assert_auto_traits<T: Send + Sync + Unpin + UnwindSafe + RefUnwindSafe>() {}
+
+#[test]
+fn regardless_of_marker() {
+    assert_auto_traits::<Amount<Hostile, u64>>();
+    assert_auto_traits::<Id<Hostile, [u8; 32]>>();
+    assert_auto_traits::<Instant<Hostile, i64>>();
+    assert_auto_traits::<Amount<Invariant<Hostile>, u64>>();
+    assert_auto_traits::<Id<Rc<()>, u64>>();
+    assert_auto_traits::<phantom_newtype::TypedFlags<Hostile, u8>>();
+    #[cfg(feature = "atomics")]
+    {
+        assert_auto_traits::<phantom_newtype::IdGenerator<Hostile, u64>>();
+        assert_auto_traits::<phantom_newtype::AtomicId<Hostile, u64>>();
+        assert_auto_traits::<phantom_newtype::AtomicAmount<Hostile, u64>>();
+        assert_auto_traits::<phantom_newtype::AtomicInstant<Hostile, u64>>();
+    }
+}
+
+#[test]
+fn crosses_catch_unwind() {
+    let id = Id::<Hostile, u64>::from(7);
+    let caught = panic::catch_unwind(|| {
+        let _ = &id;
+        panic!("request handler failed");
+    });
+    assert!(caught.is_err());
+    assert_eq!(panic::catch_unwind(|| *id.get()).unwrap(), 7);
+}
+
+#[test]
+fn follows_repr() {
+    // `Cell` is not `RefUnwindSafe`, so neither is an amount of it: only `AssertUnwindSafe` lets a
+    // reference to it into `catch_unwind`. (That it doesn't compile otherwise is checked by a
+    // `compile_fail` example in the `variance` documentation.)
+    let counter = Amount::<(), Cell<u32>>::new(Cell::new(1));
+    let result = panic::catch_unwind(AssertUnwindSafe(|| counter.get().set(2)));
+    assert!(result.is_ok());
+    assert_eq!(counter.get().get(), 2);
+}
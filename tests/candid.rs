@@ -0,0 +1,68 @@
+// Copyright 2024 Peter Lyons Kehl
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Amounts, ids and instants have the Candid type of their `Repr`, and round-trip through Candid
+//! like it.
+#![cfg(feature = "candid")]
+#![cfg_attr(
+    feature = "unstable_generic_const_own_type",
+    feature(generic_const_exprs)
+)]
+#![cfg_attr(
+    feature = "unstable_generic_const_own_type",
+    allow(incomplete_features)
+)]
+
+use candid::{decode_args, encode_args, CandidType, Deserialize};
+use phantom_newtype::{Amount, Id, Instant};
+
+enum User {}
+enum Cents {}
+enum UnixSeconds {}
+
+type UserId = Id<User, u64>;
+type Balance = Amount<Cents, i64>;
+type Timestamp = Instant<UnixSeconds, i64>;
+
+#[derive(CandidType, Deserialize, Debug, PartialEq)]
+struct Account {
+    owner: UserId,
+    balance: Balance,
+    updated: Timestamp,
+}
+
+#[test]
+fn types_match_repr() {
+    assert_eq!(UserId::ty(), u64::ty());
+    assert_eq!(Balance::ty(), i64::ty());
+    assert_eq!(Id::<User, String>::ty(), String::ty());
+}
+
+#[test]
+fn round_trip() {
+    let account = Account {
+        owner: UserId::from(7),
+        balance: Balance::new(-250),
+        updated: Timestamp::new(1_700_000_000),
+    };
+    let bytes = encode_args((&account, UserId::from(8))).unwrap();
+    let (decoded, other): (Account, UserId) = decode_args(&bytes).unwrap();
+    assert_eq!(decoded, account);
+    assert_eq!(other, UserId::from(8));
+
+    assert_eq!(
+        encode_args((Balance::new(-250),)).unwrap(),
+        encode_args((-250i64,)).unwrap()
+    );
+}
@@ -0,0 +1,154 @@
+// Copyright 2024 Peter Lyons Kehl
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Instants of `i64` convert to `chrono` and `time` date-times and back unchanged, and
+//! date-times convert to instants rounding down (also before the epoch).
+#![cfg(any(feature = "chrono", feature = "time"))]
+#![cfg_attr(
+    feature = "unstable_generic_const_own_type",
+    feature(generic_const_exprs)
+)]
+#![cfg_attr(
+    feature = "unstable_generic_const_own_type",
+    allow(incomplete_features)
+)]
+
+use core::convert::TryFrom;
+use phantom_newtype::time::{TimeOutOfRange, UnixMillis, UnixNanos, UnixSeconds};
+use phantom_newtype::Instant;
+
+const SAMPLES: [i64; 7] = [
+    0,
+    1,
+    -1,
+    1_700_000_000,
+    -14_182_940,
+    253_402_300_799, // 9999-12-31T23:59:59Z
+    -62_135_596_800, // 0001-01-01T00:00:00Z
+];
+
+#[cfg(feature = "chrono")]
+mod chrono_interop {
+    use super::*;
+    use chrono::{DateTime, Utc};
+
+    #[test]
+    fn round_trips() {
+        for secs in SAMPLES {
+            let seconds = Instant::<UnixSeconds, i64>::from(secs);
+            let time = DateTime::<Utc>::try_from(seconds).unwrap();
+            assert_eq!(time.timestamp(), secs);
+            assert_eq!(Instant::try_from(time), Ok(seconds));
+
+            let millis = Instant::<UnixMillis, i64>::from(secs * 1_000 + 7);
+            let time = DateTime::<Utc>::try_from(millis).unwrap();
+            assert_eq!(Instant::try_from(time), Ok(millis));
+        }
+        let nanos = Instant::<UnixNanos, i64>::from(i64::MIN);
+        let time = DateTime::<Utc>::try_from(nanos).unwrap();
+        assert_eq!(Instant::try_from(time), Ok(nanos));
+    }
+
+    #[test]
+    fn rounds_down() {
+        let time = DateTime::from_timestamp(-2, 999_999_999).unwrap();
+        assert_eq!(
+            Instant::<UnixSeconds, i64>::try_from(time),
+            Ok(Instant::from(-2))
+        );
+        assert_eq!(
+            Instant::<UnixMillis, i64>::try_from(time),
+            Ok(Instant::from(-1_001))
+        );
+    }
+
+    #[test]
+    fn out_of_range() {
+        let far = Instant::<UnixSeconds, i64>::from(i64::MAX);
+        assert_eq!(DateTime::<Utc>::try_from(far), Err(TimeOutOfRange));
+        let max = DateTime::<Utc>::MAX_UTC;
+        assert_eq!(
+            Instant::<UnixNanos, i64>::try_from(max),
+            Err(TimeOutOfRange)
+        );
+    }
+
+    #[test]
+    fn rfc3339_matches_chrono() {
+        use chrono::{Datelike, Timelike};
+
+        for secs in SAMPLES {
+            let millis = Instant::<UnixMillis, i64>::from(secs * 1_000 + 7);
+            let time = DateTime::<Utc>::try_from(millis).unwrap();
+            let expected = format!(
+                "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+                time.year(),
+                time.month(),
+                time.day(),
+                time.hour(),
+                time.minute(),
+                time.second(),
+                time.timestamp_subsec_millis()
+            );
+            assert_eq!(millis.rfc3339().to_string(), expected);
+        }
+    }
+}
+
+#[cfg(feature = "time")]
+mod time_interop {
+    use super::*;
+    use time::OffsetDateTime;
+
+    #[test]
+    fn round_trips() {
+        for secs in SAMPLES {
+            let seconds = Instant::<UnixSeconds, i64>::from(secs);
+            let time = OffsetDateTime::try_from(seconds).unwrap();
+            assert_eq!(time.unix_timestamp(), secs);
+            assert_eq!(Instant::try_from(time), Ok(seconds));
+
+            let millis = Instant::<UnixMillis, i64>::from(secs * 1_000 + 7);
+            let time = OffsetDateTime::try_from(millis).unwrap();
+            assert_eq!(Instant::try_from(time), Ok(millis));
+        }
+        let nanos = Instant::<UnixNanos, i64>::from(i64::MIN);
+        let time = OffsetDateTime::try_from(nanos).unwrap();
+        assert_eq!(Instant::try_from(time), Ok(nanos));
+    }
+
+    #[test]
+    fn rounds_down() {
+        let time = OffsetDateTime::from_unix_timestamp_nanos(-1_000_000_001).unwrap();
+        assert_eq!(
+            Instant::<UnixSeconds, i64>::try_from(time),
+            Ok(Instant::from(-2))
+        );
+        assert_eq!(
+            Instant::<UnixMillis, i64>::try_from(time),
+            Ok(Instant::from(-1_001))
+        );
+    }
+
+    #[test]
+    fn out_of_range() {
+        let far = Instant::<UnixSeconds, i64>::from(i64::MAX);
+        assert_eq!(OffsetDateTime::try_from(far), Err(TimeOutOfRange));
+        let late = OffsetDateTime::from_unix_timestamp(253_402_300_799).unwrap();
+        assert_eq!(
+            Instant::<UnixNanos, i64>::try_from(late),
+            Err(TimeOutOfRange)
+        );
+    }
+}
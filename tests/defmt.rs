@@ -0,0 +1,41 @@
+// Copyright 2024 Peter Lyons Kehl
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Amounts, ids and instants are `defmt::Format` whenever their `Repr` is. (Logging itself needs a
+//! `defmt` global logger and linker script of an embedded target, so this only checks the impls.)
+#![cfg(feature = "defmt")]
+#![cfg_attr(
+    feature = "unstable_generic_const_own_type",
+    feature(generic_const_exprs)
+)]
+#![cfg_attr(
+    feature = "unstable_generic_const_own_type",
+    allow(incomplete_features)
+)]
+
+use phantom_newtype::{Amount, Id, Instant};
+
+enum Ticks {}
+enum Sensor {}
+enum MilliCelsius {}
+
+fn assert_format<T: defmt::Format>() {}
+
+#[test]
+fn format() {
+    assert_format::<Instant<Ticks, u32>>();
+    assert_format::<Id<Sensor, u8>>();
+    assert_format::<Amount<MilliCelsius, i32>>();
+    assert_format::<Id<Sensor, [u8; 6]>>();
+}
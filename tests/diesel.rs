@@ -0,0 +1,99 @@
+// Copyright 2024 Peter Lyons Kehl
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Amounts, ids and instants as column types of a Diesel schema (like typed primary keys), against
+//! an in-memory SQLite database.
+#![cfg(feature = "diesel")]
+#![cfg_attr(
+    feature = "unstable_generic_const_own_type",
+    feature(generic_const_exprs)
+)]
+#![cfg_attr(
+    feature = "unstable_generic_const_own_type",
+    allow(incomplete_features)
+)]
+
+use diesel::prelude::*;
+use diesel::sqlite::SqliteConnection;
+use phantom_newtype::{Amount, Id, Instant};
+
+enum User {}
+enum Cents {}
+enum UnixSeconds {}
+
+type UserId = Id<User, i64>;
+type Balance = Amount<Cents, i64>;
+type Timestamp = Instant<UnixSeconds, i64>;
+
+diesel::table! {
+    account (user_id) {
+        user_id -> BigInt,
+        balance -> BigInt,
+        updated -> Nullable<BigInt>,
+    }
+}
+
+#[derive(Queryable, Insertable, PartialEq, Debug)]
+#[diesel(table_name = account)]
+struct Account {
+    user_id: UserId,
+    balance: Balance,
+    updated: Option<Timestamp>,
+}
+
+#[test]
+fn typed_columns() {
+    let mut conn = SqliteConnection::establish(":memory:").unwrap();
+    diesel::sql_query(
+        "CREATE TABLE account (user_id BIGINT PRIMARY KEY NOT NULL, balance BIGINT NOT NULL, \
+         updated BIGINT)",
+    )
+    .execute(&mut conn)
+    .unwrap();
+
+    let alice = Account {
+        user_id: UserId::from(7),
+        balance: Balance::new(-250),
+        updated: Some(Timestamp::new(1_700_000_000)),
+    };
+    let bob = Account {
+        user_id: UserId::from(8),
+        balance: Balance::new(100),
+        updated: None,
+    };
+    diesel::insert_into(account::table)
+        .values([&alice, &bob])
+        .execute(&mut conn)
+        .unwrap();
+
+    let found: Account = account::table
+        .find(UserId::from(7))
+        .first(&mut conn)
+        .unwrap();
+    assert_eq!(found, alice);
+
+    let rich: Vec<UserId> = account::table
+        .filter(account::balance.gt(Balance::new(0)))
+        .select(account::user_id)
+        .load(&mut conn)
+        .unwrap();
+    assert_eq!(rich, [bob.user_id]);
+
+    let never_updated: Vec<UserId> = account::table
+        .filter(account::updated.is_null())
+        .select(account::user_id)
+        .load(&mut conn)
+        .unwrap();
+    assert_eq!(never_updated, [UserId::from(8)]);
+}
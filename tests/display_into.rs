@@ -0,0 +1,106 @@
+// Copyright 2024 Peter Lyons Kehl
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! `display_into` streams into an existing buffer without allocating, the same as `write!` with
+//! `display()` does. A counting global allocator checks that (this test binary has only one test,
+//! so that no other test allocates meanwhile).
+#![cfg_attr(
+    feature = "unstable_generic_const_own_type",
+    feature(generic_const_exprs)
+)]
+#![cfg_attr(
+    feature = "unstable_generic_const_own_type",
+    allow(incomplete_features)
+)]
+
+use core::fmt::{self, Write};
+use phantom_newtype::{Amount, DisplayerOf, Instant};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+enum Blob {}
+#[cfg(not(feature = "unstable_generic_const_own_type"))]
+type BlobId = phantom_newtype::Id<Blob, [u8; 1024]>;
+#[cfg(feature = "unstable_generic_const_own_type")]
+type BlobId = phantom_newtype::IdForFlags<
+    { phantom_newtype::trait_flag::TRAIT_FLAGS_NO_COPY_NO_DEFAULT },
+    Blob,
+    [u8; 1024],
+>;
+
+impl DisplayerOf<BlobId> for Blob {
+    fn display(id: &BlobId, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        id.get().iter().try_for_each(|b| write!(f, "{:02x}", b))
+    }
+}
+
+enum Seconds {}
+impl DisplayerOf<Amount<Seconds, u64>> for Seconds {
+    fn display(amount: &Amount<Seconds, u64>, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} s", amount.get())
+    }
+}
+impl DisplayerOf<Instant<Seconds, u64>> for Seconds {
+    fn display(instant: &Instant<Seconds, u64>, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "at {} s", instant.get())
+    }
+}
+
+#[test]
+fn display_into_does_not_allocate() {
+    let id = BlobId::from([0xa5; 1024]);
+    let amount = Amount::<Seconds, u64>::from(42);
+    let instant = Instant::<Seconds, u64>::from(7);
+    let mut buffer = String::with_capacity(4096);
+
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    id.display_into(&mut buffer).unwrap();
+    amount.display_into(&mut buffer).unwrap();
+    instant.display_into(&mut buffer).unwrap();
+    let streamed = ALLOCATIONS.load(Ordering::Relaxed) - before;
+    assert_eq!(streamed, 0);
+    assert_eq!(buffer.len(), 2048 + "42 s".len() + "at 7 s".len());
+
+    // The same as `write!` with `display()`.
+    let mut written = String::with_capacity(4096);
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    write!(
+        written,
+        "{}{}{}",
+        id.display(),
+        amount.display(),
+        instant.display()
+    )
+    .unwrap();
+    assert_eq!(ALLOCATIONS.load(Ordering::Relaxed) - before, 0);
+    assert_eq!(written, buffer);
+}
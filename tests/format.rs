@@ -0,0 +1,96 @@
+// Copyright 2024 Peter Lyons Kehl
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Each formatting trait, with flags (width, fill, alignment, sign, precision, `#`, `0`), formats
+//! amounts, ids and instants exactly as it formats their `Repr`.
+#![cfg_attr(
+    feature = "unstable_generic_const_own_type",
+    feature(generic_const_exprs)
+)]
+#![cfg_attr(
+    feature = "unstable_generic_const_own_type",
+    allow(incomplete_features)
+)]
+
+use phantom_newtype::{Amount, AmountNoCopy, Id, IdNoCopy, Instant, InstantNoCopy};
+
+enum Unit {}
+
+/// Asserts that each format string formats `$wrapped` as it formats `$repr`.
+macro_rules! assert_same_format {
+    ($wrapped:expr, $repr:expr; $($format:literal)*) => {
+        $(
+            assert_eq!(format!($format, $wrapped), format!($format, $repr), "format {}", $format);
+        )*
+    };
+}
+
+macro_rules! format_tests {
+    ($($module:ident: $wrapper:ident;)*) => {
+        $(
+            mod $module {
+                use super::*;
+
+                #[test]
+                fn display() {
+                    let value: $wrapper<Unit, i32> = (-42).into();
+                    assert_same_format!(value, -42i32;
+                        "{}" "{:8}" "{:<8}" "{:^8}" "{:>8}" "{:*^9}" "{:+}" "{:08}" "{:+08}");
+                    let text: $wrapper<Unit, &str> = "abc".into();
+                    assert_same_format!(text, "abc"; "{:>6}" "{:-<6}" "{:.2}" "{:>6.2}");
+                }
+
+                #[test]
+                fn display_float() {
+                    let value: $wrapper<Unit, f64> = 1.23456.into();
+                    assert_same_format!(value, 1.23456f64;
+                        "{}" "{:.3}" "{:10.2}" "{:<10.1}" "{:+.0}" "{:010.4}");
+                }
+
+                #[test]
+                fn integer_radixes() {
+                    let value: $wrapper<Unit, u32> = 3054.into();
+                    assert_same_format!(value, 3054u32;
+                        "{:x}" "{:#x}" "{:08x}" "{:#010x}" "{:X}" "{:#X}" "{:>8X}"
+                        "{:o}" "{:#o}" "{:b}" "{:#b}" "{:016b}");
+                }
+
+                #[test]
+                fn exponents() {
+                    let value: $wrapper<Unit, f64> = 1234.5.into();
+                    assert_same_format!(value, 1234.5f64;
+                        "{:e}" "{:.2e}" "{:12.1e}" "{:E}" "{:.3E}" "{:<12E}");
+                }
+
+                #[test]
+                fn debug() {
+                    let value: $wrapper<Unit, i32> = (-42).into();
+                    assert_same_format!(value, -42i32; "{:?}" "{:8?}" "{:<8?}" "{:+?}" "{:08?}");
+                    let hex: $wrapper<Unit, u32> = 255.into();
+                    assert_same_format!(hex, 255u32; "{:x?}" "{:X?}" "{:6x?}");
+                    let text: $wrapper<Unit, &str> = "abc".into();
+                    assert_same_format!(text, "abc"; "{:?}" "{:>8?}");
+                }
+            }
+        )*
+    };
+}
+format_tests! {
+    amount: Amount;
+    amount_no_copy: AmountNoCopy;
+    id: Id;
+    id_no_copy: IdNoCopy;
+    instant: Instant;
+    instant_no_copy: InstantNoCopy;
+}
@@ -0,0 +1,167 @@
+// Copyright 2024 Peter Lyons Kehl
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Algebraic laws of the operator impls, checked with `proptest` for integer reprs and all
+//! combinations of trait flags. They guard the hand-written operator impls against asymmetries
+//! (like an `X` and `XAssign` pair that disagree).
+#![cfg_attr(
+    feature = "unstable_generic_const_own_type",
+    feature(generic_const_exprs)
+)]
+#![cfg_attr(
+    feature = "unstable_generic_const_own_type",
+    allow(incomplete_features)
+)]
+
+use phantom_newtype::{
+    Amount, AmountNoCopy, AmountNoCopyNoDefault, AmountNoDefault, Instant, InstantNoCopy,
+    InstantNoCopyNoDefault, InstantNoDefault, Scalar,
+};
+use proptest::prelude::*;
+use std::cmp::Ordering;
+
+enum Unit {}
+
+// Values small enough that sums, differences and products (of two of them) don't overflow `i64`.
+fn small() -> impl Strategy<Value = i64> {
+    -(1i64 << 30)..(1i64 << 30)
+}
+
+macro_rules! amount_laws {
+    ($($module:ident: $amount:ident;)*) => {
+        $(
+            mod $module {
+                use super::*;
+                type A = $amount<Unit, i64>;
+
+                proptest! {
+                    #[test]
+                    fn add_commutes(a in small(), b in small()) {
+                        prop_assert_eq!(A::new(a) + A::new(b), A::new(b) + A::new(a));
+                    }
+
+                    #[test]
+                    fn add_associates(a in small(), b in small(), c in small()) {
+                        prop_assert_eq!(
+                            (A::new(a) + A::new(b)) + A::new(c),
+                            A::new(a) + (A::new(b) + A::new(c))
+                        );
+                    }
+
+                    #[test]
+                    fn sub_inverts_add(a in small(), b in small()) {
+                        prop_assert_eq!(A::new(a) + A::new(b) - A::new(b), A::new(a));
+                    }
+
+                    #[test]
+                    fn assign_matches_binary(a in small(), b in small()) {
+                        let mut sum = A::new(a);
+                        sum += A::new(b);
+                        prop_assert_eq!(sum, A::new(a) + A::new(b));
+
+                        let mut difference = A::new(a);
+                        difference -= A::new(b);
+                        prop_assert_eq!(difference, A::new(a) - A::new(b));
+
+                        let mut product = A::new(a);
+                        product *= b;
+                        prop_assert_eq!(product, A::new(a) * b);
+
+                        let mut scaled = A::new(a);
+                        scaled *= Scalar(b as i32);
+                        prop_assert_eq!(scaled, A::new(a) * Scalar(b as i32));
+                    }
+
+                    #[test]
+                    fn ops_match_repr(a in small(), b in small()) {
+                        prop_assert_eq!((A::new(a) + A::new(b)).get_copy(), a + b);
+                        prop_assert_eq!((A::new(a) - A::new(b)).get_copy(), a - b);
+                        prop_assert_eq!((A::new(a) * b).get_copy(), a * b);
+                        if b != 0 {
+                            prop_assert_eq!(A::new(a) / A::new(b), a / b);
+                        }
+                    }
+
+                    #[test]
+                    fn order_is_total_and_matches_repr(a in any::<i64>(), b in any::<i64>()) {
+                        let ordering = A::new(a).cmp(&A::new(b));
+                        prop_assert_eq!(ordering, a.cmp(&b));
+                        prop_assert_eq!(A::new(b).cmp(&A::new(a)), ordering.reverse());
+                        prop_assert_eq!(A::new(a).partial_cmp(&A::new(b)), Some(ordering));
+                        prop_assert_eq!(A::new(a) == A::new(b), ordering == Ordering::Equal);
+                    }
+                }
+            }
+        )*
+    };
+}
+amount_laws! {
+    amount_is_copy_is_default: Amount;
+    amount_no_copy_is_default: AmountNoCopy;
+    amount_is_copy_no_default: AmountNoDefault;
+    amount_no_copy_no_default: AmountNoCopyNoDefault;
+}
+
+macro_rules! instant_laws {
+    ($($module:ident: $instant:ident, $amount:ident;)*) => {
+        $(
+            mod $module {
+                use super::*;
+                type I = $instant<Unit, i64>;
+                type A = $amount<Unit, i64>;
+
+                proptest! {
+                    #[test]
+                    fn sub_inverts_add(t in small(), d in small()) {
+                        prop_assert_eq!(I::new(t) + A::new(d) - A::new(d), I::new(t));
+                        prop_assert_eq!((I::new(t) + A::new(d)) - I::new(t), A::new(d));
+                    }
+
+                    #[test]
+                    fn add_commutes(t in small(), d in small()) {
+                        prop_assert_eq!(A::new(d) + I::new(t), I::new(t) + A::new(d));
+                    }
+
+                    #[test]
+                    fn assign_matches_binary(t in small(), d in small()) {
+                        let mut later = I::new(t);
+                        later += A::new(d);
+                        prop_assert_eq!(later, I::new(t) + A::new(d));
+
+                        let mut earlier = I::new(t);
+                        earlier -= A::new(d);
+                        prop_assert_eq!(earlier, I::new(t) - A::new(d));
+
+                        let mut product = I::new(t);
+                        product *= d;
+                        prop_assert_eq!(product, I::new(t) * d);
+                    }
+
+                    #[test]
+                    fn order_is_total_and_matches_repr(a in any::<i64>(), b in any::<i64>()) {
+                        let ordering = I::new(a).cmp(&I::new(b));
+                        prop_assert_eq!(ordering, a.cmp(&b));
+                        prop_assert_eq!(I::new(b).cmp(&I::new(a)), ordering.reverse());
+                    }
+                }
+            }
+        )*
+    };
+}
+instant_laws! {
+    instant_is_copy_is_default: Instant, Amount;
+    instant_no_copy_is_default: InstantNoCopy, AmountNoCopy;
+    instant_is_copy_no_default: InstantNoDefault, AmountNoDefault;
+    instant_no_copy_no_default: InstantNoCopyNoDefault, AmountNoCopyNoDefault;
+}
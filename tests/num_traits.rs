@@ -0,0 +1,89 @@
+// Copyright 2024 Peter Lyons Kehl
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Amounts (and instants) in generic code over `num-traits` bounds: zero, checked, saturating and
+//! wrapping arithmetic, and bounds, all like their `Repr`.
+#![cfg(feature = "num-traits")]
+#![cfg_attr(
+    feature = "unstable_generic_const_own_type",
+    feature(generic_const_exprs)
+)]
+#![cfg_attr(
+    feature = "unstable_generic_const_own_type",
+    allow(incomplete_features)
+)]
+
+use num_traits::{Bounded, CheckedAdd, CheckedSub, Saturating, WrappingAdd, Zero};
+use phantom_newtype::{Amount, Instant};
+
+enum Cents {}
+type Price = Amount<Cents, u32>;
+
+enum Seconds {}
+type Timestamp = Instant<Seconds, i64>;
+
+/// A typical accumulator of the `num` ecosystem: `None` on overflow.
+fn checked_total<T: Zero + CheckedAdd>(items: &[T]) -> Option<T> {
+    items
+        .iter()
+        .try_fold(T::zero(), |total, item| total.checked_add(item))
+}
+
+#[test]
+fn zero() {
+    assert_eq!(Price::zero(), Price::from(0));
+    assert!(Price::zero().is_zero());
+    assert!(!Price::from(1).is_zero());
+    assert_eq!(checked_total::<Price>(&[]), Some(Price::from(0)));
+}
+
+#[test]
+fn checked() {
+    let prices = [Price::from(100), Price::from(250)];
+    assert_eq!(checked_total(&prices), Some(Price::from(350)));
+    assert_eq!(checked_total(&[Price::max_value(), Price::from(1)]), None);
+
+    assert_eq!(
+        CheckedSub::checked_sub(&Price::from(5), &Price::from(3)),
+        Some(Price::from(2))
+    );
+    assert_eq!(
+        CheckedSub::checked_sub(&Price::from(3), &Price::from(5)),
+        None
+    );
+}
+
+#[test]
+fn saturating_and_wrapping() {
+    assert_eq!(
+        Saturating::saturating_add(Price::max_value(), Price::from(1)),
+        Price::max_value()
+    );
+    assert_eq!(
+        Saturating::saturating_sub(Price::from(1), Price::from(2)),
+        Price::min_value()
+    );
+    assert_eq!(
+        WrappingAdd::wrapping_add(&Price::max_value(), &Price::from(1)),
+        Price::from(0)
+    );
+}
+
+#[test]
+fn bounded() {
+    assert_eq!(Price::min_value(), Price::from(u32::MIN));
+    assert_eq!(Price::max_value(), Price::from(u32::MAX));
+    assert_eq!(Timestamp::min_value(), Timestamp::from(i64::MIN));
+    assert_eq!(Timestamp::max_value(), Timestamp::from(i64::MAX));
+}
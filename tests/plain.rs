@@ -0,0 +1,75 @@
+// Copyright 2024 Peter Lyons Kehl
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The structs of `prelude::plain` forward formatting, serde and scaling like the flagged types
+//! they wrap.
+#![cfg(feature = "plain")]
+#![cfg_attr(
+    feature = "unstable_generic_const_own_type",
+    feature(generic_const_exprs)
+)]
+#![cfg_attr(
+    feature = "unstable_generic_const_own_type",
+    allow(incomplete_features)
+)]
+
+use phantom_newtype::prelude::plain::{Amount, Id, Instant};
+use phantom_newtype::Scalar;
+
+enum User {}
+enum Meters {}
+enum Seconds {}
+
+#[test]
+fn formatting() {
+    assert_eq!(format!("{}", Id::<User, u64>::new(7)), "7");
+    assert_eq!(format!("{:>4}", Amount::<Meters, u32>::new(42)), "  42");
+    assert_eq!(format!("{:.2}", Instant::<Seconds, f64>::new(1.5)), "1.50");
+    assert_eq!(format!("{:#06x}", Id::<User, u32>::new(255)), "0x00ff");
+    assert_eq!(format!("{:X}", Amount::<Meters, u32>::new(255)), "FF");
+    assert_eq!(format!("{:o}", Instant::<Seconds, u8>::new(8)), "10");
+    assert_eq!(format!("{:b}", Id::<User, u8>::new(5)), "101");
+    assert_eq!(format!("{:e}", Amount::<Meters, f64>::new(1500.0)), "1.5e3");
+    assert_eq!(format!("{:E}", Instant::<Seconds, f64>::new(0.25)), "2.5E-1");
+    assert_eq!(format!("{:?}", Id::<User, u64>::new(7)), "7");
+}
+
+#[test]
+fn scaling() {
+    let length = Amount::<Meters, u64>::new(3);
+    assert_eq!(length * 2u64, Amount::new(6));
+    assert_eq!(length * Scalar(1.5), Amount::new(5));
+    assert_eq!(length / Scalar(2u8), Amount::new(1));
+
+    let mut total = length;
+    total *= 4u64;
+    total /= Scalar(3u32);
+    assert_eq!(total, Amount::new(4));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde() {
+    let user = Id::<User, u64>::new(7);
+    assert_eq!(serde_json::to_string(&user).unwrap(), "7");
+    assert_eq!(serde_json::from_str::<Id<User, u64>>("7").unwrap(), user);
+
+    let length = Amount::<Meters, f64>::new(2.5);
+    assert_eq!(serde_json::to_string(&length).unwrap(), "2.5");
+    assert_eq!(serde_json::from_str::<Amount<Meters, f64>>("2.5").unwrap(), length);
+
+    let at = Instant::<Seconds, i64>::new(-3);
+    assert_eq!(serde_json::to_string(&at).unwrap(), "-3");
+    assert_eq!(serde_json::from_str::<Instant<Seconds, i64>>("-3").unwrap(), at);
+}
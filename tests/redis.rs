@@ -0,0 +1,82 @@
+// Copyright 2024 Peter Lyons Kehl
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Amounts, ids and instants are written into commands, and parsed from replies, like their
+//! `Repr`. No server needed: this compares packed commands and parses `Value`s.
+#![cfg(feature = "redis")]
+#![cfg_attr(
+    feature = "unstable_generic_const_own_type",
+    feature(generic_const_exprs)
+)]
+#![cfg_attr(
+    feature = "unstable_generic_const_own_type",
+    allow(incomplete_features)
+)]
+
+use phantom_newtype::{Amount, Id, Instant};
+use redis::{FromRedisValue, Value};
+
+enum User {}
+enum Cents {}
+enum UnixSeconds {}
+
+type UserId = Id<User, u64>;
+type Balance = Amount<Cents, i64>;
+type Timestamp = Instant<UnixSeconds, i64>;
+
+#[test]
+fn commands_match_repr() {
+    let mut typed = redis::cmd("HSET");
+    typed
+        .arg(UserId::from(7))
+        .arg("balance")
+        .arg(Balance::new(-250))
+        .arg("updated")
+        .arg(Timestamp::new(1_700_000_000));
+    let mut plain = redis::cmd("HSET");
+    plain
+        .arg(7u64)
+        .arg("balance")
+        .arg(-250i64)
+        .arg("updated")
+        .arg(1_700_000_000i64);
+    assert_eq!(typed.get_packed_command(), plain.get_packed_command());
+
+    let name = Id::<User, String>::from("alice".to_owned());
+    assert_eq!(
+        redis::cmd("GET").arg(&name).get_packed_command(),
+        redis::cmd("GET").arg("alice").get_packed_command()
+    );
+}
+
+#[test]
+fn replies_parse_like_repr() {
+    assert_eq!(
+        UserId::from_redis_value(Value::Int(7)).unwrap(),
+        UserId::from(7)
+    );
+    assert_eq!(
+        Balance::from_redis_value(Value::BulkString(b"-250".to_vec())).unwrap(),
+        Balance::new(-250)
+    );
+    assert_eq!(
+        Option::<Timestamp>::from_redis_value(Value::Nil).unwrap(),
+        None
+    );
+    assert!(UserId::from_redis_value(Value::BulkString(b"seven".to_vec())).is_err());
+
+    let ids: Vec<UserId> =
+        redis::from_redis_value(Value::Array(vec![Value::Int(1), Value::Int(2)])).unwrap();
+    assert_eq!(ids, [UserId::from(1), UserId::from(2)]);
+}
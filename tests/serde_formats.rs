@@ -0,0 +1,124 @@
+// Copyright 2024 Peter Lyons Kehl
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Amounts, ids and instants (with all combinations of trait flags) serialize into exactly the
+//! same bytes as their `Repr`, and round-trip, in JSON, CBOR, bincode and postcard.
+#![cfg(feature = "serde")]
+#![cfg_attr(
+    feature = "unstable_generic_const_own_type",
+    feature(generic_const_exprs)
+)]
+#![cfg_attr(
+    feature = "unstable_generic_const_own_type",
+    allow(incomplete_features)
+)]
+
+use phantom_newtype::serde_fixed::serialized_size;
+use phantom_newtype::{
+    Amount, AmountNoCopy, AmountNoCopyNoDefault, AmountNoDefault, Id, IdNoCopy, IdNoCopyNoDefault,
+    IdNoDefault, Instant, InstantNoCopy, InstantNoCopyNoDefault, InstantNoDefault,
+};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fmt::Debug;
+
+enum Unit {}
+
+fn cbor<T: Serialize>(value: &T) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    ciborium::into_writer(value, &mut bytes).unwrap();
+    bytes
+}
+
+/// Checks that `wrapped` serializes as `repr` in each format, and that it deserializes back.
+fn check<W, R>(wrapped: W, repr: R)
+where
+    W: Serialize + DeserializeOwned + PartialEq + Debug,
+    R: Serialize,
+{
+    let json = serde_json::to_vec(&wrapped).unwrap();
+    assert_eq!(json, serde_json::to_vec(&repr).unwrap());
+    assert_eq!(serde_json::from_slice::<W>(&json).unwrap(), wrapped);
+
+    let cbor_bytes = cbor(&wrapped);
+    assert_eq!(cbor_bytes, cbor(&repr));
+    assert_eq!(
+        ciborium::from_reader::<W, _>(&cbor_bytes[..]).unwrap(),
+        wrapped
+    );
+
+    let bincode_bytes = bincode::serialize(&wrapped).unwrap();
+    assert_eq!(bincode_bytes, bincode::serialize(&repr).unwrap());
+    assert_eq!(bincode::deserialize::<W>(&bincode_bytes).unwrap(), wrapped);
+
+    let postcard_bytes = postcard::to_allocvec(&wrapped).unwrap();
+    assert_eq!(postcard_bytes, postcard::to_allocvec(&repr).unwrap());
+    assert_eq!(postcard::from_bytes::<W>(&postcard_bytes).unwrap(), wrapped);
+}
+
+macro_rules! format_tests {
+    ($($module:ident: $wrapper:ident;)*) => {
+        $(
+            mod $module {
+                use super::*;
+
+                #[test]
+                fn integers_match_repr() {
+                    for value in [0, 1, 127, 128, 300, u64::MAX] {
+                        check($wrapper::<Unit, u64>::from(value), value);
+                    }
+                    for value in [0, -1, -64, 65, i32::MIN, i32::MAX] {
+                        check($wrapper::<Unit, i32>::from(value), value);
+                    }
+                }
+
+                #[test]
+                fn byte_arrays_match_repr() {
+                    let mut hash = [0u8; 32];
+                    hash.iter_mut().enumerate().for_each(|(i, b)| *b = i as u8 * 7);
+                    let wrapped: $wrapper<Unit, [u8; 32]> = hash.into();
+                    check(wrapped, hash);
+                }
+
+                #[test]
+                fn bincode_size_is_fixed() {
+                    let wrapped: $wrapper<Unit, u64> = 5.into();
+                    assert_eq!(
+                        bincode::serialize(&wrapped).unwrap().len(),
+                        serialized_size::<$wrapper<Unit, u64>>()
+                    );
+                    let wrapped: $wrapper<Unit, [u8; 32]> = [1; 32].into();
+                    assert_eq!(
+                        bincode::serialize(&wrapped).unwrap().len(),
+                        serialized_size::<$wrapper<Unit, [u8; 32]>>()
+                    );
+                }
+            }
+        )*
+    };
+}
+format_tests! {
+    amount_is_copy_is_default: Amount;
+    amount_no_copy_is_default: AmountNoCopy;
+    amount_is_copy_no_default: AmountNoDefault;
+    amount_no_copy_no_default: AmountNoCopyNoDefault;
+    id_is_copy_is_default: Id;
+    id_no_copy_is_default: IdNoCopy;
+    id_is_copy_no_default: IdNoDefault;
+    id_no_copy_no_default: IdNoCopyNoDefault;
+    instant_is_copy_is_default: Instant;
+    instant_no_copy_is_default: InstantNoCopy;
+    instant_is_copy_no_default: InstantNoDefault;
+    instant_no_copy_no_default: InstantNoCopyNoDefault;
+}
@@ -0,0 +1,80 @@
+// Copyright 2024 Peter Lyons Kehl
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Amounts, ids and instants bind into queries and decode from rows like their `Repr`, against an
+//! in-memory SQLite database.
+#![cfg(feature = "sqlx")]
+#![cfg_attr(
+    feature = "unstable_generic_const_own_type",
+    feature(generic_const_exprs)
+)]
+#![cfg_attr(
+    feature = "unstable_generic_const_own_type",
+    allow(incomplete_features)
+)]
+
+use phantom_newtype::{Amount, Id, Instant};
+use sqlx::sqlite::SqliteConnection;
+use sqlx::{Connection, Row};
+
+enum User {}
+enum Cents {}
+enum UnixSeconds {}
+
+type UserId = Id<User, i64>;
+type Balance = Amount<Cents, i64>;
+type Timestamp = Instant<UnixSeconds, i64>;
+
+#[tokio::test(flavor = "current_thread")]
+async fn binds_and_decodes_like_repr() {
+    let mut conn = SqliteConnection::connect("sqlite::memory:").await.unwrap();
+    sqlx::query(
+        "CREATE TABLE account (user_id INTEGER, balance INTEGER, updated INTEGER, name TEXT)",
+    )
+    .execute(&mut conn)
+    .await
+    .unwrap();
+
+    sqlx::query("INSERT INTO account VALUES (?, ?, ?, ?)")
+        .bind(UserId::from(7))
+        .bind(Balance::new(-250))
+        .bind(Timestamp::new(1_700_000_000))
+        .bind(Id::<User, String>::from("alice".to_owned()))
+        .execute(&mut conn)
+        .await
+        .unwrap();
+
+    let row = sqlx::query("SELECT user_id, balance, updated, name FROM account WHERE user_id = ?")
+        .bind(UserId::from(7))
+        .fetch_one(&mut conn)
+        .await
+        .unwrap();
+    assert_eq!(row.get::<UserId, _>("user_id"), UserId::from(7));
+    assert_eq!(row.get::<Balance, _>("balance"), Balance::new(-250));
+    assert_eq!(
+        row.get::<Timestamp, _>("updated"),
+        Timestamp::new(1_700_000_000)
+    );
+    assert_eq!(
+        row.get::<Id<User, String>, _>("name"),
+        Id::from("alice".to_owned())
+    );
+
+    let (user_id, balance): (UserId, Balance) =
+        sqlx::query_as("SELECT user_id, balance FROM account")
+            .fetch_one(&mut conn)
+            .await
+            .unwrap();
+    assert_eq!((user_id, balance), (UserId::from(7), Balance::new(-250)));
+}
@@ -0,0 +1,53 @@
+// Copyright 2024 Peter Lyons Kehl
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Byte-backed ids are `subtle::ConstantTimeEq`, and `IdCtEq` compares with it.
+#![cfg(feature = "subtle")]
+#![cfg_attr(
+    feature = "unstable_generic_const_own_type",
+    feature(generic_const_exprs)
+)]
+#![cfg_attr(
+    feature = "unstable_generic_const_own_type",
+    allow(incomplete_features)
+)]
+
+use phantom_newtype::repr::{CtEq, Redacted};
+use phantom_newtype::{Id, IdCtEq, IdRedacted};
+use subtle::ConstantTimeEq;
+
+enum ApiKey {}
+
+#[test]
+fn byte_backed_ids_are_constant_time_eq() {
+    let key = Id::<ApiKey, [u8; 4]>::from(*b"abcd");
+    assert!(bool::from(key.ct_eq(&Id::from(*b"abcd"))));
+    assert!(!bool::from(key.ct_eq(&Id::from(*b"abcx"))));
+
+    let key = Id::<ApiKey, Vec<u8>>::from(b"abcd".to_vec());
+    assert!(bool::from(key.ct_eq(&Id::from(b"abcd".to_vec()))));
+    assert!(!bool::from(key.ct_eq(&Id::from(b"abc".to_vec()))));
+}
+
+#[test]
+fn id_ct_eq() {
+    let key = IdCtEq::<ApiKey, [u8; 4]>::from(CtEq(*b"abcd"));
+    assert_eq!(key, IdCtEq::from(CtEq(*b"abcd")));
+    assert_ne!(key, IdCtEq::from(CtEq(*b"xbcd")));
+    assert!(bool::from(key.ct_eq(&IdCtEq::from(CtEq(*b"abcd")))));
+
+    let redacted = IdRedacted::<ApiKey, CtEq<[u8; 4]>>::from(Redacted(CtEq(*b"abcd")));
+    assert!(redacted == IdRedacted::from(Redacted(CtEq(*b"abcd"))));
+    assert!(redacted != IdRedacted::from(Redacted(CtEq(*b"abcx"))));
+}
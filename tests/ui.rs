@@ -0,0 +1,45 @@
+// Copyright 2024 Peter Lyons Kehl
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Compile-fail tests of the type safety: mixing units or entities, adding instants, copying
+//! `...NoCopy` aliases and calling `default()` on `...NoDefault` aliases. Also snapshots of the
+//! `#[diagnostic::on_unimplemented]` messages: of mixing units in `+` and `-`, of missing opt-ins
+//! and conversions, and of unsupported `Repr`s (and, in `tests/ui/features/`, of feature-gated
+//! traits). The fixtures in `tests/ui/` are shared by the stable run and, through `include!`, by
+//! the `unstable_generic_const_own_type` run in `tests/ui/nightly/` (which has its own `.stderr`
+//! files, since the diagnostics differ). Regenerate the `.stderr` files with
+//! `TRYBUILD=overwrite cargo test --test ui` (with `--features money,serde`, then with
+//! `--features plain`, and the same with `+nightly --features unstable_generic_const_own_type,...`)
+//! after a toolchain upgrade.
+
+#[test]
+fn ui() {
+    let cases = trybuild::TestCases::new();
+    #[cfg(not(feature = "unstable_generic_const_own_type"))]
+    let dir = "tests/ui";
+    #[cfg(feature = "unstable_generic_const_own_type")]
+    let dir = "tests/ui/nightly";
+    // With `plain`, rustc mentions its structs in errors about the flagged types of the same
+    // name, so that the other snapshots would differ.
+    #[cfg(feature = "plain")]
+    cases.compile_fail(format!("{dir}/features/plain.rs"));
+    #[cfg(not(feature = "plain"))]
+    {
+        cases.compile_fail(format!("{dir}/*.rs"));
+        #[cfg(feature = "money")]
+        cases.compile_fail(format!("{dir}/features/money.rs"));
+        #[cfg(feature = "serde")]
+        cases.compile_fail(format!("{dir}/features/serde_unit.rs"));
+    }
+}
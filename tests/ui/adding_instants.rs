@@ -0,0 +1,9 @@
+use phantom_newtype::Instant;
+
+enum SecondsFromEpoch {}
+
+type UnixTime = Instant<SecondsFromEpoch, i64>;
+
+fn main() {
+    let _ = UnixTime::from(1) + UnixTime::from(2);
+}
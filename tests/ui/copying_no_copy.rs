@@ -0,0 +1,17 @@
+use phantom_newtype::{AmountNoCopy, IdNoCopyNoDefault, InstantNoCopy};
+
+enum Meters {}
+
+fn main() {
+    let amount = AmountNoCopy::<Meters, u32>::from(1);
+    let _moved = amount;
+    let _copied = amount;
+
+    let id = IdNoCopyNoDefault::<Meters, u32>::from(2);
+    let _moved = id;
+    let _copied = id;
+
+    let instant = InstantNoCopy::<Meters, u32>::from(3);
+    let _moved = instant;
+    let _copied = instant;
+}
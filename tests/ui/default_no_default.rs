@@ -0,0 +1,9 @@
+use phantom_newtype::{AmountNoDefault, IdNoCopyNoDefault, InstantNoDefault};
+
+enum Meters {}
+
+fn main() {
+    let _ = AmountNoDefault::<Meters, u32>::default();
+    let _ = IdNoCopyNoDefault::<Meters, u32>::default();
+    let _ = InstantNoDefault::<Meters, u32>::default();
+}
@@ -0,0 +1,9 @@
+use phantom_newtype::money::Money;
+use phantom_newtype::WithDisplayer;
+
+enum Points {}
+
+fn main() {
+    let balance = Money::<Points>::from(100);
+    println!("{}", WithDisplayer::<_, Points>::new(balance));
+}
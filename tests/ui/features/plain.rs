@@ -0,0 +1,11 @@
+use phantom_newtype::prelude::plain::{Amount, Id};
+
+enum Recipient {}
+enum Message {}
+enum Seconds {}
+enum Bytes {}
+
+fn main() {
+    assert_eq!(Id::<Recipient, u64>::new(15), Id::<Message, u64>::new(15));
+    let _ = Amount::<Seconds, u64>::new(1) + Amount::<Bytes, u64>::new(2);
+}
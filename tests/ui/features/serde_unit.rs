@@ -0,0 +1,16 @@
+use phantom_newtype::serde_unit::{self, UnitName};
+use phantom_newtype::Amount;
+use serde_json::value::Serializer;
+
+enum Bytes {}
+enum Meters {}
+enum ShoutCase {}
+
+impl UnitName for Meters {
+    const NAME: &'static str = "meters";
+}
+
+fn main() {
+    let _ = serde_unit::serialize(&Amount::<Bytes, u64>::from(1), Serializer);
+    let _ = serde_unit::cased::serialize::<ShoutCase, _, _>(&Amount::<Meters, u64>::from(1), Serializer);
+}
@@ -0,0 +1,23 @@
+use phantom_newtype::{Amount, DebugerOf, Id, TypedFlags, WithDisplayer};
+
+enum Bytes {}
+enum Canister {}
+enum Principal {}
+enum Commit {}
+enum Permission {}
+
+// A stand-in for `debug()` of amounts, ids and instants, which requires this of their unit.
+fn debug<Unit: DebugerOf<T>, T>(_: &T) {}
+
+fn main() {
+    let size = Amount::<Bytes, u64>::from(1024);
+    println!("{}", WithDisplayer::<_, Bytes>::new(size));
+    debug::<Bytes, Amount<Bytes, u64>>(&size);
+
+    let commit = Id::<Commit, [u8; 20]>::from([0; 20]);
+    println!("{}", WithDisplayer::<_, Commit>::new(commit));
+
+    let _ = Id::<Canister, u64>::from(7).upcast::<Principal>();
+
+    println!("{:?}", TypedFlags::<Permission, u8>::empty());
+}
@@ -0,0 +1,12 @@
+use phantom_newtype::Amount;
+
+// These types are just markers and have no semantic meaning.
+enum Apples {}
+enum Oranges {}
+
+fn main() {
+    let three_apples = Amount::<Apples, u64>::from(3);
+    let five_oranges = Amount::<Oranges, u64>::from(5);
+
+    let _ = three_apples + five_oranges;
+}
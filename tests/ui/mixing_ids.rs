@@ -0,0 +1,11 @@
+use phantom_newtype::Id;
+
+enum Recipient {}
+enum Message {}
+
+type RecipientId = Id<Recipient, u64>;
+type MessageId = Id<Message, u64>;
+
+fn main() {
+    assert_eq!(RecipientId::from(15), MessageId::from(15));
+}
@@ -0,0 +1,4 @@
+#![feature(generic_const_exprs)]
+#![allow(incomplete_features)]
+
+include!("../copying_no_copy.rs");
@@ -0,0 +1,4 @@
+#![feature(generic_const_exprs)]
+#![allow(incomplete_features)]
+
+include!("../default_no_default.rs");
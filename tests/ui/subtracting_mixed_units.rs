@@ -0,0 +1,13 @@
+use phantom_newtype::{Amount, Instant};
+
+enum Apples {}
+enum Oranges {}
+enum Seconds {}
+enum Millis {}
+
+fn main() {
+    let _ = Amount::<Apples, u64>::from(5) - Amount::<Oranges, u64>::from(3);
+
+    let _ = Instant::<Seconds, i64>::from(5) - Instant::<Millis, i64>::from(3);
+    let _ = Instant::<Seconds, i64>::from(5) - Amount::<Millis, i64>::from(3);
+}
@@ -0,0 +1,19 @@
+use phantom_newtype::{Amount, To, ToFrom, ToFromMut, ToMut};
+
+enum Meters {}
+enum Feet {}
+
+fn main() {
+    let mut length = Amount::<Meters, u32>::from(3);
+
+    // Without an opt-in of the amount (or of its unit):
+    let _ = To::<Feet>::to(length);
+    let _ = ToMut::<Feet>::to_mut(&mut length);
+    let _ = ToFrom::<Feet>::to(length);
+    let _ = ToFromMut::<Feet>::to_mut(&mut length);
+    let _ = length.cast::<Feet>();
+
+    // Not an amount, id or instant:
+    let _ = ToFrom::<Feet>::to(3u32);
+    let _ = ToFromMut::<Feet>::to_mut(&mut 3u32);
+}
@@ -0,0 +1,31 @@
+use phantom_newtype::repr::{
+    CheckedAdd, CheckedSub, FlagsRepr, FromF64, NonZeroRepr, SortableBytes, ToF64,
+};
+use phantom_newtype::wire::{self, Endian};
+use phantom_newtype::{Amount, NonZeroId, Scalar};
+
+enum User {}
+enum Bytes {}
+
+// Stand-ins for the functions and impls of this crate that require these traits of `Repr`.
+fn checked_add<Repr: CheckedAdd>() {}
+fn checked_sub<Repr: CheckedSub>() {}
+fn non_zero<Repr: NonZeroRepr>() {}
+fn sortable<Repr: SortableBytes>() {}
+fn flags<Repr: FlagsRepr>() {}
+fn to_f64<Repr: ToF64>() {}
+fn from_f64<Repr: FromF64>() {}
+
+fn main() {
+    checked_add::<f64>();
+    checked_sub::<f64>();
+    non_zero::<u64>();
+    sortable::<f64>();
+    flags::<f64>();
+    to_f64::<char>();
+    from_f64::<char>();
+
+    let _: Option<NonZeroId<User, f64>> = None;
+    let _ = Amount::<Bytes, u64>::from(1) * Scalar('2');
+    let _ = wire::read_repr::<f64>(&mut &[0u8; 8][..], Endian::Big);
+}
@@ -0,0 +1,52 @@
+// Copyright 2024 Peter Lyons Kehl
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Amounts, ids and instants are `valuable::Valuable` like their `Repr`, so that `tracing` (built
+//! with `valuable` support) records them as structured, numeric fields: `user_id = id.as_value()`.
+//! (`tracing::field::Value` itself is sealed, but `user_id = id.get()` records the `Repr` too.)
+#![cfg(feature = "valuable")]
+#![cfg_attr(
+    feature = "unstable_generic_const_own_type",
+    feature(generic_const_exprs)
+)]
+#![cfg_attr(
+    feature = "unstable_generic_const_own_type",
+    allow(incomplete_features)
+)]
+
+use phantom_newtype::{Amount, Id, Instant};
+use valuable::{Valuable, Value};
+
+enum User {}
+enum Cents {}
+enum Millis {}
+
+#[test]
+fn as_value() {
+    let id: Id<User, u64> = Id::new(7);
+    let amount: Amount<Cents, i32> = Amount::new(-250);
+    let instant: Instant<Millis, u128> = Instant::new(1_000);
+    assert!(matches!(id.as_value(), Value::U64(7)));
+    assert!(matches!(amount.as_value(), Value::I32(-250)));
+    assert!(matches!(instant.as_value(), Value::U128(1_000)));
+}
+
+#[test]
+fn slice_as_value() {
+    let ids: [Id<User, u8>; 3] = [Id::new(1), Id::new(2), Id::new(3)];
+    match ids.as_value() {
+        Value::Listable(list) => assert_eq!(list.size_hint(), (3, Some(3))),
+        _ => panic!("expected a list"),
+    }
+}
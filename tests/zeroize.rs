@@ -0,0 +1,61 @@
+// Copyright 2024 Peter Lyons Kehl
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Amounts, ids, instants and `repr::Redacted` are `zeroize::Zeroize` and `zeroize::ZeroizeOnDrop`
+//! like their `Repr`.
+#![cfg(feature = "zeroize")]
+#![cfg_attr(
+    feature = "unstable_generic_const_own_type",
+    feature(generic_const_exprs)
+)]
+#![cfg_attr(
+    feature = "unstable_generic_const_own_type",
+    allow(incomplete_features)
+)]
+
+use phantom_newtype::repr::Redacted;
+use phantom_newtype::{Amount, IdNoCopy, IdRedacted, Instant};
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
+
+enum ApiKey {}
+enum Meters {}
+
+fn assert_zeroize_on_drop<T: ZeroizeOnDrop>() {}
+
+#[test]
+fn zeroize_delegates_to_repr() {
+    let mut key = IdNoCopy::<ApiKey, [u8; 4]>::from(*b"abcd");
+    key.zeroize();
+    assert_eq!(key.get(), &[0; 4]);
+
+    let mut redacted = IdRedacted::<ApiKey, [u8; 4]>::from(Redacted(*b"abcd"));
+    redacted.zeroize();
+    assert_eq!(redacted.get().0, [0; 4]);
+
+    let mut amount = Amount::<Meters, u64>::from(7);
+    amount.zeroize();
+    assert_eq!(amount, Amount::from(0));
+
+    let mut instant = Instant::<Meters, i32>::from(-7);
+    instant.zeroize();
+    assert_eq!(instant, Instant::from(0));
+}
+
+#[test]
+fn zeroize_on_drop_delegates_to_repr() {
+    assert_zeroize_on_drop::<IdNoCopy<ApiKey, Zeroizing<[u8; 32]>>>();
+    assert_zeroize_on_drop::<IdRedacted<ApiKey, Zeroizing<[u8; 32]>>>();
+    assert_zeroize_on_drop::<Amount<Meters, Zeroizing<u64>>>();
+    assert_zeroize_on_drop::<Instant<Meters, Zeroizing<u64>>>();
+}